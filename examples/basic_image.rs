@@ -1,6 +1,5 @@
 use futures::executor::block_on;
 use g2d::{Handle, Texture};
-use image::RgbaImage;
 
 async fn run() {
     let handle = g2d::WindowlessHandle::new().await.unwrap();
@@ -14,10 +13,7 @@ async fn run() {
     // Load the pixels from the texture
     let pixels = texture.canvas().pixels().await.unwrap();
 
-    RgbaImage::from_raw(16, 16, pixels.into_vec())
-        .unwrap()
-        .save("test.png")
-        .unwrap();
+    pixels.save("test.png").unwrap();
 }
 
 fn main() {
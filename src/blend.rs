@@ -0,0 +1,58 @@
+/// How the output of a draw is combined with the existing contents of a
+/// [Texture](crate::Texture).
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum BlendMode {
+    /// Overwrites the destination, ignoring alpha.
+    #[default]
+    Replace,
+
+    /// Draws over the destination, using the alpha of the output.
+    SourceOver,
+
+    /// Adds the output to the destination.  Useful for lights and particles.
+    Additive,
+}
+
+impl BlendMode {
+    /// Returns the [BlendMode] of draws which fade their edges out with alpha, such as shapes,
+    /// text and sprites: this one, except [BlendMode::Replace] draws over the destination, as
+    /// replacing it would punch transparent holes around them.
+    #[inline]
+    pub(crate) const fn blending(self) -> Self {
+        match self {
+            Self::Replace => Self::SourceOver,
+            _ => self,
+        }
+    }
+
+    /// Converts this [BlendMode] to a [`wgpu::BlendState`].
+    ///
+    /// *premultiplied* should be `true` if the shader outputs colors with premultiplied alpha.
+    pub const fn to_wgpu_blend_state(&self, premultiplied: bool) -> wgpu::BlendState {
+        let src_factor = if premultiplied {
+            wgpu::BlendFactor::One
+        } else {
+            wgpu::BlendFactor::SrcAlpha
+        };
+
+        match self {
+            Self::Replace => wgpu::BlendState::REPLACE,
+            Self::SourceOver => wgpu::BlendState {
+                color: wgpu::BlendComponent {
+                    src_factor,
+                    dst_factor: wgpu::BlendFactor::OneMinusSrcAlpha,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha: wgpu::BlendComponent::OVER,
+            },
+            Self::Additive => wgpu::BlendState {
+                color: wgpu::BlendComponent {
+                    src_factor,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha: wgpu::BlendComponent::OVER,
+            },
+        }
+    }
+}
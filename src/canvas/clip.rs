@@ -0,0 +1,326 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+
+use crate::{
+    path::FLATTEN_TOLERANCE, BlendMode, Canvas, FillRule, Handle, Path, PipelineKey,
+    PipelineShader, Rect, StencilMode, Texture, Vec2,
+};
+
+/// The positions of the samples the clip mask is rendered with, relative to the center of each
+/// pixel: a lattice spreading them evenly, with no two samples on the same row or column.
+///
+/// Their number must match `SAMPLE_COUNT` in `clip_path.wgsl`.
+const CLIP_MASK_SAMPLES: [Vec2; 16] = [
+    Vec2::new(-0.46875, -0.46875),
+    Vec2::new(-0.40625, -0.15625),
+    Vec2::new(-0.34375, 0.15625),
+    Vec2::new(-0.28125, 0.46875),
+    Vec2::new(-0.21875, -0.21875),
+    Vec2::new(-0.15625, 0.09375),
+    Vec2::new(-0.09375, 0.40625),
+    Vec2::new(-0.03125, -0.28125),
+    Vec2::new(0.03125, 0.03125),
+    Vec2::new(0.09375, 0.34375),
+    Vec2::new(0.15625, -0.34375),
+    Vec2::new(0.21875, -0.03125),
+    Vec2::new(0.28125, 0.28125),
+    Vec2::new(0.34375, -0.40625),
+    Vec2::new(0.40625, -0.09375),
+    Vec2::new(0.46875, 0.21875),
+];
+
+/// The format of the clip mask, which holds the coverage of the clip paths.
+const CLIP_MASK_WGPU_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R8Unorm;
+
+/// The source of [ClipPath] ids.
+static NEXT_CLIP_PATH_ID: AtomicU64 = AtomicU64::new(0);
+
+/// A path added to the clip of a [Canvas].
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ClipPath {
+    /// Uniquely identifies this clip path, used to tell if the clip mask is up to date.
+    id: u64,
+
+    /// The path, triangulated as fans (see `Path::fan_triangles`).
+    triangles: Arc<[Vec2]>,
+    fill_rule: FillRule,
+}
+
+impl<'a, H: Handle> Canvas<'a, H> {
+    /// Restricts all following draws to the provided rectangle, in pixels.
+    ///
+    /// The rectangle is intersected with the current clip, so nested clips can only shrink the
+    /// drawable area.  Use [Canvas::save] and [Canvas::restore] to undo a clip.
+    pub fn clip_rect(&mut self, rect: Rect) {
+        self.state.clip = Some(match self.state.clip {
+            Some(clip) => clip.intersection(&rect),
+            None => rect,
+        });
+    }
+
+    /// Restricts all following draws to the inside of the provided [Path], in pixels.
+    ///
+    /// Like [Canvas::clip_rect], the path is intersected with the current clip.  The edges of
+    /// path clips are anti-aliased with 16 samples per pixel, and pixels outside of them are
+    /// left untouched whatever the [BlendMode].
+    pub fn clip_path(&mut self, path: &Path, fill_rule: FillRule) {
+        self.clip_rect(path.bounds());
+        self.state.clip_paths.push(ClipPath {
+            id: NEXT_CLIP_PATH_ID.fetch_add(1, Ordering::Relaxed),
+            triangles: path.fan_triangles(FLATTEN_TOLERANCE).into(),
+            fill_rule,
+        });
+    }
+
+    /// Creates the bind group giving the provided pipeline the clip mask of this [Canvas], in
+    /// group `1`.  Returns `None` if there are no clip paths.
+    ///
+    /// The clip mask is rendered first if it doesn't hold the current clip paths.
+    pub(super) fn create_wgpu_clip_bind_group(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        wgpu_pipeline: &wgpu::RenderPipeline,
+    ) -> Option<wgpu::BindGroup> {
+        if self.state.clip_paths.is_empty() {
+            return None;
+        }
+
+        let wgpu_device = self.handle().wgpu_device();
+        let wgpu_clip_mask_texture = self.wgpu_clip_mask_texture.get_or_init(|| {
+            wgpu_device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("Clip Mask Texture"),
+                size: self.size().to_extent_3d(),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: CLIP_MASK_WGPU_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            })
+        });
+        let wgpu_clip_mask_view =
+            wgpu_clip_mask_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let clip_path_ids: Vec<u64> = self.state.clip_paths.iter().map(|clip| clip.id).collect();
+        if *self.clip_mask_path_ids.borrow() != clip_path_ids {
+            self.render_clip_mask(encoder, &wgpu_clip_mask_view);
+            *self.clip_mask_path_ids.borrow_mut() = clip_path_ids;
+        }
+
+        Some(wgpu_device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &wgpu_pipeline.get_bind_group_layout(1),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&wgpu_clip_mask_view),
+            }],
+        }))
+    }
+
+    /// Renders the coverage of the intersection of the current clip paths to the clip mask.
+    ///
+    /// The clip paths are written to the stencil buffer and covered once per sample, each time
+    /// moved by the offset of the sample, so every sample adds its share of the coverage.
+    fn render_clip_mask(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        wgpu_clip_mask_view: &wgpu::TextureView,
+    ) {
+        let wgpu_device = self.handle().wgpu_device();
+        let size = self.size();
+        let pipeline = |stencil| {
+            self.handle().pipeline_cache().get(
+                wgpu_device,
+                PipelineKey {
+                    shader: PipelineShader::ClipPath,
+                    // Only the cover pass writes colors, adding to the coverage.
+                    blend_mode: BlendMode::Additive,
+                    format: CLIP_MASK_WGPU_FORMAT,
+                    sample_count: 1,
+                    stencil,
+                    clipped: false,
+                },
+            )
+        };
+        let wind_non_zero_pipeline = pipeline(StencilMode::WindNonZero);
+        let wind_even_odd_pipeline = pipeline(StencilMode::WindEvenOdd);
+        let intersect_pipeline = pipeline(StencilMode::Intersect);
+        let reset_winding_pipeline = pipeline(StencilMode::ResetWinding);
+        let cover_pipeline = pipeline(StencilMode::Cover);
+
+        let wgpu_stencil_texture = self.wgpu_stencil_texture.get_or_init(|| {
+            wgpu_device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("Clip Stencil Texture"),
+                size: size.to_extent_3d(),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: StencilMode::WGPU_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            })
+        });
+        let wgpu_stencil_view =
+            wgpu_stencil_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // All the pipelines share the same shader, so their bind group layouts are compatible.
+        let wgpu_viewport_buffer = self.create_wgpu_viewport_buffer();
+        let wgpu_bind_group = wgpu_device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &wind_non_zero_pipeline.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu_viewport_buffer.as_entire_binding(),
+            }],
+        });
+
+        // A quad covering the whole texture for the passes which touch every pixel, with a
+        // margin so it still does once moved by a sample offset.
+        let (width, height) = (size.width as f32 + 1.0, size.height as f32 + 1.0);
+        let mut vertices = vec![
+            Vec2::new(-1.0, -1.0),
+            Vec2::new(width, -1.0),
+            Vec2::new(-1.0, height),
+            Vec2::new(-1.0, height),
+            Vec2::new(width, -1.0),
+            Vec2::new(width, height),
+        ];
+        let path_ranges: Vec<_> = self
+            .state
+            .clip_paths
+            .iter()
+            .map(|clip| {
+                let start = vertices.len() as u32;
+                vertices.extend_from_slice(&clip.triangles);
+                start..vertices.len() as u32
+            })
+            .collect();
+        let wgpu_vertex_buffer = wgpu_device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::cast_slice(&vertices),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let wgpu_sample_buffer = wgpu_device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::cast_slice(&CLIP_MASK_SAMPLES),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Clip Mask Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: wgpu_clip_mask_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &wgpu_stencil_view,
+                depth_ops: None,
+                stencil_ops: Some(wgpu::Operations {
+                    // Every sample starts inside the clip.
+                    load: wgpu::LoadOp::Clear(StencilMode::INSIDE),
+                    store: wgpu::StoreOp::Discard,
+                }),
+            }),
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_bind_group(0, &wgpu_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, wgpu_vertex_buffer.slice(..));
+
+        // The single instance of every draw is the sample the clip paths are moved by.
+        let sample_size = std::mem::size_of::<Vec2>() as wgpu::BufferAddress;
+        for sample in 0..CLIP_MASK_SAMPLES.len() as wgpu::BufferAddress {
+            let sample_offset = sample * sample_size;
+            render_pass.set_vertex_buffer(
+                1,
+                wgpu_sample_buffer.slice(sample_offset..sample_offset + sample_size),
+            );
+            render_pass.set_stencil_reference(0);
+
+            for (clip, range) in self.state.clip_paths.iter().zip(&path_ranges) {
+                // Count the winding of the path...
+                render_pass.set_pipeline(match clip.fill_rule {
+                    FillRule::NonZero => &wind_non_zero_pipeline,
+                    FillRule::EvenOdd => &wind_even_odd_pipeline,
+                });
+                render_pass.draw(range.clone(), 0..1);
+
+                // ...remove everything outside of it from the clip...
+                render_pass.set_pipeline(&intersect_pipeline);
+                render_pass.draw(0..6, 0..1);
+
+                // ...and start over for the next path.
+                render_pass.set_pipeline(&reset_winding_pipeline);
+                render_pass.draw(0..6, 0..1);
+            }
+
+            // Add the coverage of the sample, putting every sample back inside for the next one.
+            render_pass.set_stencil_reference(StencilMode::INSIDE);
+            render_pass.set_pipeline(&cover_pipeline);
+            render_pass.draw(0..6, 0..1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        canvas::tests::{handle, pixel},
+        Color, Dimension, Paint, Shape, ShapeStyle, Vertex,
+    };
+
+    #[test]
+    fn clip_path_leaves_the_outside_untouched() {
+        let handle = handle();
+        let texture = handle.make_blank_texture(Dimension::new(32, 32));
+        let mut canvas = texture.canvas();
+        canvas.clear(Color::RED).unwrap();
+        canvas.clip_path(
+            &Path::circle(Vec2::new(16.0, 16.0), 10.0),
+            FillRule::NonZero,
+        );
+
+        // Vertices replace the destination by default, shapes draw over it.  Either way, the
+        // corners of the bounds of the circle, which the clip rectangle doesn't exclude, must keep
+        // the background.
+        let quad = [
+            [-1.0, -1.0],
+            [1.0, -1.0],
+            [1.0, 1.0],
+            [-1.0, -1.0],
+            [1.0, 1.0],
+            [-1.0, 1.0],
+        ]
+        .map(|[x, y]| Vertex::new(Vec2::new(x, y), Vec2::default(), Color::BLUE));
+        canvas
+            .draw_vertices(&handle.make_vertex_buffer(&quad), Paint::Fill)
+            .unwrap();
+        assert_eq!(pixel(&canvas, 16, 16), [0, 0, 255, 255]);
+        assert_eq!(pixel(&canvas, 7, 7), [255, 0, 0, 255]);
+        assert_eq!(pixel(&canvas, 24, 24), [255, 0, 0, 255]);
+
+        canvas
+            .draw_shape(
+                Shape::Circle {
+                    center: Vec2::new(16.0, 16.0),
+                    radius: 16.0,
+                },
+                ShapeStyle::fill(Color::LIME),
+            )
+            .unwrap();
+        assert_eq!(pixel(&canvas, 16, 16), [0, 255, 0, 255]);
+        assert_eq!(pixel(&canvas, 7, 24), [255, 0, 0, 255]);
+        assert_eq!(pixel(&canvas, 24, 7), [255, 0, 0, 255]);
+    }
+}
@@ -0,0 +1,486 @@
+use std::sync::Arc;
+
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+
+use crate::{
+    filter::{apply_wgpu_filters, create_wgpu_filter_texture},
+    BlendMode, Canvas, Dimension, Error, Filter, Handle, PipelineShader, Rect, Texture, Transform,
+    Vec2,
+};
+
+/// How the pixels of a mask are turned into coverage, see [Canvas::push_mask].
+///
+/// Follows the semantics of the SVG `mask-type` property.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum MaskMode {
+    /// The coverage is the alpha of the mask.
+    Alpha,
+
+    /// The coverage is the luminance of the mask, multiplied by its alpha.
+    ///
+    /// The colors of the mask are expected to be premultiplied, as they are when drawn into a
+    /// transparent [Texture] with G2d.
+    #[default]
+    Luminance,
+}
+
+/// The uniform of the layer mask shader.
+#[derive(bytemuck::Zeroable, bytemuck::Pod, Clone, Copy, Debug)]
+#[repr(C)]
+struct MaskUniform {
+    to_mask: [[f32; 4]; 3],
+    size: [f32; 2],
+    mode: u32,
+    srgb: u32,
+}
+
+/// The uniform of the layer composite shader.
+#[derive(bytemuck::Zeroable, bytemuck::Pod, Clone, Copy, Debug)]
+#[repr(C)]
+struct CompositeUniform {
+    origin: [i32; 2],
+}
+
+/// What happens to a [Layer] when it is popped.
+#[derive(Debug)]
+enum LayerEffect {
+    /// The layer is composited through a mask.
+    Mask {
+        wgpu_mask_view: wgpu::TextureView,
+        mask_format: wgpu::TextureFormat,
+        mask_size: Dimension,
+        mode: MaskMode,
+
+        /// Maps pixels of the mask to pixels of the [Canvas].
+        transform: Transform,
+    },
+
+    /// The layer is filtered, then composited.
+    Filters(Vec<Filter>),
+}
+
+/// An offscreen texture which draws are redirected to until it is popped and composited onto the
+/// texture below it.
+#[derive(Debug)]
+pub(crate) struct Layer {
+    wgpu_texture: wgpu::Texture,
+    effect: LayerEffect,
+}
+
+impl<'a, H: Handle> Canvas<'a, H> {
+    /// Starts drawing through a mask.  Every draw until the matching [Canvas::pop_mask] is
+    /// modulated by the coverage of the mask, as with an SVG `<mask>`.
+    ///
+    /// *transform* maps the pixels of the mask to the pixels of this [Canvas].  Nothing outside of
+    /// the transformed mask is drawn.  Masks can be nested, in which case their coverages
+    /// multiply.
+    ///
+    /// Any [Texture] can be a mask, including one previously drawn to with G2d.
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    /// - Fails if the mask does not have the `TEXTURE_BINDING` usage.
+    pub fn push_mask(
+        &mut self,
+        mask: &impl Texture<H>,
+        mode: MaskMode,
+        transform: Transform,
+    ) -> Result<(), Error> {
+        self.check_render_attachment()?;
+        if !mask
+            .wgpu_texture_usage()
+            .contains(wgpu::TextureUsages::TEXTURE_BINDING)
+        {
+            return Err(Error::LackingTextureUsage(
+                wgpu::TextureUsages::TEXTURE_BINDING,
+            ));
+        }
+
+        self.push_layer_texture(LayerEffect::Mask {
+            wgpu_mask_view: mask
+                .wgpu_texture()
+                .create_view(&wgpu::TextureViewDescriptor::default()),
+            mask_format: mask.wgpu_texture_format(),
+            mask_size: mask.size(),
+            mode,
+            transform,
+        });
+
+        Ok(())
+    }
+
+    /// Stops drawing through the mask pushed last by [Canvas::push_mask], compositing everything
+    /// drawn since onto the texture below.
+    ///
+    /// Compositing draws over the texture below with the current [BlendMode] and clip, so what
+    /// was drawn before [Canvas::push_mask] shows through wherever the mask or the layer is
+    /// transparent.  [BlendMode::Replace] composites like [BlendMode::SourceOver].
+    ///
+    /// # Fails
+    /// - Fails if no mask was pushed.
+    pub fn pop_mask(&mut self) -> Result<(), Error> {
+        let Some(Layer {
+            effect: LayerEffect::Mask { .. },
+            ..
+        }) = self.layers.last()
+        else {
+            return Err(Error::UnbalancedLayer);
+        };
+        let layer = self.layers.pop().unwrap();
+        let LayerEffect::Mask {
+            wgpu_mask_view,
+            mask_format,
+            mask_size,
+            mode,
+            transform,
+        } = &layer.effect
+        else {
+            unreachable!()
+        };
+
+        // A mask which collapses to a line covers nothing, so map every pixel outside of it.
+        let to_mask = transform.inverse().unwrap_or(Transform::new(
+            Vec2::default(),
+            Vec2::default(),
+            Vec2::new(-1.0, -1.0),
+        ));
+        let uniform = MaskUniform {
+            to_mask: to_mask.to_wgpu_mat3(),
+            size: [mask_size.width as f32, mask_size.height as f32],
+            mode: match mode {
+                MaskMode::Alpha => 0,
+                MaskMode::Luminance => 1,
+            },
+            srgb: mask_format.is_srgb() as u32,
+        };
+
+        let wgpu_device = self.handle().wgpu_device();
+        let wgpu_pipeline = self.wgpu_render_pipeline(PipelineShader::LayerMask);
+
+        let wgpu_uniform_buffer = wgpu_device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::bytes_of(&uniform),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let wgpu_layer_view = layer
+            .wgpu_texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let wgpu_sampler = wgpu_device.create_sampler(&wgpu::SamplerDescriptor {
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let wgpu_bind_group = wgpu_device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &wgpu_pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu_uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&wgpu_layer_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(wgpu_mask_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 3,
+                    resource: wgpu::BindingResource::Sampler(&wgpu_sampler),
+                },
+            ],
+        });
+
+        self.draw_fullscreen(&wgpu_pipeline, &wgpu_bind_group, self.scissor_rect());
+        Ok(())
+    }
+
+    /// Starts drawing into a new layer.  Everything drawn until the matching [Canvas::pop_layer]
+    /// has the provided [Filters](Filter) applied to it as a whole, like a CSS `filter`.
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    pub fn push_layer(&mut self, filters: &[Filter]) -> Result<(), Error> {
+        self.check_render_attachment()?;
+        self.push_layer_texture(LayerEffect::Filters(filters.to_vec()));
+        Ok(())
+    }
+
+    /// Stops drawing into the layer pushed last by [Canvas::push_layer], filtering it and
+    /// compositing it onto the texture below.
+    ///
+    /// Compositing draws over the texture below with the current [BlendMode] and clip, like
+    /// [Canvas::pop_mask].
+    ///
+    /// # Fails
+    /// - Fails if no layer was pushed, or if the last push was a mask.
+    pub fn pop_layer(&mut self) -> Result<(), Error> {
+        let Some(Layer {
+            effect: LayerEffect::Filters(_),
+            ..
+        }) = self.layers.last()
+        else {
+            return Err(Error::UnbalancedLayer);
+        };
+        let layer = self.layers.pop().unwrap();
+        let LayerEffect::Filters(filters) = &layer.effect else {
+            unreachable!()
+        };
+
+        let filtered = apply_wgpu_filters(self.handle(), &layer.wgpu_texture, filters);
+        self.draw_layer_texture(
+            &filtered,
+            [0, 0],
+            self.state.blend_mode.blending(),
+            self.scissor_rect(),
+        );
+        Ok(())
+    }
+
+    /// Applies [Filters](Filter) to what has already been drawn inside *rect*, like a CSS
+    /// `backdrop-filter`.  Useful for frosted glass, by blurring the backdrop before drawing a
+    /// translucent panel over it.
+    ///
+    /// Only pixels inside both *rect* and the clip change, but the filters can read pixels
+    /// outside of them, e.g. for the edges of a blur.
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` and `COPY_SRC` usages.
+    pub fn backdrop_filter(&self, rect: Rect, filters: &[Filter]) -> Result<(), Error> {
+        self.check_render_attachment()?;
+        if !self
+            .wgpu_target_texture()
+            .usage()
+            .contains(wgpu::TextureUsages::COPY_SRC)
+        {
+            return Err(Error::LackingTextureUsage(wgpu::TextureUsages::COPY_SRC));
+        }
+
+        let Some(scissor_rect @ [x, y, width, height]) = self.scissor_rect_within(rect) else {
+            return Ok(());
+        };
+
+        // Copy the area the filters read from.
+        let size = self.size();
+        let margin = filters.iter().map(Filter::margin).sum::<f32>().ceil() as u32;
+        let (left, top) = (x.saturating_sub(margin), y.saturating_sub(margin));
+        let (right, bottom) = (
+            (x + width + margin).min(size.width),
+            (y + height + margin).min(size.height),
+        );
+        let backdrop_size = Dimension::new(right - left, bottom - top);
+        let wgpu_backdrop_texture = create_wgpu_filter_texture(
+            self.handle(),
+            backdrop_size,
+            self.wgpu_target_texture().format(),
+        );
+
+        let mut encoder = self
+            .handle()
+            .wgpu_device()
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        encoder.copy_texture_to_texture(
+            wgpu::ImageCopyTexture {
+                texture: self.wgpu_target_texture(),
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: left,
+                    y: top,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu_backdrop_texture.as_image_copy(),
+            backdrop_size.to_extent_3d(),
+        );
+        self.handle()
+            .wgpu_queue()
+            .submit(std::iter::once(encoder.finish()));
+
+        let filtered = apply_wgpu_filters(self.handle(), &wgpu_backdrop_texture, filters);
+        self.draw_layer_texture(
+            &filtered,
+            [left as i32, top as i32],
+            BlendMode::Replace,
+            Some(scissor_rect),
+        );
+        Ok(())
+    }
+
+    /// Draws a premultiplied texture onto the current target, with its top-left corner at
+    /// *origin*, in pixels.
+    fn draw_layer_texture(
+        &self,
+        wgpu_texture: &wgpu::Texture,
+        origin: [i32; 2],
+        blend_mode: BlendMode,
+        scissor_rect: Option<[u32; 4]>,
+    ) {
+        let wgpu_device = self.handle().wgpu_device();
+        let wgpu_pipeline =
+            self.wgpu_render_pipeline_with_blend_mode(PipelineShader::LayerComposite, blend_mode);
+
+        let wgpu_uniform_buffer = wgpu_device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::bytes_of(&CompositeUniform { origin }),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let wgpu_texture_view = wgpu_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let wgpu_bind_group = wgpu_device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &wgpu_pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu_uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&wgpu_texture_view),
+                },
+            ],
+        });
+
+        self.draw_fullscreen(&wgpu_pipeline, &wgpu_bind_group, scissor_rect);
+    }
+
+    /// Draws a triangle covering the whole current target with a pipeline whose shader generates
+    /// its vertices, restricted to the scissor rectangle and clip paths.
+    fn draw_fullscreen(
+        &self,
+        wgpu_pipeline: &Arc<wgpu::RenderPipeline>,
+        wgpu_bind_group: &wgpu::BindGroup,
+        scissor_rect: Option<[u32; 4]>,
+    ) {
+        let Some(scissor_rect) = scissor_rect else {
+            return;
+        };
+
+        let mut encoder = self
+            .handle()
+            .wgpu_device()
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+        let wgpu_texture_view = self.create_wgpu_target_view();
+        let wgpu_clip_bind_group = self.create_wgpu_clip_bind_group(&mut encoder, wgpu_pipeline);
+
+        {
+            let mut render_pass =
+                self.begin_render_pass(&mut encoder, &wgpu_texture_view, scissor_rect);
+
+            render_pass.set_pipeline(wgpu_pipeline);
+            render_pass.set_bind_group(0, wgpu_bind_group, &[]);
+            if let Some(wgpu_clip_bind_group) = &wgpu_clip_bind_group {
+                render_pass.set_bind_group(1, wgpu_clip_bind_group, &[]);
+            }
+            render_pass.draw(0..3, 0..1);
+        }
+
+        // Submit to be drawn.
+        self.handle()
+            .wgpu_queue()
+            .submit(std::iter::once(encoder.finish()));
+    }
+
+    /// Returns the [`wgpu::Texture`] draws currently go to: the top layer, or the texture of this
+    /// [Canvas] if there are no layers.
+    #[inline]
+    pub(super) fn wgpu_target_texture(&self) -> &wgpu::Texture {
+        self.layers
+            .last()
+            .map_or(self.wgpu_texture, |layer| &layer.wgpu_texture)
+    }
+
+    /// Creates a view of the first mip level of [Canvas::wgpu_target_texture], the only level
+    /// draws go to.
+    pub(super) fn create_wgpu_target_view(&self) -> wgpu::TextureView {
+        self.wgpu_target_texture()
+            .create_view(&wgpu::TextureViewDescriptor {
+                base_mip_level: 0,
+                mip_level_count: Some(1),
+                ..Default::default()
+            })
+    }
+
+    /// Pushes a new transparent layer, redirecting draws to it.
+    fn push_layer_texture(&mut self, effect: LayerEffect) {
+        // wgpu zero-initializes textures, so the layer starts out transparent.
+        let wgpu_texture = create_wgpu_filter_texture(
+            self.handle(),
+            self.size(),
+            self.wgpu_target_texture().format(),
+        );
+
+        self.layers.push(Layer {
+            wgpu_texture,
+            effect,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        canvas::tests::{handle, pixel},
+        Color, CornerRadii, Shape, ShapeStyle,
+    };
+
+    const BACKGROUND: [u8; 4] = [255, 0, 0, 255];
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> Shape {
+        Shape::RoundedRect {
+            rect: Rect::new(x, y, width, height),
+            radii: CornerRadii::uniform(0.0),
+        }
+    }
+
+    #[test]
+    fn masks_keep_the_pixels_beneath() {
+        let handle = handle();
+        let mask = handle.make_blank_texture(Dimension::new(32, 32));
+        mask.canvas()
+            .draw_shape(rect(0.0, 0.0, 16.0, 32.0), ShapeStyle::fill(Color::WHITE))
+            .unwrap();
+
+        let texture = handle.make_blank_texture(Dimension::new(32, 32));
+        let mut canvas = texture.canvas();
+        canvas.clear(Color::RED).unwrap();
+        canvas
+            .push_mask(&mask, MaskMode::Alpha, Transform::IDENTITY)
+            .unwrap();
+        canvas
+            .draw_shape(rect(0.0, 0.0, 32.0, 24.0), ShapeStyle::fill(Color::BLUE))
+            .unwrap();
+        canvas.pop_mask().unwrap();
+
+        assert_eq!(pixel(&canvas, 4, 4), [0, 0, 255, 255]);
+        // Outside of the mask...
+        assert_eq!(pixel(&canvas, 28, 4), BACKGROUND);
+        // ...and outside of what was drawn in the layer.
+        assert_eq!(pixel(&canvas, 4, 28), BACKGROUND);
+    }
+
+    #[test]
+    fn layers_keep_the_pixels_beneath() {
+        let handle = handle();
+        let texture = handle.make_blank_texture(Dimension::new(32, 32));
+        let mut canvas = texture.canvas();
+        canvas.clear(Color::RED).unwrap();
+        canvas.push_layer(&[]).unwrap();
+        canvas
+            .draw_shape(
+                rect(8.0, 8.0, 16.0, 16.0),
+                ShapeStyle::fill(Color::BLUE.with_alpha(0.5)),
+            )
+            .unwrap();
+        canvas.pop_layer().unwrap();
+
+        // The translucent blue shows the red beneath.
+        let [red, green, blue, alpha] = pixel(&canvas, 16, 16);
+        assert!(red > 0 && green == 0 && blue > 0 && alpha == 255);
+        assert_eq!(pixel(&canvas, 4, 4), BACKGROUND);
+    }
+}
@@ -0,0 +1,572 @@
+mod clip;
+mod layer;
+mod shadow;
+mod shape;
+mod sprite;
+mod state;
+mod text;
+
+pub(crate) use clip::*;
+pub use layer::*;
+pub use state::*;
+
+use std::{
+    cell::{OnceCell, RefCell},
+    sync::Arc,
+};
+
+use wgpu::{
+    util::{BufferInitDescriptor, DeviceExt},
+    TextureUsages,
+};
+
+use crate::{
+    paint::PaintUniform, BlendMode, Color, Error, Handle, Paint, PipelineKey, PipelineShader,
+    PixelRect, Pixels, Rect, StencilMode, Texture, TextureFormat, Vec2, VertexBuffer,
+};
+
+/// A view into a [Texture] used for reading or writing to it.
+#[derive(Debug)]
+pub struct Canvas<'a, H: Handle> {
+    handle: &'a H,
+    wgpu_texture: &'a wgpu::Texture,
+
+    /// The current drawing state.
+    state: CanvasState,
+
+    /// The states pushed by [Canvas::save].
+    saved_states: Vec<CanvasState>,
+
+    /// The coverage of the clip paths, multiplied into every draw.  Created when a clip path is
+    /// first used.
+    wgpu_clip_mask_texture: OnceCell<wgpu::Texture>,
+
+    /// The stencil buffer the clip mask is rendered through.
+    wgpu_stencil_texture: OnceCell<wgpu::Texture>,
+
+    /// The ids of the clip paths currently rendered to the clip mask.
+    clip_mask_path_ids: RefCell<Vec<u64>>,
+
+    /// The layers pushed by [Canvas::push_mask] and [Canvas::push_layer].
+    layers: Vec<Layer>,
+}
+
+impl<'a, H: Handle> Canvas<'a, H> {
+    /// Creates an [Canvas] from its raw parts.
+    ///
+    /// NOTE: the provided [`wgpu::Texture`] should have been created from the provided [Handle].
+    #[inline]
+    pub fn from_raw_parts(handle: &'a H, wgpu_texture: &'a wgpu::Texture) -> Self {
+        Self {
+            handle,
+            wgpu_texture,
+            state: CanvasState::default(),
+            saved_states: Vec::new(),
+            wgpu_clip_mask_texture: OnceCell::new(),
+            wgpu_stencil_texture: OnceCell::new(),
+            clip_mask_path_ids: RefCell::new(Vec::new()),
+            layers: Vec::new(),
+        }
+    }
+
+    /// Returns an error if this [Canvas] can't be drawn to.
+    fn check_render_attachment(&self) -> Result<(), Error> {
+        if !self
+            .wgpu_texture_usage()
+            .contains(TextureUsages::RENDER_ATTACHMENT)
+        {
+            return Err(Error::LackingTextureUsage(TextureUsages::RENDER_ATTACHMENT));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the [`wgpu::RenderPipeline`] for drawing to this [Canvas] with the provided shader.
+    ///
+    /// The pipeline is chosen for the format and sample count of the underlying
+    /// [`wgpu::Texture`], and blends with the current [BlendMode], drawing over the destination
+    /// instead of replacing it (see `BlendMode::blending`).
+    #[inline]
+    fn wgpu_render_pipeline(&self, shader: PipelineShader) -> Arc<wgpu::RenderPipeline> {
+        self.wgpu_render_pipeline_with_blend_mode(shader, self.state.blend_mode.blending())
+    }
+
+    /// Like `wgpu_render_pipeline`, but with the provided [BlendMode] instead of the current one.
+    fn wgpu_render_pipeline_with_blend_mode(
+        &self,
+        shader: PipelineShader,
+        blend_mode: BlendMode,
+    ) -> Arc<wgpu::RenderPipeline> {
+        self.handle().pipeline_cache().get(
+            self.handle().wgpu_device(),
+            PipelineKey {
+                shader,
+                blend_mode,
+                format: self.wgpu_target_texture().format(),
+                sample_count: self.wgpu_target_texture().sample_count(),
+                stencil: StencilMode::None,
+                clipped: !self.state.clip_paths.is_empty(),
+            },
+        )
+    }
+
+    /// Creates a uniform buffer holding the size of this [Canvas], used by shaders to convert
+    /// pixels to clip space.
+    fn create_wgpu_viewport_buffer(&self) -> wgpu::Buffer {
+        self.handle()
+            .wgpu_device()
+            .create_buffer_init(&BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&[
+                    self.size().width as f32,
+                    self.size().height as f32,
+                ]),
+                usage: wgpu::BufferUsages::UNIFORM,
+            })
+    }
+
+    /// Creates the bind group of a shader filling shapes with a [Paint]: the [PaintUniform] at
+    /// binding 0 followed by *entries*, which are shifted one binding up.
+    fn create_wgpu_paint_bind_group(
+        &self,
+        wgpu_pipeline: &wgpu::RenderPipeline,
+        paint_uniform: &PaintUniform,
+        entries: &[wgpu::BindingResource<'_>],
+    ) -> wgpu::BindGroup {
+        let wgpu_device = self.handle().wgpu_device();
+        let wgpu_paint_buffer = wgpu_device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::bytes_of(paint_uniform),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+
+        let entries: Vec<_> = std::iter::once(wgpu_paint_buffer.as_entire_binding())
+            .chain(entries.iter().cloned())
+            .zip(0..)
+            .map(|(resource, binding)| wgpu::BindGroupEntry { binding, resource })
+            .collect();
+        wgpu_device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &wgpu_pipeline.get_bind_group_layout(0),
+            entries: &entries,
+        })
+    }
+
+    /// Returns the scissor rectangle of the current clip, in whole pixels, as `[x, y, width,
+    /// height]`.
+    ///
+    /// Returns [None] if the clip doesn't cover any pixels, in which case nothing should be drawn.
+    #[inline]
+    fn scissor_rect(&self) -> Option<[u32; 4]> {
+        let size = self.size();
+        self.scissor_rect_within(Rect::new(0.0, 0.0, size.width as f32, size.height as f32))
+    }
+
+    /// Converts a scissor rectangle (see `scissor_rect`) to a [Rect] in pixels, used to skip
+    /// what it clips out entirely.
+    #[inline]
+    fn scissor_bounds([x, y, width, height]: [u32; 4]) -> Rect {
+        Rect::new(x as f32, y as f32, width as f32, height as f32)
+    }
+
+    /// Like `scissor_rect`, but also restricted to the provided rectangle.
+    fn scissor_rect_within(&self, rect: Rect) -> Option<[u32; 4]> {
+        let size = self.size();
+        let bounds = Rect::new(0.0, 0.0, size.width as f32, size.height as f32).intersection(&rect);
+        let clip = match self.state.clip {
+            Some(clip) => clip.intersection(&bounds),
+            None => bounds,
+        };
+
+        let (min, max) = (clip.min(), clip.max());
+        let (x, y) = (min.x.floor().max(0.0) as u32, min.y.floor().max(0.0) as u32);
+        let (right, bottom) = (
+            (max.x.ceil() as u32).min(size.width),
+            (max.y.ceil() as u32).min(size.height),
+        );
+
+        if clip.is_empty() || right <= x || bottom <= y {
+            None
+        } else {
+            Some([x, y, right - x, bottom - y])
+        }
+    }
+
+    /// Begins a render pass which draws over the contents of this [Canvas], restricted to the
+    /// provided scissor rectangle (see `scissor_rect`).
+    fn begin_render_pass<'p>(
+        &self,
+        encoder: &'p mut wgpu::CommandEncoder,
+        wgpu_texture_view: &'p wgpu::TextureView,
+        [x, y, width, height]: [u32; 4],
+    ) -> wgpu::RenderPass<'p> {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: wgpu_texture_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_scissor_rect(x, y, width, height);
+        render_pass
+    }
+
+    /// Writes data directly to a [Texture].
+    ///
+    /// The data is expected to be in the format of the [Texture].
+    ///
+    /// # Fails
+    /// - Fails if the underlying data is too big or small.
+    /// - Fails if the [Texture] doesn't have the `COPY_DST` usage.
+    /// - Fails if G2d doesn't support the format of the [Texture].
+    pub fn write(&self, data: &[u8]) -> Result<(), Error> {
+        let bytes_per_pixel = self.checked_format()?.bytes_per_pixel();
+
+        if data.len() as u32 != self.size().area() * bytes_per_pixel {
+            return Err(Error::TextureDataSizeMismatch {
+                expected: self.size().area() * bytes_per_pixel,
+                got: data.len() as u32,
+            });
+        }
+
+        self.write_region(
+            PixelRect::from_size(self.size()),
+            data,
+            self.size().width * bytes_per_pixel,
+        )
+    }
+
+    /// Writes data directly to a region of a [Texture], leaving the rest of it untouched.
+    ///
+    /// The data is expected to be in the format of the [Texture], with each row of the region
+    /// starting *bytes_per_row* bytes after the one before.  Any bytes between the end of a row
+    /// and the start of the next are skipped.
+    ///
+    /// # Fails
+    /// - Fails if the region doesn't fit within the [Texture].
+    /// - Fails if *bytes_per_row* is smaller than a row of the region.
+    /// - Fails if the data is too small for the region.
+    /// - Fails if the [Texture] doesn't have the `COPY_DST` usage.
+    /// - Fails if G2d doesn't support the format of the [Texture].
+    pub fn write_region(
+        &self,
+        region: PixelRect,
+        data: &[u8],
+        bytes_per_row: u32,
+    ) -> Result<(), Error> {
+        if !self
+            .wgpu_texture_usage()
+            .contains(wgpu::TextureUsages::COPY_DST)
+        {
+            return Err(Error::LackingTextureUsage(wgpu::TextureUsages::COPY_DST));
+        }
+        self.check_region(region)?;
+
+        let row_len = region.width * self.checked_format()?.bytes_per_pixel();
+        if bytes_per_row < row_len {
+            return Err(Error::BytesPerRowTooSmall {
+                minimum: row_len,
+                got: bytes_per_row,
+            });
+        }
+
+        if region.is_empty() {
+            return Ok(());
+        }
+
+        // The last row doesn't need to be followed by padding.
+        let data_len = bytes_per_row * (region.height - 1) + row_len;
+        if (data.len() as u32) < data_len {
+            return Err(Error::TextureDataSizeMismatch {
+                expected: data_len,
+                got: data.len() as u32,
+            });
+        }
+
+        self.handle().wgpu_queue().write_texture(
+            wgpu::ImageCopyTexture {
+                texture: self.wgpu_texture(),
+                mip_level: 0,
+                origin: region.to_origin_3d(),
+                aspect: wgpu::TextureAspect::All,
+            },
+            &data[..data_len as usize],
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: None,
+            },
+            region.size().to_extent_3d(),
+        );
+
+        Ok(())
+    }
+
+    /// Attempts to get the pixels of this [Texture].
+    ///
+    /// # Fails
+    /// - Fails if the texture doesn't have the `COPY_SRC` usage.
+    /// - Fails if G2d doesn't support the format of the texture.
+    pub async fn pixels(&self) -> Result<Pixels, Error> {
+        self.pixels_region(PixelRect::from_size(self.size())).await
+    }
+
+    /// Attempts to get the pixels of a region of this [Texture].
+    ///
+    /// # Fails
+    /// - Fails if the region doesn't fit within the [Texture].
+    /// - Fails if the texture doesn't have the `COPY_SRC` usage.
+    /// - Fails if G2d doesn't support the format of the texture.
+    pub async fn pixels_region(&self, region: PixelRect) -> Result<Pixels, Error> {
+        if !self
+            .wgpu_texture_usage()
+            .contains(wgpu::TextureUsages::COPY_SRC)
+        {
+            return Err(Error::LackingTextureUsage(wgpu::TextureUsages::COPY_SRC));
+        }
+        self.check_region(region)?;
+        let format = self.checked_format()?;
+
+        if region.is_empty() {
+            return Ok(Pixels::from_raw_parts(region.size(), format, Vec::new()));
+        }
+
+        let mut wgpu_encoder = self
+            .handle()
+            .wgpu_device()
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+        // The rows of the output buffer must be a multiple of 256 bytes long
+        let bytes_per_pixel = format.bytes_per_pixel();
+        let unpadded_bytes_per_row = region.width * bytes_per_pixel;
+        let padded_bytes_per_row =
+            unpadded_bytes_per_row.next_multiple_of(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT);
+
+        let output_buffer_size = (padded_bytes_per_row * region.height) as wgpu::BufferAddress;
+        let output_buffer = self
+            .handle()
+            .wgpu_device()
+            .create_buffer(&wgpu::BufferDescriptor {
+                size: output_buffer_size,
+                usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+                label: None,
+                mapped_at_creation: false,
+            });
+
+        // Write the data of the region to the output buffer
+        wgpu_encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: self.wgpu_texture(),
+                mip_level: 0,
+                origin: region.to_origin_3d(),
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &output_buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            region.size().to_extent_3d(),
+        );
+
+        // Submit the commands to the queue.
+        self.handle()
+            .wgpu_queue()
+            .submit(Some(wgpu_encoder.finish()));
+
+        // Map the buffer.
+        let (tx, rx) = futures_intrusive::channel::shared::oneshot_channel();
+        output_buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                tx.send(result).unwrap();
+            });
+        self.handle().wgpu_device().poll(wgpu::Maintain::Wait);
+        rx.receive().await.unwrap().unwrap();
+
+        // Remove the row padding from the buffer
+        let mut pixel_data = Vec::with_capacity((region.size().area() * bytes_per_pixel) as usize);
+
+        output_buffer
+            .slice(..)
+            .get_mapped_range()
+            .chunks(padded_bytes_per_row as usize)
+            .for_each(|row| pixel_data.extend_from_slice(&row[0..unpadded_bytes_per_row as usize]));
+
+        Ok(Pixels::from_raw_parts(region.size(), format, pixel_data))
+    }
+
+    /// Attempts to get the color of the pixel containing *point*, e.g. for an eyedropper, reading
+    /// back only that pixel.
+    ///
+    /// The color has sRGB-encoded channels, see [Pixels::color_at].
+    ///
+    /// # Fails
+    /// - Fails if *point* is outside of this [Texture].
+    /// - Fails if the texture doesn't have the `COPY_SRC` usage.
+    /// - Fails if G2d doesn't support the format of the texture.
+    pub async fn pixel_at(&self, point: Vec2) -> Result<Color, Error> {
+        let region = PixelRect::new(point.x.floor() as u32, point.y.floor() as u32, 1, 1);
+        if point.x < 0.0 || point.y < 0.0 {
+            return Err(Error::RegionOutOfBounds {
+                region,
+                size: self.size(),
+            });
+        }
+
+        let pixels = self.pixels_region(region).await?;
+        Ok(pixels
+            .color_at(0, 0)
+            .expect("the pixels hold the pixel read back"))
+    }
+
+    /// Returns the [TextureFormat] of this [Texture], failing if G2d doesn't support it.
+    fn checked_format(&self) -> Result<TextureFormat, Error> {
+        self.format()
+            .ok_or(Error::UnsupportedTextureFormat(self.wgpu_texture_format()))
+    }
+
+    /// Checks that a region fits within this [Texture].
+    fn check_region(&self, region: PixelRect) -> Result<(), Error> {
+        if region.fits_within(self.size()) {
+            Ok(())
+        } else {
+            Err(Error::RegionOutOfBounds {
+                region,
+                size: self.size(),
+            })
+        }
+    }
+
+    /// Clears this [Texture], filling it with the provided color.
+    ///
+    /// NOTE: clearing ignores the clip of this [Canvas] and always fills the whole [Texture].
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    pub fn clear(&self, color: Color) -> Result<(), Error> {
+        self.check_render_attachment()?;
+
+        let mut encoder = self
+            .handle()
+            .wgpu_device()
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+        let wgpu_texture_view = self.create_wgpu_target_view();
+
+        // Begin the clear render pass.
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &wgpu_texture_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(color.to_wgpu_color()),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        // Submit to be drawn.
+        self.handle()
+            .wgpu_queue()
+            .submit(std::iter::once(encoder.finish()));
+
+        Ok(())
+    }
+
+    /// Draws the vertices in the provided [VertexBuffer] with the provided [Paint].
+    ///
+    /// Gradients are positioned in the pixels of this [Canvas].  The vertices are drawn with the
+    /// current [BlendMode] as is, so they overwrite the destination with the default
+    /// [BlendMode::Replace].
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    /// - Fails if a gradient has more than [LinearGradient::MAX_STOPS](crate::LinearGradient::MAX_STOPS) stops.
+    pub fn draw_vertices(&self, vertices: &VertexBuffer<'_, H>, paint: Paint) -> Result<(), Error> {
+        self.check_render_attachment()?;
+        let paint_uniform = PaintUniform::new(&paint)?;
+
+        let Some(scissor_rect) = self.scissor_rect() else {
+            return Ok(());
+        };
+
+        let wgpu_pipeline = self
+            .wgpu_render_pipeline_with_blend_mode(PipelineShader::PaintFill, self.state.blend_mode);
+        let wgpu_bind_group =
+            self.create_wgpu_paint_bind_group(&wgpu_pipeline, &paint_uniform, &[]);
+
+        let mut encoder = self
+            .handle()
+            .wgpu_device()
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+        let wgpu_texture_view = self.create_wgpu_target_view();
+        let wgpu_clip_bind_group = self.create_wgpu_clip_bind_group(&mut encoder, &wgpu_pipeline);
+
+        {
+            let mut render_pass =
+                self.begin_render_pass(&mut encoder, &wgpu_texture_view, scissor_rect);
+
+            render_pass.set_pipeline(&wgpu_pipeline);
+            render_pass.set_bind_group(0, &wgpu_bind_group, &[]);
+            if let Some(wgpu_clip_bind_group) = &wgpu_clip_bind_group {
+                render_pass.set_bind_group(1, wgpu_clip_bind_group, &[]);
+            }
+            render_pass.set_vertex_buffer(0, vertices.wgpu_buffer().slice(..));
+            render_pass.draw(0..vertices.len() as u32, 0..1);
+        }
+
+        // Submit to be drawn.
+        self.handle()
+            .wgpu_queue()
+            .submit(std::iter::once(encoder.finish()));
+
+        Ok(())
+    }
+}
+
+impl<'a, H: Handle> Texture<H> for Canvas<'a, H> {
+    fn handle(&self) -> &H {
+        self.handle
+    }
+
+    fn wgpu_texture(&self) -> &wgpu::Texture {
+        self.wgpu_texture
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::WindowlessHandle;
+
+    /// Creates the [Handle] of the tests which draw.
+    pub(super) fn handle() -> WindowlessHandle {
+        block_on(WindowlessHandle::new()).unwrap()
+    }
+
+    /// Reads back the pixel at *(x, y)* of a [Canvas], as RGBA8.
+    pub(super) fn pixel(canvas: &Canvas<'_, WindowlessHandle>, x: u32, y: u32) -> [u8; 4] {
+        block_on(canvas.pixel_at(Vec2::new(x as f32 + 0.5, y as f32 + 0.5)))
+            .unwrap()
+            .to_rgba_bytes()
+    }
+}
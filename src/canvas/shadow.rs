@@ -0,0 +1,132 @@
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+
+use crate::{
+    shadow::BoxShadowInstance, BoxShadow, Canvas, CornerRadii, Error, Handle, PipelineShader, Rect,
+    Texture,
+};
+
+impl<'a, H: Handle> Canvas<'a, H> {
+    /// Draws the [BoxShadow] of a rounded rectangle, like the CSS `box-shadow` property.
+    ///
+    /// Only the shadow is drawn: outer shadows are left out beneath the rectangle and inset
+    /// shadows never leave it.  The blur is computed analytically, so each shadow costs a single
+    /// quad whatever its blur radius.
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    #[inline]
+    pub fn draw_box_shadow(
+        &self,
+        rect: Rect,
+        radii: CornerRadii,
+        shadow: BoxShadow,
+    ) -> Result<(), Error> {
+        self.draw_box_shadows(&[(rect, radii, shadow)])
+    }
+
+    /// Draws many [BoxShadows](BoxShadow) at once, each cast by its own rounded rectangle.
+    ///
+    /// All the shadows are drawn in a single draw call, in order.
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    pub fn draw_box_shadows(
+        &self,
+        shadows: &[(Rect, CornerRadii, BoxShadow)],
+    ) -> Result<(), Error> {
+        self.check_render_attachment()?;
+
+        let Some(scissor_rect) = self.scissor_rect() else {
+            return Ok(());
+        };
+
+        // Skip the shadows which are completely clipped out or invisible.
+        let scissor_bounds = Self::scissor_bounds(scissor_rect);
+        let instances: Vec<BoxShadowInstance> = shadows
+            .iter()
+            .filter(|(_, _, shadow)| shadow.color.alpha > 0.0)
+            .map(|(rect, radii, shadow)| BoxShadowInstance::new(*rect, *radii, shadow))
+            .filter(|instance| instance.bounds().intersects(&scissor_bounds))
+            .collect();
+
+        if instances.is_empty() {
+            return Ok(());
+        }
+
+        let wgpu_device = self.handle().wgpu_device();
+        let wgpu_pipeline = self.wgpu_render_pipeline(PipelineShader::BoxShadow);
+
+        let wgpu_instance_buffer = wgpu_device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::cast_slice(&instances),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let wgpu_viewport_buffer = self.create_wgpu_viewport_buffer();
+        let wgpu_bind_group = wgpu_device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &wgpu_pipeline.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu_viewport_buffer.as_entire_binding(),
+            }],
+        });
+
+        let mut encoder =
+            wgpu_device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+        let wgpu_texture_view = self.create_wgpu_target_view();
+        let wgpu_clip_bind_group = self.create_wgpu_clip_bind_group(&mut encoder, &wgpu_pipeline);
+
+        {
+            let mut render_pass =
+                self.begin_render_pass(&mut encoder, &wgpu_texture_view, scissor_rect);
+
+            render_pass.set_pipeline(&wgpu_pipeline);
+            render_pass.set_bind_group(0, &wgpu_bind_group, &[]);
+            if let Some(wgpu_clip_bind_group) = &wgpu_clip_bind_group {
+                render_pass.set_bind_group(1, wgpu_clip_bind_group, &[]);
+            }
+            render_pass.set_vertex_buffer(0, wgpu_instance_buffer.slice(..));
+            render_pass.draw(0..6, 0..instances.len() as u32);
+        }
+
+        // Submit to be drawn.
+        self.handle()
+            .wgpu_queue()
+            .submit(std::iter::once(encoder.finish()));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        canvas::tests::{handle, pixel},
+        Color, Dimension, Vec2,
+    };
+
+    #[test]
+    fn shadows_keep_the_pixels_beneath() {
+        let handle = handle();
+        let texture = handle.make_blank_texture(Dimension::new(32, 32));
+        let canvas = texture.canvas();
+        canvas.clear(Color::RED).unwrap();
+        canvas
+            .draw_box_shadow(
+                Rect::new(8.0, 8.0, 16.0, 16.0),
+                CornerRadii::uniform(4.0),
+                BoxShadow::new(Vec2::default(), 8.0, Color::BLACK),
+            )
+            .unwrap();
+
+        // Outer shadows are left out beneath their box...
+        assert_eq!(pixel(&canvas, 16, 16), [255, 0, 0, 255]);
+
+        // ...and fade out over the background around it.
+        let [red, green, blue, alpha] = pixel(&canvas, 6, 16);
+        assert!(red > 0 && red < 255 && green == 0 && blue == 0 && alpha == 255);
+        assert_eq!(pixel(&canvas, 0, 0)[3], 255);
+    }
+}
@@ -0,0 +1,135 @@
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+
+use crate::{
+    shape::ShapeInstance, Canvas, Error, Handle, PipelineShader, Shape, ShapeStyle, Texture,
+};
+
+impl<'a, H: Handle> Canvas<'a, H> {
+    /// Draws a [Shape] with the provided [ShapeStyle].
+    ///
+    /// Shapes are rendered with a signed distance field, so their edges stay smooth at any size.
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    #[inline]
+    pub fn draw_shape(&self, shape: Shape, style: ShapeStyle) -> Result<(), Error> {
+        self.draw_shapes(&[(shape, style)])
+    }
+
+    /// Draws many [Shapes](Shape) at once, each with its own [ShapeStyle].
+    ///
+    /// All the shapes are drawn in a single draw call, in order.
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    pub fn draw_shapes(&self, shapes: &[(Shape, ShapeStyle)]) -> Result<(), Error> {
+        self.check_render_attachment()?;
+
+        let Some(scissor_rect) = self.scissor_rect() else {
+            return Ok(());
+        };
+
+        // Skip the shapes which are completely clipped out.
+        let scissor_bounds = Self::scissor_bounds(scissor_rect);
+        let instances: Vec<ShapeInstance> = shapes
+            .iter()
+            .map(|(shape, style)| ShapeInstance::new(shape, style))
+            .filter(|instance| instance.bounds().intersects(&scissor_bounds))
+            .collect();
+
+        if instances.is_empty() {
+            return Ok(());
+        }
+
+        let wgpu_device = self.handle().wgpu_device();
+        let wgpu_pipeline = self.wgpu_render_pipeline(PipelineShader::SdfShape);
+
+        let wgpu_instance_buffer = wgpu_device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::cast_slice(&instances),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let wgpu_viewport_buffer = self.create_wgpu_viewport_buffer();
+        let wgpu_bind_group = wgpu_device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &wgpu_pipeline.get_bind_group_layout(0),
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu_viewport_buffer.as_entire_binding(),
+            }],
+        });
+
+        let mut encoder =
+            wgpu_device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+        let wgpu_texture_view = self.create_wgpu_target_view();
+        let wgpu_clip_bind_group = self.create_wgpu_clip_bind_group(&mut encoder, &wgpu_pipeline);
+
+        {
+            let mut render_pass =
+                self.begin_render_pass(&mut encoder, &wgpu_texture_view, scissor_rect);
+
+            render_pass.set_pipeline(&wgpu_pipeline);
+            render_pass.set_bind_group(0, &wgpu_bind_group, &[]);
+            if let Some(wgpu_clip_bind_group) = &wgpu_clip_bind_group {
+                render_pass.set_bind_group(1, wgpu_clip_bind_group, &[]);
+            }
+            render_pass.set_vertex_buffer(0, wgpu_instance_buffer.slice(..));
+            render_pass.draw(0..6, 0..instances.len() as u32);
+        }
+
+        // Submit to be drawn.
+        self.handle()
+            .wgpu_queue()
+            .submit(std::iter::once(encoder.finish()));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        canvas::tests::{handle, pixel},
+        Color, CornerRadii, Dimension, Rect, Vec2,
+    };
+
+    #[test]
+    fn shapes_keep_the_pixels_around_them() {
+        let handle = handle();
+        let texture = handle.make_blank_texture(Dimension::new(32, 32));
+        let canvas = texture.canvas();
+        canvas.clear(Color::RED).unwrap();
+        canvas
+            .draw_shapes(&[
+                (
+                    Shape::RoundedRect {
+                        rect: Rect::new(0.0, 0.0, 32.0, 16.0),
+                        radii: CornerRadii::uniform(8.0),
+                    },
+                    ShapeStyle::fill(Color::BLUE),
+                ),
+                (
+                    Shape::Circle {
+                        center: Vec2::new(16.0, 24.0),
+                        radius: 7.5,
+                    },
+                    ShapeStyle::fill(Color::BLUE),
+                ),
+            ])
+            .unwrap();
+
+        assert_eq!(pixel(&canvas, 16, 8), [0, 0, 255, 255]);
+        assert_eq!(pixel(&canvas, 16, 24), [0, 0, 255, 255]);
+
+        // The corners of the quads, outside of the shapes.
+        assert_eq!(pixel(&canvas, 0, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(&canvas, 31, 15), [255, 0, 0, 255]);
+        assert_eq!(pixel(&canvas, 9, 31), [255, 0, 0, 255]);
+
+        // The anti-aliased edges blend with the background instead of showing through.
+        let [red, green, blue, alpha] = pixel(&canvas, 2, 2);
+        assert!(red > 0 && green == 0 && blue > 0 && alpha == 255);
+    }
+}
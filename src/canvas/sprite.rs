@@ -0,0 +1,348 @@
+use std::ops::Range;
+
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+
+use crate::{
+    nine_slice::nine_slice_instances,
+    sprite::{BatchedSprite, SpriteInstance},
+    Canvas, DrawTextureOptions, Error, Handle, NineSliceInsets, NineSliceMode, PipelineShader,
+    Rect, SpriteBatch, Texture, TextureSampling,
+};
+
+impl<'a, H: Handle> Canvas<'a, H> {
+    /// Draws the *src* rectangle of a [Texture] onto the *dst* rectangle of this [Canvas], both in
+    /// pixels, like `drawImage` in the HTML canvas.
+    ///
+    /// The texture is stretched to fill *dst*, then tinted, flipped and rotated as set by
+    /// *options*.  Its pixels are expected to hold straight (not premultiplied) alpha, like the
+    /// data given to [Handle::make_texture].
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    /// - Fails if *texture* does not have the `TEXTURE_BINDING` usage.
+    /// - Fails if *texture* is the texture of this [Canvas].
+    pub fn draw_texture(
+        &self,
+        texture: &impl Texture<H>,
+        src: Rect,
+        dst: Rect,
+        options: &DrawTextureOptions,
+    ) -> Result<(), Error> {
+        let instance = SpriteInstance::new(texture.size(), src, dst, options);
+        self.draw_sprite_instances(texture.wgpu_texture(), options.sampling, &[instance])
+    }
+
+    /// Draws a bordered [Texture] onto the *dst* rectangle of this [Canvas] as a nine-slice: its
+    /// corners keep their size, its edges are scaled along one axis and its center along both,
+    /// stretched or tiled as set by *mode*.
+    ///
+    /// The borders are scaled down evenly when they don't fit in *dst*.
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    /// - Fails if *texture* does not have the `TEXTURE_BINDING` usage.
+    /// - Fails if *texture* is the texture of this [Canvas].
+    pub fn draw_nine_slice(
+        &self,
+        texture: &impl Texture<H>,
+        insets: NineSliceInsets,
+        dst: Rect,
+        mode: NineSliceMode,
+    ) -> Result<(), Error> {
+        let instances = nine_slice_instances(texture.size(), insets, dst, mode);
+        self.draw_sprite_instances(texture.wgpu_texture(), TextureSampling::Linear, &instances)
+    }
+
+    /// Draws the sprites of a [SpriteBatch], in a single render pass with one draw call for each
+    /// run of sprites sharing a texture, sampling and [BlendMode](crate::BlendMode).
+    ///
+    /// The sprites are drawn with the blend modes set on the batch, ignoring the one of this
+    /// [Canvas], and are kept in the batch.
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    /// - Fails if a texture of the batch does not have the `TEXTURE_BINDING` usage.
+    /// - Fails if a texture of the batch is the texture of this [Canvas].
+    pub fn draw_sprite_batch(&self, batch: &mut SpriteBatch<'_>) -> Result<(), Error> {
+        self.check_render_attachment()?;
+
+        let Some(scissor_rect) = self.scissor_rect() else {
+            return Ok(());
+        };
+
+        // Skip the sprites which are completely clipped out, and find the runs of sprites which
+        // share a draw call.
+        let scissor_bounds = Self::scissor_bounds(scissor_rect);
+        let mut instances = Vec::with_capacity(batch.len());
+        let mut runs: Vec<(BatchedSprite<'_>, Range<u32>)> = Vec::new();
+        for sprite in batch.sorted_sprites() {
+            if !sprite.instance.bounds().intersects(&scissor_bounds) {
+                continue;
+            }
+
+            match runs.last_mut() {
+                Some((first, range)) if sprite.shares_draw_call(first) => {
+                    *range = range.start..range.end + 1;
+                }
+                _ => {
+                    if !sprite
+                        .wgpu_texture
+                        .usage()
+                        .contains(wgpu::TextureUsages::TEXTURE_BINDING)
+                    {
+                        return Err(Error::LackingTextureUsage(
+                            wgpu::TextureUsages::TEXTURE_BINDING,
+                        ));
+                    }
+                    if std::ptr::eq(sprite.wgpu_texture, self.wgpu_texture()) {
+                        return Err(Error::TextureDrawnOntoItself);
+                    }
+
+                    let start = instances.len() as u32;
+                    runs.push((*sprite, start..start + 1));
+                }
+            }
+            instances.push(sprite.instance);
+        }
+
+        if instances.is_empty() {
+            return Ok(());
+        }
+
+        let wgpu_device = self.handle().wgpu_device();
+        let wgpu_viewport_buffer = self.create_wgpu_viewport_buffer();
+        let wgpu_samplers = TextureSampling::ALL
+            .map(|sampling| wgpu_device.create_sampler(&sampling.to_wgpu_sampler_descriptor()));
+
+        // Create the pipeline and bind group of each run before the render pass, which borrows
+        // them.
+        let draws: Vec<_> = runs
+            .into_iter()
+            .map(|(sprite, range)| {
+                let wgpu_pipeline = self.wgpu_render_pipeline_with_blend_mode(
+                    PipelineShader::Sprite,
+                    sprite.blend_mode,
+                );
+                let wgpu_source_view = sprite.sampling.create_wgpu_source_view(sprite.wgpu_texture);
+                let wgpu_bind_group = wgpu_device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    label: None,
+                    layout: &wgpu_pipeline.get_bind_group_layout(0),
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu_viewport_buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::TextureView(&wgpu_source_view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: wgpu::BindingResource::Sampler(
+                                &wgpu_samplers[sprite.sampling as usize],
+                            ),
+                        },
+                    ],
+                });
+                (wgpu_pipeline, wgpu_bind_group, range)
+            })
+            .collect();
+
+        let wgpu_instance_buffer = batch.wgpu_instance_buffer(wgpu_device, instances.len());
+        self.handle().wgpu_queue().write_buffer(
+            wgpu_instance_buffer,
+            0,
+            bytemuck::cast_slice(&instances),
+        );
+
+        let mut encoder =
+            wgpu_device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+        let wgpu_texture_view = self.create_wgpu_target_view();
+        let wgpu_clip_bind_groups: Vec<_> = draws
+            .iter()
+            .map(|(wgpu_pipeline, _, _)| {
+                self.create_wgpu_clip_bind_group(&mut encoder, wgpu_pipeline)
+            })
+            .collect();
+
+        {
+            let mut render_pass =
+                self.begin_render_pass(&mut encoder, &wgpu_texture_view, scissor_rect);
+
+            render_pass.set_vertex_buffer(0, wgpu_instance_buffer.slice(..));
+            for ((wgpu_pipeline, wgpu_bind_group, range), wgpu_clip_bind_group) in
+                draws.iter().zip(&wgpu_clip_bind_groups)
+            {
+                render_pass.set_pipeline(wgpu_pipeline);
+                render_pass.set_bind_group(0, wgpu_bind_group, &[]);
+                if let Some(wgpu_clip_bind_group) = wgpu_clip_bind_group {
+                    render_pass.set_bind_group(1, wgpu_clip_bind_group, &[]);
+                }
+                render_pass.draw(0..6, range.clone());
+            }
+        }
+
+        // Submit to be drawn.
+        self.handle()
+            .wgpu_queue()
+            .submit(std::iter::once(encoder.finish()));
+
+        Ok(())
+    }
+
+    /// Draws sprites from the provided [`wgpu::Texture`] in a single draw call, in order.
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    /// - Fails if the texture does not have the `TEXTURE_BINDING` usage.
+    /// - Fails if the texture is the texture of this [Canvas].
+    pub(crate) fn draw_sprite_instances(
+        &self,
+        wgpu_texture: &wgpu::Texture,
+        sampling: TextureSampling,
+        instances: &[SpriteInstance],
+    ) -> Result<(), Error> {
+        self.check_render_attachment()?;
+        if !wgpu_texture
+            .usage()
+            .contains(wgpu::TextureUsages::TEXTURE_BINDING)
+        {
+            return Err(Error::LackingTextureUsage(
+                wgpu::TextureUsages::TEXTURE_BINDING,
+            ));
+        }
+        if std::ptr::eq(wgpu_texture, self.wgpu_texture()) {
+            return Err(Error::TextureDrawnOntoItself);
+        }
+
+        let Some(scissor_rect) = self.scissor_rect() else {
+            return Ok(());
+        };
+
+        // Skip the sprites which are completely clipped out.
+        let scissor_bounds = Self::scissor_bounds(scissor_rect);
+        let instances: Vec<SpriteInstance> = instances
+            .iter()
+            .filter(|instance| instance.bounds().intersects(&scissor_bounds))
+            .copied()
+            .collect();
+
+        if instances.is_empty() {
+            return Ok(());
+        }
+
+        let wgpu_device = self.handle().wgpu_device();
+        let wgpu_pipeline = self.wgpu_render_pipeline(PipelineShader::Sprite);
+
+        let wgpu_instance_buffer = wgpu_device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::cast_slice(&instances),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+        let wgpu_viewport_buffer = self.create_wgpu_viewport_buffer();
+        let wgpu_source_view = sampling.create_wgpu_source_view(wgpu_texture);
+        let wgpu_sampler = wgpu_device.create_sampler(&sampling.to_wgpu_sampler_descriptor());
+        let wgpu_bind_group = wgpu_device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &wgpu_pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu_viewport_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&wgpu_source_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&wgpu_sampler),
+                },
+            ],
+        });
+
+        let mut encoder =
+            wgpu_device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+        let wgpu_texture_view = self.create_wgpu_target_view();
+        let wgpu_clip_bind_group = self.create_wgpu_clip_bind_group(&mut encoder, &wgpu_pipeline);
+
+        {
+            let mut render_pass =
+                self.begin_render_pass(&mut encoder, &wgpu_texture_view, scissor_rect);
+
+            render_pass.set_pipeline(&wgpu_pipeline);
+            render_pass.set_bind_group(0, &wgpu_bind_group, &[]);
+            if let Some(wgpu_clip_bind_group) = &wgpu_clip_bind_group {
+                render_pass.set_bind_group(1, wgpu_clip_bind_group, &[]);
+            }
+            render_pass.set_vertex_buffer(0, wgpu_instance_buffer.slice(..));
+            render_pass.draw(0..6, 0..instances.len() as u32);
+        }
+
+        // Submit to be drawn.
+        self.handle()
+            .wgpu_queue()
+            .submit(std::iter::once(encoder.finish()));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        canvas::tests::{handle, pixel},
+        BlendMode, Color, Dimension,
+    };
+
+    #[test]
+    fn textures_and_batches_keep_the_pixels_beneath() {
+        let handle = handle();
+
+        // A 4x4 icon whose middle 2x2 pixels are opaque blue.
+        let icon: Vec<u8> = (0..4)
+            .flat_map(|y| {
+                (0..4).flat_map(move |x| {
+                    if (1..3).contains(&x) && (1..3).contains(&y) {
+                        [0, 0, 255, 255]
+                    } else {
+                        [0; 4]
+                    }
+                })
+            })
+            .collect();
+        let icon = handle.make_texture(Dimension::new(4, 4), &icon).unwrap();
+        let (src, dst) = (Rect::new(0.0, 0.0, 4.0, 4.0), Rect::new(4.0, 4.0, 8.0, 8.0));
+        let options = DrawTextureOptions {
+            sampling: TextureSampling::Nearest,
+            ..DrawTextureOptions::new()
+        };
+
+        let texture = handle.make_blank_texture(Dimension::new(16, 16));
+        let mut canvas = texture.canvas();
+        canvas.clear(Color::RED).unwrap();
+        canvas.draw_texture(&icon, src, dst, &options).unwrap();
+
+        // Even with BlendMode::Replace, batches draw like Canvas::draw_texture.
+        let batch_texture = handle.make_blank_texture(Dimension::new(16, 16));
+        let batch_canvas = batch_texture.canvas();
+        batch_canvas.clear(Color::RED).unwrap();
+        let mut batch = SpriteBatch::new();
+        batch.set_blend_mode(BlendMode::Replace);
+        batch.add(&icon, src, dst, &options);
+        batch_canvas.draw_sprite_batch(&mut batch).unwrap();
+
+        canvas.set_blend_mode(BlendMode::Replace);
+        canvas.draw_texture(&icon, src, dst, &options).unwrap();
+
+        for canvas in [&canvas, &batch_canvas] {
+            assert_eq!(pixel(canvas, 8, 8), [0, 0, 255, 255]);
+            // The transparent pixels of the icon.
+            assert_eq!(pixel(canvas, 4, 4), [255, 0, 0, 255]);
+            assert_eq!(pixel(canvas, 11, 8), [255, 0, 0, 255]);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use super::ClipPath;
+use crate::{BlendMode, Canvas, Handle, Rect};
+
+/// The drawing state of a [Canvas], saved and restored with [Canvas::save] and
+/// [Canvas::restore].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CanvasState {
+    /// The [BlendMode] used when drawing.
+    pub blend_mode: BlendMode,
+
+    /// The rectangle draws are restricted to, in pixels.  [None] if drawing isn't clipped.
+    ///
+    /// Also contains the bounds of every clip path.
+    pub clip: Option<Rect>,
+
+    /// The paths draws are restricted to, see [Canvas::clip_path].
+    pub(crate) clip_paths: Vec<ClipPath>,
+}
+
+impl<'a, H: Handle> Canvas<'a, H> {
+    /// Returns the current drawing state of this [Canvas].
+    #[inline]
+    pub fn state(&self) -> &CanvasState {
+        &self.state
+    }
+
+    /// Pushes a copy of the current drawing state onto the state stack.  It can be brought back
+    /// with [Canvas::restore].
+    #[inline]
+    pub fn save(&mut self) {
+        self.saved_states.push(self.state.clone());
+    }
+
+    /// Pops the drawing state last pushed by [Canvas::save], making it the current state.
+    ///
+    /// Does nothing if there is no saved state.
+    #[inline]
+    pub fn restore(&mut self) {
+        if let Some(state) = self.saved_states.pop() {
+            self.state = state;
+        }
+    }
+
+    /// Returns the [BlendMode] used when drawing to this [Canvas].
+    #[inline]
+    pub fn blend_mode(&self) -> BlendMode {
+        self.state.blend_mode
+    }
+
+    /// Sets the [BlendMode] used when drawing to this [Canvas].  Defaults to
+    /// [BlendMode::Replace].
+    ///
+    /// Only [Canvas::draw_vertices] replaces the destination: every other draw fades its edges
+    /// out with alpha, so draws over it with [BlendMode::SourceOver] instead.
+    #[inline]
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.state.blend_mode = blend_mode;
+    }
+}
@@ -0,0 +1,732 @@
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+
+use crate::{
+    paint::PaintUniform,
+    path::FLATTEN_TOLERANCE,
+    text::{AtlasViews, GlyphInstance, GlyphRaster, SDF_SIZE, SDF_SPREAD, SUBPIXEL_STEPS},
+    BitmapFont, Canvas, Color, Error, Font, Handle, LinearGradient, Paint, Path, PipelineShader,
+    RadialGradient, Rect, SdfTextStyle, SweepGradient, TextFont, TextLayout, TextLayoutOptions,
+    Texture, Transform, Vec2,
+};
+
+/// The uniform of the signed distance field text shader.
+#[derive(bytemuck::Zeroable, bytemuck::Pod, Clone, Copy, Debug)]
+#[repr(C)]
+struct SdfTextUniform {
+    transform: [[f32; 4]; 3],
+    viewport_size: [f32; 2],
+    scale: f32,
+    spread: f32,
+    outline_color: Color,
+    glow_color: Color,
+    outline_width: f32,
+    glow_radius: f32,
+    _padding: [u32; 2],
+}
+
+/// The uniform of the color text shader.
+#[derive(bytemuck::Zeroable, bytemuck::Pod, Clone, Copy, Debug)]
+#[repr(C)]
+struct ColorTextUniform {
+    transform: [[f32; 4]; 3],
+    viewport_size: [f32; 2],
+    opacity: f32,
+    _padding: u32,
+}
+
+impl<'a, H: Handle> Canvas<'a, H> {
+    /// Draws a string of text with the provided font and [Paint].
+    ///
+    /// *font* is a [Font] or a [BitmapFont].  *size* is the font size in pixels and *position* is
+    /// where the baseline of the first line starts.  Each `'\n'` starts a new line.  Gradients are
+    /// positioned in the pixels of this [Canvas].
+    ///
+    /// The text is shaped and laid out with the default [TextLayoutOptions]; use a [TextLayout]
+    /// to wrap or align it, or to draw it many times.  Bitmap fonts are laid out from their
+    /// advances and kerning pairs, and scaled from their own size to *size*.
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    /// - Fails if a gradient has more than [LinearGradient::MAX_STOPS](crate::LinearGradient::MAX_STOPS) stops.
+    /// - Fails if the glyphs of the text don't all fit in the [GlyphAtlas](crate::GlyphAtlas).
+    pub fn draw_text<'f>(
+        &self,
+        font: impl Into<TextFont<'f>>,
+        size: f32,
+        position: Vec2,
+        text: &str,
+        paint: &Paint,
+    ) -> Result<(), Error> {
+        match font.into() {
+            TextFont::Vector(font) => {
+                let layout = TextLayout::new(font, size, text, &TextLayoutOptions::default());
+                let ascent = layout.lines().first().map_or(0.0, |line| line.baseline);
+                self.draw_text_layout(&layout, Vec2::new(position.x, position.y - ascent), paint)
+            }
+            TextFont::Bitmap(font) => self.draw_bitmap_text(font, size, position, text, paint),
+        }
+    }
+
+    /// Draws a string of text with a [BitmapFont], see [Canvas::draw_text].
+    fn draw_bitmap_text(
+        &self,
+        font: &BitmapFont,
+        size: f32,
+        position: Vec2,
+        text: &str,
+        paint: &Paint,
+    ) -> Result<(), Error> {
+        self.check_render_attachment()?;
+        let paint_uniform = PaintUniform::new(paint)?;
+
+        let Some(scissor_rect) = self.scissor_rect() else {
+            return Ok(());
+        };
+
+        // Skip the glyphs which are completely clipped out, and draw the others page by page.
+        let scissor_bounds = Self::scissor_bounds(scissor_rect);
+        let mut instances = font.glyph_instances(size, position, text);
+        instances.retain(|(_, instance)| instance.bounds().intersects(&scissor_bounds));
+        instances.sort_by_key(|(page, _)| *page);
+
+        let wgpu_pipeline = self.wgpu_render_pipeline(PipelineShader::BitmapText);
+        let wgpu_viewport_buffer = self.create_wgpu_viewport_buffer();
+        for page_instances in instances.chunk_by(|a, b| a.0 == b.0) {
+            let wgpu_page_view = font.wgpu_pages()[page_instances[0].0]
+                .create_view(&wgpu::TextureViewDescriptor::default());
+            let wgpu_bind_group = self.create_wgpu_paint_bind_group(
+                &wgpu_pipeline,
+                &paint_uniform,
+                &[
+                    wgpu_viewport_buffer.as_entire_binding(),
+                    wgpu::BindingResource::TextureView(&wgpu_page_view),
+                ],
+            );
+
+            let page_instances: Vec<_> = page_instances
+                .iter()
+                .map(|(_, instance)| *instance)
+                .collect();
+            self.draw_glyph_instances(
+                &wgpu_pipeline,
+                &wgpu_bind_group,
+                &page_instances,
+                scissor_rect,
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Draws a [TextLayout] with its top-left corner at *position*, with the provided [Paint].
+    ///
+    /// Glyphs are rasterized into the [GlyphAtlas](crate::GlyphAtlas) of the [Handle] the first
+    /// time they are drawn at a size, and reused afterwards.
+    ///
+    /// Color glyphs, like emoji, keep their own colors: layered glyphs from the `COLR` table are
+    /// drawn with G2d's paths and gradients, and embedded PNG or BGRA bitmaps are scaled to the
+    /// font size.  Only the alpha of a [Paint::Solid] applies to them, and its color fills the
+    /// layers which use the color of the text.
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    /// - Fails if a gradient has more than [LinearGradient::MAX_STOPS](crate::LinearGradient::MAX_STOPS) stops.
+    /// - Fails if the glyphs of the text don't all fit in the [GlyphAtlas](crate::GlyphAtlas).
+    pub fn draw_text_layout(
+        &self,
+        layout: &TextLayout,
+        position: Vec2,
+        paint: &Paint,
+    ) -> Result<(), Error> {
+        self.check_render_attachment()?;
+        let paint_uniform = PaintUniform::new(paint)?;
+
+        let Some(scissor_rect) = self.scissor_rect() else {
+            return Ok(());
+        };
+
+        let size = layout.font_size();
+        let (foreground, opacity) = color_glyph_paint(paint);
+        let faces: Vec<_> = layout.fonts().iter().map(Font::face).collect();
+
+        // Snap the origins of the glyphs to the nearest subpixel step, or to whole pixels for
+        // color glyphs.
+        let (glyphs, origins): (Vec<_>, Vec<_>) = layout
+            .glyphs()
+            .iter()
+            .map(|glyph| {
+                let font = &layout.fonts()[glyph.font];
+                let origin = position + glyph.position;
+                if let Some(raster) = GlyphRaster::for_color_glyph(
+                    &faces[glyph.font],
+                    glyph.glyph_id,
+                    size,
+                    foreground,
+                ) {
+                    return (
+                        (font, glyph.glyph_id, raster),
+                        Vec2::new(origin.x.round(), origin.y.round()),
+                    );
+                }
+
+                let steps = (origin.x * SUBPIXEL_STEPS as f32).round() as i32;
+                let subpixel = steps.rem_euclid(SUBPIXEL_STEPS as i32);
+                (
+                    (
+                        font,
+                        glyph.glyph_id,
+                        GlyphRaster::Coverage {
+                            size_bits: size.to_bits(),
+                            subpixel: subpixel as u8,
+                        },
+                    ),
+                    Vec2::new(
+                        ((steps - subpixel) / SUBPIXEL_STEPS as i32) as f32,
+                        origin.y.round(),
+                    ),
+                )
+            })
+            .unzip();
+
+        let (atlas_glyphs, atlas_views) = self
+            .handle()
+            .glyph_atlas()
+            .prepare(self.handle(), &glyphs)?;
+
+        // Skip the glyphs which are empty or completely clipped out.
+        let scissor_bounds = Self::scissor_bounds(scissor_rect);
+        let (mut instances, mut color_instances) = (Vec::new(), Vec::new());
+        for ((atlas_glyph, (_, _, raster)), origin) in atlas_glyphs.iter().zip(&glyphs).zip(origins)
+        {
+            let Some(instance) = GlyphInstance::new(atlas_glyph, origin, raster.scale(size)) else {
+                continue;
+            };
+            if !instance.bounds().intersects(&scissor_bounds) {
+                continue;
+            }
+
+            if atlas_glyph.color {
+                color_instances.push(instance);
+            } else {
+                instances.push(instance);
+            }
+        }
+
+        if !instances.is_empty() {
+            let wgpu_pipeline = self.wgpu_render_pipeline(PipelineShader::Text);
+            let wgpu_viewport_buffer = self.create_wgpu_viewport_buffer();
+            let wgpu_bind_group = self.create_wgpu_paint_bind_group(
+                &wgpu_pipeline,
+                &paint_uniform,
+                &[
+                    wgpu_viewport_buffer.as_entire_binding(),
+                    wgpu::BindingResource::TextureView(&atlas_views.wgpu_mask_view),
+                ],
+            );
+
+            self.draw_glyph_instances(&wgpu_pipeline, &wgpu_bind_group, &instances, scissor_rect);
+        }
+
+        self.draw_color_glyph_instances(
+            &atlas_views,
+            &color_instances,
+            Transform::IDENTITY,
+            opacity,
+            scissor_rect,
+        );
+        Ok(())
+    }
+
+    /// Draws a [TextLayout] from the signed distance fields of its glyphs, which stays crisp
+    /// when scaled, rotated or skewed.
+    ///
+    /// *transform* maps the pixels of the layout, with its top-left corner at the origin, to the
+    /// pixels of this [Canvas].  Glyphs are filled with the provided [Paint] and decorated with
+    /// the outline and glow of *style*.  Gradients are positioned in the pixels of this [Canvas].
+    ///
+    /// The distance field of each glyph is generated once per [Font] and reused for every size;
+    /// they are better suited to large or animated text than to small body text, which
+    /// [Canvas::draw_text_layout] renders more sharply.
+    ///
+    /// Color glyphs are drawn like by [Canvas::draw_text_layout], rasterized at the size the
+    /// transform scales them to, without outline or glow.
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    /// - Fails if a gradient has more than [LinearGradient::MAX_STOPS](crate::LinearGradient::MAX_STOPS) stops.
+    /// - Fails if the glyphs of the text don't all fit in the [GlyphAtlas](crate::GlyphAtlas).
+    pub fn draw_sdf_text_layout(
+        &self,
+        layout: &TextLayout,
+        transform: Transform,
+        style: &SdfTextStyle,
+        paint: &Paint,
+    ) -> Result<(), Error> {
+        self.check_render_attachment()?;
+        let paint_uniform = PaintUniform::new(paint)?;
+
+        let Some(scissor_rect) = self.scissor_rect() else {
+            return Ok(());
+        };
+
+        let size = layout.font_size();
+        let (foreground, opacity) = color_glyph_paint(paint);
+        let faces: Vec<_> = layout.fonts().iter().map(Font::face).collect();
+        let color_size = (size * transform.determinant().abs().sqrt())
+            .ceil()
+            .max(1.0);
+
+        let glyphs: Vec<_> = layout
+            .glyphs()
+            .iter()
+            .map(|glyph| {
+                let raster = GlyphRaster::for_color_glyph(
+                    &faces[glyph.font],
+                    glyph.glyph_id,
+                    color_size,
+                    foreground,
+                );
+                (
+                    &layout.fonts()[glyph.font],
+                    glyph.glyph_id,
+                    raster.unwrap_or(GlyphRaster::Sdf),
+                )
+            })
+            .collect();
+        let (atlas_glyphs, atlas_views) = self
+            .handle()
+            .glyph_atlas()
+            .prepare(self.handle(), &glyphs)?;
+
+        // Skip the glyphs which are empty or completely clipped out once transformed.
+        let scissor_bounds = Self::scissor_bounds(scissor_rect);
+        let is_visible = |instance: &GlyphInstance| {
+            transformed_bounds(instance.bounds(), transform).intersects(&scissor_bounds)
+        };
+        let (mut instances, mut color_instances) = (Vec::new(), Vec::new());
+        for ((atlas_glyph, (_, _, raster)), glyph) in
+            atlas_glyphs.iter().zip(&glyphs).zip(layout.glyphs())
+        {
+            let Some(instance) =
+                GlyphInstance::new(atlas_glyph, glyph.position, raster.scale(size))
+            else {
+                continue;
+            };
+            if !is_visible(&instance) {
+                continue;
+            }
+
+            if atlas_glyph.color {
+                color_instances.push(instance);
+            } else {
+                instances.push(instance);
+            }
+        }
+
+        if !instances.is_empty() {
+            self.draw_sdf_glyph_instances(
+                &atlas_views,
+                &instances,
+                transform,
+                style,
+                &paint_uniform,
+                size / SDF_SIZE,
+                scissor_rect,
+            );
+        }
+
+        self.draw_color_glyph_instances(
+            &atlas_views,
+            &color_instances,
+            transform,
+            opacity,
+            scissor_rect,
+        );
+        Ok(())
+    }
+
+    /// Draws a [TextLayout] along a [Path], each glyph rotated to follow it, from the signed
+    /// distance fields of its glyphs.
+    ///
+    /// The baseline of the first line of the layout runs along the path, starting *offset* pixels
+    /// from its start; following lines are drawn on its right-hand side.  Each glyph is centered
+    /// on the point of the path its middle falls on, and glyphs falling past either end of the
+    /// path are left out.  Glyphs are filled and decorated like by
+    /// [Canvas::draw_sdf_text_layout].
+    ///
+    /// # Fails
+    /// - Fails if this [Texture] does not have the `RENDER_ATTACHMENT` usage.
+    /// - Fails if a gradient has more than [LinearGradient::MAX_STOPS](crate::LinearGradient::MAX_STOPS) stops.
+    /// - Fails if the glyphs of the text don't all fit in the [GlyphAtlas](crate::GlyphAtlas).
+    pub fn draw_text_on_path(
+        &self,
+        path: &Path,
+        layout: &TextLayout,
+        offset: f32,
+        style: &SdfTextStyle,
+        paint: &Paint,
+    ) -> Result<(), Error> {
+        self.check_render_attachment()?;
+        let paint_uniform = PaintUniform::new(paint)?;
+
+        let Some(scissor_rect) = self.scissor_rect() else {
+            return Ok(());
+        };
+
+        let size = layout.font_size();
+        let (foreground, opacity) = color_glyph_paint(paint);
+        let faces: Vec<_> = layout.fonts().iter().map(Font::face).collect();
+        let measure = PathMeasure::new(path);
+        let baseline = layout.lines().first().map_or(0.0, |line| line.baseline);
+
+        // Find where each glyph goes on the path, leaving out the ones past its ends.
+        let (glyphs, transforms): (Vec<_>, Vec<_>) = layout
+            .glyphs()
+            .iter()
+            .filter_map(|glyph| {
+                let font = &layout.fonts()[glyph.font];
+                let middle = glyph.position.x + font.advance(glyph.glyph_id, size) / 2.0;
+                let (point, direction) = measure.position(middle + offset)?;
+                let transform = Transform::translate(point)
+                    * Transform::rotate(direction.y.atan2(direction.x))
+                    * Transform::translate(Vec2::new(-middle, -baseline));
+
+                let raster = GlyphRaster::for_color_glyph(
+                    &faces[glyph.font],
+                    glyph.glyph_id,
+                    size,
+                    foreground,
+                );
+                Some((
+                    (font, glyph.glyph_id, raster.unwrap_or(GlyphRaster::Sdf)),
+                    (glyph.position, transform),
+                ))
+            })
+            .unzip();
+        let (atlas_glyphs, atlas_views) = self
+            .handle()
+            .glyph_atlas()
+            .prepare(self.handle(), &glyphs)?;
+
+        // Move each glyph into place, skipping the empty or clipped out ones, and draw the others
+        // page by page.
+        let scissor_bounds = Self::scissor_bounds(scissor_rect);
+        let mut instances: Vec<_> = atlas_glyphs
+            .iter()
+            .zip(&glyphs)
+            .zip(transforms)
+            .filter_map(|((atlas_glyph, (_, _, raster)), (position, transform))| {
+                let instance = GlyphInstance::new(atlas_glyph, position, raster.scale(size))?;
+                Some((atlas_glyph.color, instance.transformed(transform)))
+            })
+            .collect();
+        instances.retain(|(_, instance)| instance.bounds().intersects(&scissor_bounds));
+        instances.sort_by_key(|(color, _)| *color);
+
+        for page_instances in instances.chunk_by(|a, b| a.0 == b.0) {
+            let color = page_instances[0].0;
+            let page_instances: Vec<_> = page_instances
+                .iter()
+                .map(|(_, instance)| *instance)
+                .collect();
+            if color {
+                self.draw_color_glyph_instances(
+                    &atlas_views,
+                    &page_instances,
+                    Transform::IDENTITY,
+                    opacity,
+                    scissor_rect,
+                );
+            } else {
+                self.draw_sdf_glyph_instances(
+                    &atlas_views,
+                    &page_instances,
+                    Transform::IDENTITY,
+                    style,
+                    &paint_uniform,
+                    size / SDF_SIZE,
+                    scissor_rect,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Draws glyph quads from the signed distance fields in the coverage page of the
+    /// [GlyphAtlas](crate::GlyphAtlas), within *scissor_rect*.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_sdf_glyph_instances(
+        &self,
+        atlas_views: &AtlasViews,
+        instances: &[GlyphInstance],
+        transform: Transform,
+        style: &SdfTextStyle,
+        paint_uniform: &PaintUniform,
+        scale: f32,
+        scissor_rect: [u32; 4],
+    ) {
+        let uniform = SdfTextUniform {
+            transform: transform.to_wgpu_mat3(),
+            viewport_size: [self.size().width as f32, self.size().height as f32],
+            scale,
+            spread: SDF_SPREAD,
+            outline_color: style.outline_color,
+            glow_color: style.glow_color,
+            outline_width: style.outline_width,
+            glow_radius: style.glow_radius,
+            _padding: [0; 2],
+        };
+
+        let wgpu_device = self.handle().wgpu_device();
+        let wgpu_pipeline = self.wgpu_render_pipeline(PipelineShader::SdfText);
+        let wgpu_uniform_buffer = wgpu_device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::bytes_of(&uniform),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let wgpu_sampler = wgpu_device.create_sampler(&wgpu::SamplerDescriptor {
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let wgpu_bind_group = self.create_wgpu_paint_bind_group(
+            &wgpu_pipeline,
+            paint_uniform,
+            &[
+                wgpu_uniform_buffer.as_entire_binding(),
+                wgpu::BindingResource::TextureView(&atlas_views.wgpu_mask_view),
+                wgpu::BindingResource::Sampler(&wgpu_sampler),
+            ],
+        );
+
+        self.draw_glyph_instances(&wgpu_pipeline, &wgpu_bind_group, instances, scissor_rect);
+    }
+
+    /// Draws glyph quads from the color page of the [GlyphAtlas](crate::GlyphAtlas), within
+    /// *scissor_rect*.
+    ///
+    /// *transform* maps the quads to the pixels of this [Canvas], and *opacity* multiplies their
+    /// colors.
+    fn draw_color_glyph_instances(
+        &self,
+        atlas_views: &AtlasViews,
+        instances: &[GlyphInstance],
+        transform: Transform,
+        opacity: f32,
+        scissor_rect: [u32; 4],
+    ) {
+        let Some(wgpu_color_view) = &atlas_views.wgpu_color_view else {
+            return;
+        };
+        if instances.is_empty() {
+            return;
+        }
+
+        let uniform = ColorTextUniform {
+            transform: transform.to_wgpu_mat3(),
+            viewport_size: [self.size().width as f32, self.size().height as f32],
+            opacity,
+            _padding: 0,
+        };
+
+        let wgpu_device = self.handle().wgpu_device();
+        let wgpu_pipeline = self.wgpu_render_pipeline(PipelineShader::ColorText);
+        let wgpu_uniform_buffer = wgpu_device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::bytes_of(&uniform),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let wgpu_sampler = wgpu_device.create_sampler(&wgpu::SamplerDescriptor {
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let wgpu_bind_group = wgpu_device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &wgpu_pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu_uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(wgpu_color_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&wgpu_sampler),
+                },
+            ],
+        });
+
+        self.draw_glyph_instances(&wgpu_pipeline, &wgpu_bind_group, instances, scissor_rect);
+    }
+
+    /// Draws glyph quads with one of the text pipelines, within *scissor_rect*.
+    fn draw_glyph_instances(
+        &self,
+        wgpu_pipeline: &wgpu::RenderPipeline,
+        wgpu_bind_group: &wgpu::BindGroup,
+        instances: &[GlyphInstance],
+        scissor_rect: [u32; 4],
+    ) {
+        let wgpu_device = self.handle().wgpu_device();
+        let wgpu_instance_buffer = wgpu_device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::cast_slice(instances),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let mut encoder =
+            wgpu_device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+
+        let wgpu_texture_view = self.create_wgpu_target_view();
+        let wgpu_clip_bind_group = self.create_wgpu_clip_bind_group(&mut encoder, wgpu_pipeline);
+
+        {
+            let mut render_pass =
+                self.begin_render_pass(&mut encoder, &wgpu_texture_view, scissor_rect);
+
+            render_pass.set_pipeline(wgpu_pipeline);
+            render_pass.set_bind_group(0, wgpu_bind_group, &[]);
+            if let Some(wgpu_clip_bind_group) = &wgpu_clip_bind_group {
+                render_pass.set_bind_group(1, wgpu_clip_bind_group, &[]);
+            }
+            render_pass.set_vertex_buffer(0, wgpu_instance_buffer.slice(..));
+            render_pass.draw(0..6, 0..instances.len() as u32);
+        }
+
+        // Submit to be drawn.
+        self.handle()
+            .wgpu_queue()
+            .submit(std::iter::once(encoder.finish()));
+    }
+}
+
+/// Returns the color filling the layers of color glyphs which use the color of the text, and the
+/// opacity of color glyphs, when drawing text with the provided [Paint].
+fn color_glyph_paint(paint: &Paint) -> (Color, f32) {
+    match paint {
+        Paint::Solid(color) => (color.with_alpha(1.0), color.alpha),
+        Paint::LinearGradient(LinearGradient { stops, .. })
+        | Paint::RadialGradient(RadialGradient { stops, .. })
+        | Paint::SweepGradient(SweepGradient { stops, .. }) => {
+            (stops.first().map_or(Color::WHITE, |stop| stop.color), 1.0)
+        }
+        Paint::Fill => (Color::WHITE, 1.0),
+    }
+}
+
+/// Returns the bounds of a [Rect] once transformed.
+fn transformed_bounds(rect: Rect, transform: Transform) -> Rect {
+    let (min, max) = (rect.min(), rect.max());
+    let corners = [min, Vec2::new(max.x, min.y), Vec2::new(min.x, max.y), max]
+        .map(|corner| transform.transform_point(corner));
+    let (min, max) = corners[1..]
+        .iter()
+        .fold((corners[0], corners[0]), |(min, max), corner| {
+            (
+                Vec2::new(min.x.min(corner.x), min.y.min(corner.y)),
+                Vec2::new(max.x.max(corner.x), max.y.max(corner.y)),
+            )
+        });
+    Rect::from_min_max(min, max)
+}
+
+/// The lines a [Path] is flattened to, to find the points at given distances along it.
+struct PathMeasure {
+    /// The start and end of each line, and the distance along the path it starts at.
+    lines: Vec<(Vec2, Vec2, f32)>,
+}
+
+impl PathMeasure {
+    fn new(path: &Path) -> Self {
+        let mut lines = Vec::new();
+        let mut distance = 0.0;
+        for contour in path.flatten(FLATTEN_TOLERANCE) {
+            for line in contour.windows(2) {
+                let length = (line[1] - line[0]).length();
+                if length > 0.0 {
+                    lines.push((line[0], line[1], distance));
+                    distance += length;
+                }
+            }
+        }
+
+        Self { lines }
+    }
+
+    /// Returns the point at *distance* along the path and the direction of the path there, or
+    /// [None] if *distance* is past either end of the path.
+    fn position(&self, distance: f32) -> Option<(Vec2, Vec2)> {
+        if distance < 0.0 {
+            return None;
+        }
+
+        let index = self
+            .lines
+            .partition_point(|&(_, _, start)| start <= distance)
+            .checked_sub(1)?;
+        let (start, end, start_distance) = self.lines[index];
+        let direction = end - start;
+        let t = (distance - start_distance) / direction.length();
+        (t <= 1.0).then(|| (start.lerp(end, t), direction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        canvas::tests::{handle, pixel},
+        BitmapFontDescriptor, Dimension,
+    };
+
+    #[test]
+    fn glyphs_keep_the_pixels_around_them() {
+        let handle = handle();
+
+        // An 8x8 glyph whose middle 4x4 pixels are opaque white.
+        let descriptor = BitmapFontDescriptor::from_bytes(
+            br#"info face="Block" size=8
+common lineHeight=8 base=8 scaleW=8 scaleH=8 pages=1
+page id=0 file="block.png"
+char id=65 x=0 y=0 width=8 height=8 xoffset=0 yoffset=0 xadvance=8 page=0 chnl=15
+"#,
+        )
+        .unwrap();
+        let page: Vec<u8> = (0..8)
+            .flat_map(|y| {
+                (0..8).flat_map(move |x| {
+                    if (2..6).contains(&x) && (2..6).contains(&y) {
+                        [255; 4]
+                    } else {
+                        [0; 4]
+                    }
+                })
+            })
+            .collect();
+        let page = handle.make_texture(Dimension::new(8, 8), &page).unwrap();
+        let font = BitmapFont::new(descriptor, vec![page]).unwrap();
+
+        let texture = handle.make_blank_texture(Dimension::new(16, 16));
+        let canvas = texture.canvas();
+        canvas.clear(Color::RED).unwrap();
+        canvas
+            .draw_text(
+                &font,
+                8.0,
+                Vec2::new(4.0, 12.0),
+                "A",
+                &Paint::Solid(Color::BLUE),
+            )
+            .unwrap();
+
+        assert_eq!(pixel(&canvas, 8, 8), [0, 0, 255, 255]);
+        // The transparent pixels of the glyph's quad.
+        assert_eq!(pixel(&canvas, 4, 4), [255, 0, 0, 255]);
+        assert_eq!(pixel(&canvas, 11, 11), [255, 0, 0, 255]);
+    }
+}
@@ -0,0 +1,194 @@
+use std::{io::Cursor, path::Path};
+
+use image::{DynamicImage, ImageBuffer, ImageOutputFormat, Luma, Rgba};
+
+use crate::{
+    linear_to_srgb, pixels::f16_to_f32, srgb_to_linear, Dimension, Error, Pixels, TextureFormat,
+};
+
+/// Decodes an image file into data in the provided [TextureFormat], with straight alpha.
+///
+/// Color formats storing linear data get linear colors, and `R8Unorm` gets the luminance of the
+/// image.
+pub(crate) fn decode_image(image: DynamicImage, format: TextureFormat) -> (Dimension, Vec<u8>) {
+    let size = Dimension::new(image.width(), image.height());
+    let data = match format {
+        TextureFormat::Rgba8UnormSrgb => image.into_rgba8().into_raw(),
+        TextureFormat::Bgra8UnormSrgb => {
+            let mut data = image.into_rgba8().into_raw();
+            data.chunks_exact_mut(4).for_each(|pixel| pixel.swap(0, 2));
+            data
+        }
+        TextureFormat::Rgba8Unorm | TextureFormat::Bgra8Unorm => {
+            let swap = format == TextureFormat::Bgra8Unorm;
+            image
+                .into_rgba32f()
+                .pixels()
+                .flat_map(|&Rgba([r, g, b, a])| {
+                    let (r, b) = if swap { (b, r) } else { (r, b) };
+                    [srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a]
+                        .map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+                })
+                .collect()
+        }
+        TextureFormat::Rgba16Float => image
+            .into_rgba32f()
+            .pixels()
+            .flat_map(|&Rgba([r, g, b, a])| {
+                [srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a]
+            })
+            .flat_map(|channel| f32_to_f16(channel).to_le_bytes())
+            .collect(),
+        TextureFormat::R8Unorm => image.into_luma8().into_raw(),
+    };
+    (size, data)
+}
+
+/// Reads and decodes an image file, guessing its format from its contents.
+pub(crate) fn read_image_from_bytes(bytes: &[u8]) -> Result<DynamicImage, Error> {
+    image::io::Reader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|err| Error::FailedToLoadImage(err.to_string()))?
+        .decode()
+        .map_err(|err| Error::FailedToLoadImage(err.to_string()))
+}
+
+/// Reads and decodes an image file, guessing its format from its contents or extension.
+pub(crate) fn read_image_from_path(path: &Path) -> Result<DynamicImage, Error> {
+    image::io::Reader::open(path)
+        .and_then(|reader| reader.with_guessed_format())
+        .map_err(|err| Error::FailedToLoadImage(err.to_string()))?
+        .decode()
+        .map_err(|err| Error::FailedToLoadImage(err.to_string()))
+}
+
+impl Pixels {
+    /// Encodes these [Pixels] as a PNG file.
+    ///
+    /// The pixels are converted back from the color space of their format, like
+    /// [Handle::load_texture_from_bytes](crate::Handle::load_texture_from_bytes) converts them
+    /// to it: 8-bit formats storing linear data become 8-bit sRGB, `Rgba16Float` becomes 16-bit
+    /// sRGB and `R8Unorm` becomes grayscale.
+    ///
+    /// Only available with the `image` feature.
+    ///
+    /// # Fails
+    /// Fails if the pixels don't match their size.
+    pub fn encode_png(&self) -> Result<Vec<u8>, Error> {
+        let mut png = Cursor::new(Vec::new());
+        self.to_image()?
+            .write_to(&mut png, ImageOutputFormat::Png)
+            .map_err(|err| Error::FailedToSaveImage(err.to_string()))?;
+        Ok(png.into_inner())
+    }
+
+    /// Encodes these [Pixels] like [Pixels::encode_png] and writes them to a file, in the format
+    /// matching its extension: PNG, JPEG, GIF, BMP or QOI.
+    ///
+    /// Only available with the `image` feature.
+    ///
+    /// # Fails
+    /// - Fails if the pixels don't match their size.
+    /// - Fails if the extension of the path doesn't match a supported format, or the format can't
+    ///   encode these [Pixels].
+    /// - Fails if the file can't be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        self.to_image()?
+            .save(path)
+            .map_err(|err| Error::FailedToSaveImage(err.to_string()))
+    }
+
+    /// Converts these [Pixels] to an image.
+    fn to_image(&self) -> Result<DynamicImage, Error> {
+        let format = self.format();
+        let Dimension { width, height } = self.size();
+
+        let image = match format {
+            TextureFormat::Rgba8UnormSrgb | TextureFormat::Bgra8UnormSrgb => {
+                let mut data = self.to_vec();
+                if format == TextureFormat::Bgra8UnormSrgb {
+                    data.chunks_exact_mut(4).for_each(|pixel| pixel.swap(0, 2));
+                }
+                ImageBuffer::<Rgba<u8>, _>::from_raw(width, height, data).map(DynamicImage::from)
+            }
+            TextureFormat::Rgba8Unorm | TextureFormat::Bgra8Unorm => {
+                let swap = format == TextureFormat::Bgra8Unorm;
+                let data = self
+                    .chunks_exact(4)
+                    .flat_map(|pixel| {
+                        let (r, b) = if swap {
+                            (pixel[2], pixel[0])
+                        } else {
+                            (pixel[0], pixel[2])
+                        };
+                        [r, pixel[1], b]
+                            .map(|channel| {
+                                (linear_to_srgb(channel as f32 / 255.0) * 255.0).round() as u8
+                            })
+                            .into_iter()
+                            .chain([pixel[3]])
+                    })
+                    .collect();
+                ImageBuffer::<Rgba<u8>, _>::from_raw(width, height, data).map(DynamicImage::from)
+            }
+            TextureFormat::Rgba16Float => {
+                let data = self
+                    .chunks_exact(8)
+                    .flat_map(|pixel| {
+                        let channel = |index: usize| {
+                            f16_to_f32(u16::from_le_bytes([pixel[index * 2], pixel[index * 2 + 1]]))
+                        };
+                        [
+                            linear_to_srgb(channel(0)),
+                            linear_to_srgb(channel(1)),
+                            linear_to_srgb(channel(2)),
+                            channel(3),
+                        ]
+                        .map(|channel| (channel.clamp(0.0, 1.0) * 65535.0).round() as u16)
+                    })
+                    .collect();
+                ImageBuffer::<Rgba<u16>, _>::from_raw(width, height, data).map(DynamicImage::from)
+            }
+            TextureFormat::R8Unorm => {
+                ImageBuffer::<Luma<u8>, _>::from_raw(width, height, self.to_vec())
+                    .map(DynamicImage::from)
+            }
+        };
+
+        image
+            .ok_or_else(|| Error::FailedToSaveImage("the pixels don't match their size".to_owned()))
+    }
+}
+
+/// Converts a 32-bit float to the bits of the nearest 16-bit float.
+fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+
+    if exponent == 0xff {
+        // Infinity or NaN.
+        return sign | 0x7c00 | if mantissa != 0 { 0x200 } else { 0 };
+    }
+
+    let exponent = exponent - 127 + 15;
+    if exponent >= 0x1f {
+        // Too large, rounds to infinity.
+        sign | 0x7c00
+    } else if exponent <= 0 {
+        // Subnormal, or too small and rounds to zero.
+        if exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x80_0000;
+        let shift = (14 - exponent) as u32;
+        let half = (mantissa >> shift) as u16;
+        let round = ((mantissa >> (shift - 1)) & 1) as u16;
+        sign | (half + round)
+    } else {
+        let half = sign | ((exponent as u16) << 10) | (mantissa >> 13) as u16;
+        // Round to nearest, carrying into the exponent if needed.
+        half + ((mantissa >> 12) & 1) as u16
+    }
+}
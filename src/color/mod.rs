@@ -0,0 +1,447 @@
+mod named;
+mod space;
+
+pub use space::*;
+
+use std::{
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Sub, SubAssign},
+    str::FromStr,
+};
+
+use crate::Error;
+
+/// Represents an RGBA color.
+///
+/// The color conversions of [Color] (hex codes, [Hsl], [Hsv], [Oklab], ...) treat the red, green
+/// and blue channels as sRGB-encoded, like CSS does.  G2d hands the channels to the GPU as-is,
+/// which treats them as linear values when drawing to an sRGB texture, so convert colors with
+/// [Color::to_linear] first to get the same result as a browser.
+#[derive(bytemuck::Zeroable, bytemuck::Pod, Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+#[repr(C)]
+pub struct Color {
+    pub red: f32,
+    pub green: f32,
+    pub blue: f32,
+    pub alpha: f32,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::new(0.0, 0.0, 0.0, 1.0);
+    pub const WHITE: Self = Self::new(1.0, 1.0, 1.0, 1.0);
+
+    /// Creates a new [Color] from the provided red, green, blue and alpha channels.
+    #[inline]
+    pub const fn new(red: f32, green: f32, blue: f32, alpha: f32) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+        }
+    }
+
+    /// Creates an opaque [Color] from the provided red, green and blue channels.
+    #[inline]
+    pub const fn rgb(red: f32, green: f32, blue: f32) -> Self {
+        Self::new(red, green, blue, 1.0)
+    }
+
+    /// Creates a new [Color] from 8-bit red, green, blue and alpha channels.
+    #[inline]
+    pub const fn from_rgba8(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+        Self::new(
+            red as f32 / 255.0,
+            green as f32 / 255.0,
+            blue as f32 / 255.0,
+            alpha as f32 / 255.0,
+        )
+    }
+
+    /// Creates an opaque [Color] from 8-bit red, green and blue channels.
+    #[inline]
+    pub const fn from_rgb8(red: u8, green: u8, blue: u8) -> Self {
+        Self::from_rgba8(red, green, blue, 255)
+    }
+
+    /// Parses a hex color code in the `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa` form.  The leading
+    /// `#` is optional.
+    ///
+    /// # Fails
+    /// - Fails if the string isn't a valid hex color code.
+    pub fn from_hex(hex: &str) -> Result<Self, Error> {
+        let invalid = || Error::InvalidColor(hex.to_owned());
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+
+        // `u8::from_str_radix` would also accept a leading `+`.
+        if !digits.chars().all(|digit| digit.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+
+        // Expands the channels of the short forms, `#abc` is equivalent to `#aabbcc`.
+        let channel = |index: usize, width: usize| -> Result<u8, Error> {
+            let value = u8::from_str_radix(&digits[index * width..(index + 1) * width], 16)
+                .map_err(|_| invalid())?;
+            Ok(if width == 1 { value * 17 } else { value })
+        };
+
+        match digits.len() {
+            3 => Ok(Self::from_rgb8(
+                channel(0, 1)?,
+                channel(1, 1)?,
+                channel(2, 1)?,
+            )),
+            4 => Ok(Self::from_rgba8(
+                channel(0, 1)?,
+                channel(1, 1)?,
+                channel(2, 1)?,
+                channel(3, 1)?,
+            )),
+            6 => Ok(Self::from_rgb8(
+                channel(0, 2)?,
+                channel(1, 2)?,
+                channel(2, 2)?,
+            )),
+            8 => Ok(Self::from_rgba8(
+                channel(0, 2)?,
+                channel(1, 2)?,
+                channel(2, 2)?,
+                channel(3, 2)?,
+            )),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Returns the CSS named color with the provided name, ignoring case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+
+        named::NAMED_COLORS
+            .binary_search_by(|(color_name, _)| (*color_name).cmp(name.as_str()))
+            .ok()
+            .map(|index| named::NAMED_COLORS[index].1)
+    }
+
+    /// Formats this [Color] as a `#rrggbbaa` hex color code.
+    pub fn to_hex(&self) -> String {
+        let [red, green, blue, alpha] = self.to_rgba_bytes();
+        format!("#{red:02x}{green:02x}{blue:02x}{alpha:02x}")
+    }
+
+    /// Converts a [Color] to its associated RGBA bytes.
+    ///
+    /// Channels are clamped to the range `0.0..=1.0` and rounded to the nearest byte.
+    #[inline]
+    pub fn to_rgba_bytes(&self) -> [u8; 4] {
+        let to_byte = |channel: f32| (channel.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        [
+            to_byte(self.red),
+            to_byte(self.green),
+            to_byte(self.blue),
+            to_byte(self.alpha),
+        ]
+    }
+
+    /// Converts this [Color] to a [`wgpu::Color`].
+    #[inline]
+    pub const fn to_wgpu_color(&self) -> wgpu::Color {
+        wgpu::Color {
+            r: self.red as f64,
+            g: self.green as f64,
+            b: self.blue as f64,
+            a: self.alpha as f64,
+        }
+    }
+
+    /// Returns this [Color] with its alpha channel replaced.
+    #[inline]
+    pub const fn with_alpha(mut self, alpha: f32) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// Clamps every channel of this [Color] to the range `0.0..=1.0`.
+    #[inline]
+    pub fn clamp(&self) -> Self {
+        self.map_channels(|channel| channel.clamp(0.0, 1.0))
+    }
+
+    /// Returns this [Color] with its red, green and blue channels multiplied by its alpha.
+    #[inline]
+    pub fn premultiplied(&self) -> Self {
+        Self::new(
+            self.red * self.alpha,
+            self.green * self.alpha,
+            self.blue * self.alpha,
+            self.alpha,
+        )
+    }
+
+    /// Converts the red, green and blue channels of this [Color] from sRGB to linear using the
+    /// sRGB transfer function.  Alpha is left unchanged.
+    #[inline]
+    pub fn to_linear(&self) -> Self {
+        Self::new(
+            srgb_to_linear(self.red),
+            srgb_to_linear(self.green),
+            srgb_to_linear(self.blue),
+            self.alpha,
+        )
+    }
+
+    /// Converts the red, green and blue channels of this [Color] from linear to sRGB using the
+    /// inverse sRGB transfer function.  Alpha is left unchanged.
+    #[inline]
+    pub fn to_srgb(&self) -> Self {
+        Self::new(
+            linear_to_srgb(self.red),
+            linear_to_srgb(self.green),
+            linear_to_srgb(self.blue),
+            self.alpha,
+        )
+    }
+
+    /// Linearly interpolates between this [Color] and *other* in the provided [ColorSpace].
+    ///
+    /// *t* is the interpolation factor, `0.0` returns this color and `1.0` returns *other*.
+    pub fn lerp(&self, other: Color, t: f32, space: ColorSpace) -> Self {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+
+        match space {
+            ColorSpace::Srgb => Self::new(
+                lerp(self.red, other.red),
+                lerp(self.green, other.green),
+                lerp(self.blue, other.blue),
+                lerp(self.alpha, other.alpha),
+            ),
+            ColorSpace::LinearSrgb => self
+                .to_linear()
+                .lerp(other.to_linear(), t, ColorSpace::Srgb)
+                .to_srgb(),
+            ColorSpace::Oklab => {
+                let (from, to) = (Oklab::from(*self), Oklab::from(other));
+
+                Oklab::new(
+                    lerp(from.lightness, to.lightness),
+                    lerp(from.a, to.a),
+                    lerp(from.b, to.b),
+                    lerp(from.alpha, to.alpha),
+                )
+                .into()
+            }
+            ColorSpace::Oklch => {
+                let (from, to) = (Oklch::from(*self), Oklch::from(other));
+
+                Oklch::new(
+                    lerp(from.lightness, to.lightness),
+                    lerp(from.chroma, to.chroma),
+                    lerp_hue(from.hue, to.hue, t),
+                    lerp(from.alpha, to.alpha),
+                )
+                .into()
+            }
+            ColorSpace::Hsl => {
+                let (from, to) = (Hsl::from(*self), Hsl::from(other));
+
+                Hsl::new(
+                    lerp_hue(from.hue, to.hue, t),
+                    lerp(from.saturation, to.saturation),
+                    lerp(from.lightness, to.lightness),
+                    lerp(from.alpha, to.alpha),
+                )
+                .into()
+            }
+        }
+    }
+
+    /// Applies the provided function to every channel of this [Color], including alpha.
+    #[inline]
+    fn map_channels(&self, f: impl Fn(f32) -> f32) -> Self {
+        Self::new(f(self.red), f(self.green), f(self.blue), f(self.alpha))
+    }
+
+    /// Combines the channels of this [Color] and *other* with the provided function.
+    #[inline]
+    fn zip_channels(&self, other: Color, f: impl Fn(f32, f32) -> f32) -> Self {
+        Self::new(
+            f(self.red, other.red),
+            f(self.green, other.green),
+            f(self.blue, other.blue),
+            f(self.alpha, other.alpha),
+        )
+    }
+}
+
+impl FromStr for Color {
+    type Err = Error;
+
+    /// Parses a hex color code (see [Color::from_hex]) or a CSS named color (see
+    /// [Color::from_name]).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if s.starts_with('#') {
+            Self::from_hex(s)
+        } else {
+            Self::from_name(s)
+                .map_or_else(|| Self::from_hex(s), Ok)
+                .map_err(|_| Error::InvalidColor(s.to_owned()))
+        }
+    }
+}
+
+impl Add for Color {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_channels(rhs, |a, b| a + b)
+    }
+}
+
+impl Sub for Color {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_channels(rhs, |a, b| a - b)
+    }
+}
+
+impl Mul for Color {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.zip_channels(rhs, |a, b| a * b)
+    }
+}
+
+impl Mul<f32> for Color {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: f32) -> Self::Output {
+        self.map_channels(|channel| channel * rhs)
+    }
+}
+
+impl Div<f32> for Color {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, rhs: f32) -> Self::Output {
+        self.map_channels(|channel| channel / rhs)
+    }
+}
+
+impl AddAssign for Color {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Color {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for Color {
+    #[inline]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl MulAssign<f32> for Color {
+    #[inline]
+    fn mul_assign(&mut self, rhs: f32) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign<f32> for Color {
+    #[inline]
+    fn div_assign(&mut self, rhs: f32) {
+        *self = *self / rhs;
+    }
+}
+
+/// Converts an sRGB-encoded channel to linear light.
+#[inline]
+pub fn srgb_to_linear(channel: f32) -> f32 {
+    if channel <= 0.04045 {
+        channel / 12.92
+    } else {
+        ((channel + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts a channel in linear light to sRGB encoding.
+#[inline]
+pub fn linear_to_srgb(channel: f32) -> f32 {
+    if channel <= 0.0031308 {
+        channel * 12.92
+    } else {
+        1.055 * channel.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Interpolates between two hues in degrees along the shortest arc.
+fn lerp_hue(from: f32, to: f32, t: f32) -> f32 {
+    let delta = (to - from + 540.0).rem_euclid(360.0) - 180.0;
+    (from + delta * t).rem_euclid(360.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_hex_parses_every_form() {
+        let bytes = |hex| Color::from_hex(hex).unwrap().to_rgba_bytes();
+
+        assert_eq!(bytes("#f80"), [255, 136, 0, 255]);
+        assert_eq!(bytes("#f808"), [255, 136, 0, 136]);
+        assert_eq!(bytes("#12aBcD"), [0x12, 0xab, 0xcd, 255]);
+        assert_eq!(bytes("#12abcd80"), [0x12, 0xab, 0xcd, 0x80]);
+        assert_eq!(bytes("12abcd"), [0x12, 0xab, 0xcd, 255]);
+    }
+
+    #[test]
+    fn from_hex_rejects_invalid_codes() {
+        for hex in [
+            "",
+            "#",
+            "#f",
+            "#ff",
+            "#fffff",
+            "#fffffff",
+            "#fffffffff",
+            "#+f0000",
+            "#+f0",
+            "#-f0",
+            "#ggg",
+            "#12345z",
+            "# 12345",
+            "##fff",
+            "#ff ",
+            "#ffé",
+        ] {
+            assert!(
+                matches!(Color::from_hex(hex), Err(Error::InvalidColor(_))),
+                "{hex:?} was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn to_hex_round_trips() {
+        let color = Color::from_rgba8(0x12, 0xab, 0xcd, 0x80);
+        assert_eq!(color.to_hex(), "#12abcd80");
+        assert_eq!(Color::from_hex(&color.to_hex()).unwrap(), color);
+    }
+}
@@ -0,0 +1,450 @@
+use super::Color;
+
+impl Color {
+    /// Fully transparent black.
+    pub const TRANSPARENT: Self = Self::new(0.0, 0.0, 0.0, 0.0);
+    /// CSS `aliceblue` (`#f0f8ff`).
+    pub const ALICE_BLUE: Self = Self::from_rgb8(240, 248, 255);
+    /// CSS `antiquewhite` (`#faebd7`).
+    pub const ANTIQUE_WHITE: Self = Self::from_rgb8(250, 235, 215);
+    /// CSS `aqua` (`#00ffff`).
+    pub const AQUA: Self = Self::from_rgb8(0, 255, 255);
+    /// CSS `aquamarine` (`#7fffd4`).
+    pub const AQUAMARINE: Self = Self::from_rgb8(127, 255, 212);
+    /// CSS `azure` (`#f0ffff`).
+    pub const AZURE: Self = Self::from_rgb8(240, 255, 255);
+    /// CSS `beige` (`#f5f5dc`).
+    pub const BEIGE: Self = Self::from_rgb8(245, 245, 220);
+    /// CSS `bisque` (`#ffe4c4`).
+    pub const BISQUE: Self = Self::from_rgb8(255, 228, 196);
+    /// CSS `blanchedalmond` (`#ffebcd`).
+    pub const BLANCHED_ALMOND: Self = Self::from_rgb8(255, 235, 205);
+    /// CSS `blue` (`#0000ff`).
+    pub const BLUE: Self = Self::from_rgb8(0, 0, 255);
+    /// CSS `blueviolet` (`#8a2be2`).
+    pub const BLUE_VIOLET: Self = Self::from_rgb8(138, 43, 226);
+    /// CSS `brown` (`#a52a2a`).
+    pub const BROWN: Self = Self::from_rgb8(165, 42, 42);
+    /// CSS `burlywood` (`#deb887`).
+    pub const BURLYWOOD: Self = Self::from_rgb8(222, 184, 135);
+    /// CSS `cadetblue` (`#5f9ea0`).
+    pub const CADET_BLUE: Self = Self::from_rgb8(95, 158, 160);
+    /// CSS `chartreuse` (`#7fff00`).
+    pub const CHARTREUSE: Self = Self::from_rgb8(127, 255, 0);
+    /// CSS `chocolate` (`#d2691e`).
+    pub const CHOCOLATE: Self = Self::from_rgb8(210, 105, 30);
+    /// CSS `coral` (`#ff7f50`).
+    pub const CORAL: Self = Self::from_rgb8(255, 127, 80);
+    /// CSS `cornflowerblue` (`#6495ed`).
+    pub const CORNFLOWER_BLUE: Self = Self::from_rgb8(100, 149, 237);
+    /// CSS `cornsilk` (`#fff8dc`).
+    pub const CORNSILK: Self = Self::from_rgb8(255, 248, 220);
+    /// CSS `crimson` (`#dc143c`).
+    pub const CRIMSON: Self = Self::from_rgb8(220, 20, 60);
+    /// CSS `cyan` (`#00ffff`).
+    pub const CYAN: Self = Self::from_rgb8(0, 255, 255);
+    /// CSS `darkblue` (`#00008b`).
+    pub const DARK_BLUE: Self = Self::from_rgb8(0, 0, 139);
+    /// CSS `darkcyan` (`#008b8b`).
+    pub const DARK_CYAN: Self = Self::from_rgb8(0, 139, 139);
+    /// CSS `darkgoldenrod` (`#b8860b`).
+    pub const DARK_GOLDENROD: Self = Self::from_rgb8(184, 134, 11);
+    /// CSS `darkgray` (`#a9a9a9`).
+    pub const DARK_GRAY: Self = Self::from_rgb8(169, 169, 169);
+    /// CSS `darkgreen` (`#006400`).
+    pub const DARK_GREEN: Self = Self::from_rgb8(0, 100, 0);
+    /// CSS `darkgrey` (`#a9a9a9`).
+    pub const DARK_GREY: Self = Self::from_rgb8(169, 169, 169);
+    /// CSS `darkkhaki` (`#bdb76b`).
+    pub const DARK_KHAKI: Self = Self::from_rgb8(189, 183, 107);
+    /// CSS `darkmagenta` (`#8b008b`).
+    pub const DARK_MAGENTA: Self = Self::from_rgb8(139, 0, 139);
+    /// CSS `darkolivegreen` (`#556b2f`).
+    pub const DARK_OLIVE_GREEN: Self = Self::from_rgb8(85, 107, 47);
+    /// CSS `darkorange` (`#ff8c00`).
+    pub const DARK_ORANGE: Self = Self::from_rgb8(255, 140, 0);
+    /// CSS `darkorchid` (`#9932cc`).
+    pub const DARK_ORCHID: Self = Self::from_rgb8(153, 50, 204);
+    /// CSS `darkred` (`#8b0000`).
+    pub const DARK_RED: Self = Self::from_rgb8(139, 0, 0);
+    /// CSS `darksalmon` (`#e9967a`).
+    pub const DARK_SALMON: Self = Self::from_rgb8(233, 150, 122);
+    /// CSS `darkseagreen` (`#8fbc8f`).
+    pub const DARK_SEA_GREEN: Self = Self::from_rgb8(143, 188, 143);
+    /// CSS `darkslateblue` (`#483d8b`).
+    pub const DARK_SLATE_BLUE: Self = Self::from_rgb8(72, 61, 139);
+    /// CSS `darkslategray` (`#2f4f4f`).
+    pub const DARK_SLATE_GRAY: Self = Self::from_rgb8(47, 79, 79);
+    /// CSS `darkslategrey` (`#2f4f4f`).
+    pub const DARK_SLATE_GREY: Self = Self::from_rgb8(47, 79, 79);
+    /// CSS `darkturquoise` (`#00ced1`).
+    pub const DARK_TURQUOISE: Self = Self::from_rgb8(0, 206, 209);
+    /// CSS `darkviolet` (`#9400d3`).
+    pub const DARK_VIOLET: Self = Self::from_rgb8(148, 0, 211);
+    /// CSS `deeppink` (`#ff1493`).
+    pub const DEEP_PINK: Self = Self::from_rgb8(255, 20, 147);
+    /// CSS `deepskyblue` (`#00bfff`).
+    pub const DEEP_SKY_BLUE: Self = Self::from_rgb8(0, 191, 255);
+    /// CSS `dimgray` (`#696969`).
+    pub const DIM_GRAY: Self = Self::from_rgb8(105, 105, 105);
+    /// CSS `dimgrey` (`#696969`).
+    pub const DIM_GREY: Self = Self::from_rgb8(105, 105, 105);
+    /// CSS `dodgerblue` (`#1e90ff`).
+    pub const DODGER_BLUE: Self = Self::from_rgb8(30, 144, 255);
+    /// CSS `firebrick` (`#b22222`).
+    pub const FIREBRICK: Self = Self::from_rgb8(178, 34, 34);
+    /// CSS `floralwhite` (`#fffaf0`).
+    pub const FLORAL_WHITE: Self = Self::from_rgb8(255, 250, 240);
+    /// CSS `forestgreen` (`#228b22`).
+    pub const FOREST_GREEN: Self = Self::from_rgb8(34, 139, 34);
+    /// CSS `fuchsia` (`#ff00ff`).
+    pub const FUCHSIA: Self = Self::from_rgb8(255, 0, 255);
+    /// CSS `gainsboro` (`#dcdcdc`).
+    pub const GAINSBORO: Self = Self::from_rgb8(220, 220, 220);
+    /// CSS `ghostwhite` (`#f8f8ff`).
+    pub const GHOST_WHITE: Self = Self::from_rgb8(248, 248, 255);
+    /// CSS `gold` (`#ffd700`).
+    pub const GOLD: Self = Self::from_rgb8(255, 215, 0);
+    /// CSS `goldenrod` (`#daa520`).
+    pub const GOLDENROD: Self = Self::from_rgb8(218, 165, 32);
+    /// CSS `gray` (`#808080`).
+    pub const GRAY: Self = Self::from_rgb8(128, 128, 128);
+    /// CSS `green` (`#008000`).
+    pub const GREEN: Self = Self::from_rgb8(0, 128, 0);
+    /// CSS `greenyellow` (`#adff2f`).
+    pub const GREEN_YELLOW: Self = Self::from_rgb8(173, 255, 47);
+    /// CSS `grey` (`#808080`).
+    pub const GREY: Self = Self::from_rgb8(128, 128, 128);
+    /// CSS `honeydew` (`#f0fff0`).
+    pub const HONEYDEW: Self = Self::from_rgb8(240, 255, 240);
+    /// CSS `hotpink` (`#ff69b4`).
+    pub const HOT_PINK: Self = Self::from_rgb8(255, 105, 180);
+    /// CSS `indianred` (`#cd5c5c`).
+    pub const INDIAN_RED: Self = Self::from_rgb8(205, 92, 92);
+    /// CSS `indigo` (`#4b0082`).
+    pub const INDIGO: Self = Self::from_rgb8(75, 0, 130);
+    /// CSS `ivory` (`#fffff0`).
+    pub const IVORY: Self = Self::from_rgb8(255, 255, 240);
+    /// CSS `khaki` (`#f0e68c`).
+    pub const KHAKI: Self = Self::from_rgb8(240, 230, 140);
+    /// CSS `lavender` (`#e6e6fa`).
+    pub const LAVENDER: Self = Self::from_rgb8(230, 230, 250);
+    /// CSS `lavenderblush` (`#fff0f5`).
+    pub const LAVENDER_BLUSH: Self = Self::from_rgb8(255, 240, 245);
+    /// CSS `lawngreen` (`#7cfc00`).
+    pub const LAWN_GREEN: Self = Self::from_rgb8(124, 252, 0);
+    /// CSS `lemonchiffon` (`#fffacd`).
+    pub const LEMON_CHIFFON: Self = Self::from_rgb8(255, 250, 205);
+    /// CSS `lightblue` (`#add8e6`).
+    pub const LIGHT_BLUE: Self = Self::from_rgb8(173, 216, 230);
+    /// CSS `lightcoral` (`#f08080`).
+    pub const LIGHT_CORAL: Self = Self::from_rgb8(240, 128, 128);
+    /// CSS `lightcyan` (`#e0ffff`).
+    pub const LIGHT_CYAN: Self = Self::from_rgb8(224, 255, 255);
+    /// CSS `lightgoldenrodyellow` (`#fafad2`).
+    pub const LIGHT_GOLDENROD_YELLOW: Self = Self::from_rgb8(250, 250, 210);
+    /// CSS `lightgray` (`#d3d3d3`).
+    pub const LIGHT_GRAY: Self = Self::from_rgb8(211, 211, 211);
+    /// CSS `lightgreen` (`#90ee90`).
+    pub const LIGHT_GREEN: Self = Self::from_rgb8(144, 238, 144);
+    /// CSS `lightgrey` (`#d3d3d3`).
+    pub const LIGHT_GREY: Self = Self::from_rgb8(211, 211, 211);
+    /// CSS `lightpink` (`#ffb6c1`).
+    pub const LIGHT_PINK: Self = Self::from_rgb8(255, 182, 193);
+    /// CSS `lightsalmon` (`#ffa07a`).
+    pub const LIGHT_SALMON: Self = Self::from_rgb8(255, 160, 122);
+    /// CSS `lightseagreen` (`#20b2aa`).
+    pub const LIGHT_SEA_GREEN: Self = Self::from_rgb8(32, 178, 170);
+    /// CSS `lightskyblue` (`#87cefa`).
+    pub const LIGHT_SKY_BLUE: Self = Self::from_rgb8(135, 206, 250);
+    /// CSS `lightslategray` (`#778899`).
+    pub const LIGHT_SLATE_GRAY: Self = Self::from_rgb8(119, 136, 153);
+    /// CSS `lightslategrey` (`#778899`).
+    pub const LIGHT_SLATE_GREY: Self = Self::from_rgb8(119, 136, 153);
+    /// CSS `lightsteelblue` (`#b0c4de`).
+    pub const LIGHT_STEEL_BLUE: Self = Self::from_rgb8(176, 196, 222);
+    /// CSS `lightyellow` (`#ffffe0`).
+    pub const LIGHT_YELLOW: Self = Self::from_rgb8(255, 255, 224);
+    /// CSS `lime` (`#00ff00`).
+    pub const LIME: Self = Self::from_rgb8(0, 255, 0);
+    /// CSS `limegreen` (`#32cd32`).
+    pub const LIME_GREEN: Self = Self::from_rgb8(50, 205, 50);
+    /// CSS `linen` (`#faf0e6`).
+    pub const LINEN: Self = Self::from_rgb8(250, 240, 230);
+    /// CSS `magenta` (`#ff00ff`).
+    pub const MAGENTA: Self = Self::from_rgb8(255, 0, 255);
+    /// CSS `maroon` (`#800000`).
+    pub const MAROON: Self = Self::from_rgb8(128, 0, 0);
+    /// CSS `mediumaquamarine` (`#66cdaa`).
+    pub const MEDIUM_AQUAMARINE: Self = Self::from_rgb8(102, 205, 170);
+    /// CSS `mediumblue` (`#0000cd`).
+    pub const MEDIUM_BLUE: Self = Self::from_rgb8(0, 0, 205);
+    /// CSS `mediumorchid` (`#ba55d3`).
+    pub const MEDIUM_ORCHID: Self = Self::from_rgb8(186, 85, 211);
+    /// CSS `mediumpurple` (`#9370db`).
+    pub const MEDIUM_PURPLE: Self = Self::from_rgb8(147, 112, 219);
+    /// CSS `mediumseagreen` (`#3cb371`).
+    pub const MEDIUM_SEA_GREEN: Self = Self::from_rgb8(60, 179, 113);
+    /// CSS `mediumslateblue` (`#7b68ee`).
+    pub const MEDIUM_SLATE_BLUE: Self = Self::from_rgb8(123, 104, 238);
+    /// CSS `mediumspringgreen` (`#00fa9a`).
+    pub const MEDIUM_SPRING_GREEN: Self = Self::from_rgb8(0, 250, 154);
+    /// CSS `mediumturquoise` (`#48d1cc`).
+    pub const MEDIUM_TURQUOISE: Self = Self::from_rgb8(72, 209, 204);
+    /// CSS `mediumvioletred` (`#c71585`).
+    pub const MEDIUM_VIOLET_RED: Self = Self::from_rgb8(199, 21, 133);
+    /// CSS `midnightblue` (`#191970`).
+    pub const MIDNIGHT_BLUE: Self = Self::from_rgb8(25, 25, 112);
+    /// CSS `mintcream` (`#f5fffa`).
+    pub const MINT_CREAM: Self = Self::from_rgb8(245, 255, 250);
+    /// CSS `mistyrose` (`#ffe4e1`).
+    pub const MISTY_ROSE: Self = Self::from_rgb8(255, 228, 225);
+    /// CSS `moccasin` (`#ffe4b5`).
+    pub const MOCCASIN: Self = Self::from_rgb8(255, 228, 181);
+    /// CSS `navajowhite` (`#ffdead`).
+    pub const NAVAJO_WHITE: Self = Self::from_rgb8(255, 222, 173);
+    /// CSS `navy` (`#000080`).
+    pub const NAVY: Self = Self::from_rgb8(0, 0, 128);
+    /// CSS `oldlace` (`#fdf5e6`).
+    pub const OLD_LACE: Self = Self::from_rgb8(253, 245, 230);
+    /// CSS `olive` (`#808000`).
+    pub const OLIVE: Self = Self::from_rgb8(128, 128, 0);
+    /// CSS `olivedrab` (`#6b8e23`).
+    pub const OLIVE_DRAB: Self = Self::from_rgb8(107, 142, 35);
+    /// CSS `orange` (`#ffa500`).
+    pub const ORANGE: Self = Self::from_rgb8(255, 165, 0);
+    /// CSS `orangered` (`#ff4500`).
+    pub const ORANGE_RED: Self = Self::from_rgb8(255, 69, 0);
+    /// CSS `orchid` (`#da70d6`).
+    pub const ORCHID: Self = Self::from_rgb8(218, 112, 214);
+    /// CSS `palegoldenrod` (`#eee8aa`).
+    pub const PALE_GOLDENROD: Self = Self::from_rgb8(238, 232, 170);
+    /// CSS `palegreen` (`#98fb98`).
+    pub const PALE_GREEN: Self = Self::from_rgb8(152, 251, 152);
+    /// CSS `paleturquoise` (`#afeeee`).
+    pub const PALE_TURQUOISE: Self = Self::from_rgb8(175, 238, 238);
+    /// CSS `palevioletred` (`#db7093`).
+    pub const PALE_VIOLET_RED: Self = Self::from_rgb8(219, 112, 147);
+    /// CSS `papayawhip` (`#ffefd5`).
+    pub const PAPAYA_WHIP: Self = Self::from_rgb8(255, 239, 213);
+    /// CSS `peachpuff` (`#ffdab9`).
+    pub const PEACH_PUFF: Self = Self::from_rgb8(255, 218, 185);
+    /// CSS `peru` (`#cd853f`).
+    pub const PERU: Self = Self::from_rgb8(205, 133, 63);
+    /// CSS `pink` (`#ffc0cb`).
+    pub const PINK: Self = Self::from_rgb8(255, 192, 203);
+    /// CSS `plum` (`#dda0dd`).
+    pub const PLUM: Self = Self::from_rgb8(221, 160, 221);
+    /// CSS `powderblue` (`#b0e0e6`).
+    pub const POWDER_BLUE: Self = Self::from_rgb8(176, 224, 230);
+    /// CSS `purple` (`#800080`).
+    pub const PURPLE: Self = Self::from_rgb8(128, 0, 128);
+    /// CSS `rebeccapurple` (`#663399`).
+    pub const REBECCA_PURPLE: Self = Self::from_rgb8(102, 51, 153);
+    /// CSS `red` (`#ff0000`).
+    pub const RED: Self = Self::from_rgb8(255, 0, 0);
+    /// CSS `rosybrown` (`#bc8f8f`).
+    pub const ROSY_BROWN: Self = Self::from_rgb8(188, 143, 143);
+    /// CSS `royalblue` (`#4169e1`).
+    pub const ROYAL_BLUE: Self = Self::from_rgb8(65, 105, 225);
+    /// CSS `saddlebrown` (`#8b4513`).
+    pub const SADDLE_BROWN: Self = Self::from_rgb8(139, 69, 19);
+    /// CSS `salmon` (`#fa8072`).
+    pub const SALMON: Self = Self::from_rgb8(250, 128, 114);
+    /// CSS `sandybrown` (`#f4a460`).
+    pub const SANDY_BROWN: Self = Self::from_rgb8(244, 164, 96);
+    /// CSS `seagreen` (`#2e8b57`).
+    pub const SEA_GREEN: Self = Self::from_rgb8(46, 139, 87);
+    /// CSS `seashell` (`#fff5ee`).
+    pub const SEASHELL: Self = Self::from_rgb8(255, 245, 238);
+    /// CSS `sienna` (`#a0522d`).
+    pub const SIENNA: Self = Self::from_rgb8(160, 82, 45);
+    /// CSS `silver` (`#c0c0c0`).
+    pub const SILVER: Self = Self::from_rgb8(192, 192, 192);
+    /// CSS `skyblue` (`#87ceeb`).
+    pub const SKY_BLUE: Self = Self::from_rgb8(135, 206, 235);
+    /// CSS `slateblue` (`#6a5acd`).
+    pub const SLATE_BLUE: Self = Self::from_rgb8(106, 90, 205);
+    /// CSS `slategray` (`#708090`).
+    pub const SLATE_GRAY: Self = Self::from_rgb8(112, 128, 144);
+    /// CSS `slategrey` (`#708090`).
+    pub const SLATE_GREY: Self = Self::from_rgb8(112, 128, 144);
+    /// CSS `snow` (`#fffafa`).
+    pub const SNOW: Self = Self::from_rgb8(255, 250, 250);
+    /// CSS `springgreen` (`#00ff7f`).
+    pub const SPRING_GREEN: Self = Self::from_rgb8(0, 255, 127);
+    /// CSS `steelblue` (`#4682b4`).
+    pub const STEEL_BLUE: Self = Self::from_rgb8(70, 130, 180);
+    /// CSS `tan` (`#d2b48c`).
+    pub const TAN: Self = Self::from_rgb8(210, 180, 140);
+    /// CSS `teal` (`#008080`).
+    pub const TEAL: Self = Self::from_rgb8(0, 128, 128);
+    /// CSS `thistle` (`#d8bfd8`).
+    pub const THISTLE: Self = Self::from_rgb8(216, 191, 216);
+    /// CSS `tomato` (`#ff6347`).
+    pub const TOMATO: Self = Self::from_rgb8(255, 99, 71);
+    /// CSS `turquoise` (`#40e0d0`).
+    pub const TURQUOISE: Self = Self::from_rgb8(64, 224, 208);
+    /// CSS `violet` (`#ee82ee`).
+    pub const VIOLET: Self = Self::from_rgb8(238, 130, 238);
+    /// CSS `wheat` (`#f5deb3`).
+    pub const WHEAT: Self = Self::from_rgb8(245, 222, 179);
+    /// CSS `whitesmoke` (`#f5f5f5`).
+    pub const WHITE_SMOKE: Self = Self::from_rgb8(245, 245, 245);
+    /// CSS `yellow` (`#ffff00`).
+    pub const YELLOW: Self = Self::from_rgb8(255, 255, 0);
+    /// CSS `yellowgreen` (`#9acd32`).
+    pub const YELLOW_GREEN: Self = Self::from_rgb8(154, 205, 50);
+}
+/// Every CSS named color, sorted by name.
+pub(super) const NAMED_COLORS: [(&str, Color); 149] = [
+    ("aliceblue", Color::ALICE_BLUE),
+    ("antiquewhite", Color::ANTIQUE_WHITE),
+    ("aqua", Color::AQUA),
+    ("aquamarine", Color::AQUAMARINE),
+    ("azure", Color::AZURE),
+    ("beige", Color::BEIGE),
+    ("bisque", Color::BISQUE),
+    ("black", Color::BLACK),
+    ("blanchedalmond", Color::BLANCHED_ALMOND),
+    ("blue", Color::BLUE),
+    ("blueviolet", Color::BLUE_VIOLET),
+    ("brown", Color::BROWN),
+    ("burlywood", Color::BURLYWOOD),
+    ("cadetblue", Color::CADET_BLUE),
+    ("chartreuse", Color::CHARTREUSE),
+    ("chocolate", Color::CHOCOLATE),
+    ("coral", Color::CORAL),
+    ("cornflowerblue", Color::CORNFLOWER_BLUE),
+    ("cornsilk", Color::CORNSILK),
+    ("crimson", Color::CRIMSON),
+    ("cyan", Color::CYAN),
+    ("darkblue", Color::DARK_BLUE),
+    ("darkcyan", Color::DARK_CYAN),
+    ("darkgoldenrod", Color::DARK_GOLDENROD),
+    ("darkgray", Color::DARK_GRAY),
+    ("darkgreen", Color::DARK_GREEN),
+    ("darkgrey", Color::DARK_GREY),
+    ("darkkhaki", Color::DARK_KHAKI),
+    ("darkmagenta", Color::DARK_MAGENTA),
+    ("darkolivegreen", Color::DARK_OLIVE_GREEN),
+    ("darkorange", Color::DARK_ORANGE),
+    ("darkorchid", Color::DARK_ORCHID),
+    ("darkred", Color::DARK_RED),
+    ("darksalmon", Color::DARK_SALMON),
+    ("darkseagreen", Color::DARK_SEA_GREEN),
+    ("darkslateblue", Color::DARK_SLATE_BLUE),
+    ("darkslategray", Color::DARK_SLATE_GRAY),
+    ("darkslategrey", Color::DARK_SLATE_GREY),
+    ("darkturquoise", Color::DARK_TURQUOISE),
+    ("darkviolet", Color::DARK_VIOLET),
+    ("deeppink", Color::DEEP_PINK),
+    ("deepskyblue", Color::DEEP_SKY_BLUE),
+    ("dimgray", Color::DIM_GRAY),
+    ("dimgrey", Color::DIM_GREY),
+    ("dodgerblue", Color::DODGER_BLUE),
+    ("firebrick", Color::FIREBRICK),
+    ("floralwhite", Color::FLORAL_WHITE),
+    ("forestgreen", Color::FOREST_GREEN),
+    ("fuchsia", Color::FUCHSIA),
+    ("gainsboro", Color::GAINSBORO),
+    ("ghostwhite", Color::GHOST_WHITE),
+    ("gold", Color::GOLD),
+    ("goldenrod", Color::GOLDENROD),
+    ("gray", Color::GRAY),
+    ("green", Color::GREEN),
+    ("greenyellow", Color::GREEN_YELLOW),
+    ("grey", Color::GREY),
+    ("honeydew", Color::HONEYDEW),
+    ("hotpink", Color::HOT_PINK),
+    ("indianred", Color::INDIAN_RED),
+    ("indigo", Color::INDIGO),
+    ("ivory", Color::IVORY),
+    ("khaki", Color::KHAKI),
+    ("lavender", Color::LAVENDER),
+    ("lavenderblush", Color::LAVENDER_BLUSH),
+    ("lawngreen", Color::LAWN_GREEN),
+    ("lemonchiffon", Color::LEMON_CHIFFON),
+    ("lightblue", Color::LIGHT_BLUE),
+    ("lightcoral", Color::LIGHT_CORAL),
+    ("lightcyan", Color::LIGHT_CYAN),
+    ("lightgoldenrodyellow", Color::LIGHT_GOLDENROD_YELLOW),
+    ("lightgray", Color::LIGHT_GRAY),
+    ("lightgreen", Color::LIGHT_GREEN),
+    ("lightgrey", Color::LIGHT_GREY),
+    ("lightpink", Color::LIGHT_PINK),
+    ("lightsalmon", Color::LIGHT_SALMON),
+    ("lightseagreen", Color::LIGHT_SEA_GREEN),
+    ("lightskyblue", Color::LIGHT_SKY_BLUE),
+    ("lightslategray", Color::LIGHT_SLATE_GRAY),
+    ("lightslategrey", Color::LIGHT_SLATE_GREY),
+    ("lightsteelblue", Color::LIGHT_STEEL_BLUE),
+    ("lightyellow", Color::LIGHT_YELLOW),
+    ("lime", Color::LIME),
+    ("limegreen", Color::LIME_GREEN),
+    ("linen", Color::LINEN),
+    ("magenta", Color::MAGENTA),
+    ("maroon", Color::MAROON),
+    ("mediumaquamarine", Color::MEDIUM_AQUAMARINE),
+    ("mediumblue", Color::MEDIUM_BLUE),
+    ("mediumorchid", Color::MEDIUM_ORCHID),
+    ("mediumpurple", Color::MEDIUM_PURPLE),
+    ("mediumseagreen", Color::MEDIUM_SEA_GREEN),
+    ("mediumslateblue", Color::MEDIUM_SLATE_BLUE),
+    ("mediumspringgreen", Color::MEDIUM_SPRING_GREEN),
+    ("mediumturquoise", Color::MEDIUM_TURQUOISE),
+    ("mediumvioletred", Color::MEDIUM_VIOLET_RED),
+    ("midnightblue", Color::MIDNIGHT_BLUE),
+    ("mintcream", Color::MINT_CREAM),
+    ("mistyrose", Color::MISTY_ROSE),
+    ("moccasin", Color::MOCCASIN),
+    ("navajowhite", Color::NAVAJO_WHITE),
+    ("navy", Color::NAVY),
+    ("oldlace", Color::OLD_LACE),
+    ("olive", Color::OLIVE),
+    ("olivedrab", Color::OLIVE_DRAB),
+    ("orange", Color::ORANGE),
+    ("orangered", Color::ORANGE_RED),
+    ("orchid", Color::ORCHID),
+    ("palegoldenrod", Color::PALE_GOLDENROD),
+    ("palegreen", Color::PALE_GREEN),
+    ("paleturquoise", Color::PALE_TURQUOISE),
+    ("palevioletred", Color::PALE_VIOLET_RED),
+    ("papayawhip", Color::PAPAYA_WHIP),
+    ("peachpuff", Color::PEACH_PUFF),
+    ("peru", Color::PERU),
+    ("pink", Color::PINK),
+    ("plum", Color::PLUM),
+    ("powderblue", Color::POWDER_BLUE),
+    ("purple", Color::PURPLE),
+    ("rebeccapurple", Color::REBECCA_PURPLE),
+    ("red", Color::RED),
+    ("rosybrown", Color::ROSY_BROWN),
+    ("royalblue", Color::ROYAL_BLUE),
+    ("saddlebrown", Color::SADDLE_BROWN),
+    ("salmon", Color::SALMON),
+    ("sandybrown", Color::SANDY_BROWN),
+    ("seagreen", Color::SEA_GREEN),
+    ("seashell", Color::SEASHELL),
+    ("sienna", Color::SIENNA),
+    ("silver", Color::SILVER),
+    ("skyblue", Color::SKY_BLUE),
+    ("slateblue", Color::SLATE_BLUE),
+    ("slategray", Color::SLATE_GRAY),
+    ("slategrey", Color::SLATE_GREY),
+    ("snow", Color::SNOW),
+    ("springgreen", Color::SPRING_GREEN),
+    ("steelblue", Color::STEEL_BLUE),
+    ("tan", Color::TAN),
+    ("teal", Color::TEAL),
+    ("thistle", Color::THISTLE),
+    ("tomato", Color::TOMATO),
+    ("transparent", Color::TRANSPARENT),
+    ("turquoise", Color::TURQUOISE),
+    ("violet", Color::VIOLET),
+    ("wheat", Color::WHEAT),
+    ("white", Color::WHITE),
+    ("whitesmoke", Color::WHITE_SMOKE),
+    ("yellow", Color::YELLOW),
+    ("yellowgreen", Color::YELLOW_GREEN),
+];
@@ -0,0 +1,264 @@
+use super::{linear_to_srgb, Color};
+
+/// A color space in which colors can be interpolated, see [Color::lerp].
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum ColorSpace {
+    /// sRGB-encoded channels.  Cheap, but mixes get darker in the middle.
+    #[default]
+    Srgb,
+
+    /// Linear-light sRGB channels.  Physically correct mixing of light.
+    LinearSrgb,
+
+    /// The perceptually uniform Oklab space.
+    Oklab,
+
+    /// The polar form of Oklab.  Hue is interpolated along the shortest arc.
+    Oklch,
+
+    /// Hue, saturation and lightness.  Hue is interpolated along the shortest arc.
+    Hsl,
+}
+
+/// A color in the HSL (hue, saturation, lightness) model.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Hsl {
+    /// The hue, in degrees from `0.0` to `360.0`.
+    pub hue: f32,
+    pub saturation: f32,
+    pub lightness: f32,
+    pub alpha: f32,
+}
+
+impl Hsl {
+    /// Creates a new [Hsl] color.
+    #[inline]
+    pub const fn new(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Self {
+        Self {
+            hue,
+            saturation,
+            lightness,
+            alpha,
+        }
+    }
+}
+
+/// A color in the HSV (hue, saturation, value) model.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Hsv {
+    /// The hue, in degrees from `0.0` to `360.0`.
+    pub hue: f32,
+    pub saturation: f32,
+    pub value: f32,
+    pub alpha: f32,
+}
+
+impl Hsv {
+    /// Creates a new [Hsv] color.
+    #[inline]
+    pub const fn new(hue: f32, saturation: f32, value: f32, alpha: f32) -> Self {
+        Self {
+            hue,
+            saturation,
+            value,
+            alpha,
+        }
+    }
+}
+
+/// A color in the Oklab perceptual color space.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Oklab {
+    /// The perceived lightness, from `0.0` to `1.0`.
+    pub lightness: f32,
+
+    /// How green (negative) or red (positive) the color is.
+    pub a: f32,
+
+    /// How blue (negative) or yellow (positive) the color is.
+    pub b: f32,
+    pub alpha: f32,
+}
+
+impl Oklab {
+    /// Creates a new [Oklab] color.
+    #[inline]
+    pub const fn new(lightness: f32, a: f32, b: f32, alpha: f32) -> Self {
+        Self {
+            lightness,
+            a,
+            b,
+            alpha,
+        }
+    }
+}
+
+/// A color in the Oklch color space, the polar form of [Oklab].
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Oklch {
+    /// The perceived lightness, from `0.0` to `1.0`.
+    pub lightness: f32,
+    pub chroma: f32,
+
+    /// The hue, in degrees from `0.0` to `360.0`.
+    pub hue: f32,
+    pub alpha: f32,
+}
+
+impl Oklch {
+    /// Creates a new [Oklch] color.
+    #[inline]
+    pub const fn new(lightness: f32, chroma: f32, hue: f32, alpha: f32) -> Self {
+        Self {
+            lightness,
+            chroma,
+            hue,
+            alpha,
+        }
+    }
+}
+
+/// Returns the hue in degrees and the chroma of an RGB color, along with its largest and smallest
+/// channels.
+fn hue_and_chroma(color: &Color) -> (f32, f32, f32, f32) {
+    let max = color.red.max(color.green).max(color.blue);
+    let min = color.red.min(color.green).min(color.blue);
+    let chroma = max - min;
+
+    let hue = if chroma == 0.0 {
+        0.0
+    } else if max == color.red {
+        60.0 * ((color.green - color.blue) / chroma).rem_euclid(6.0)
+    } else if max == color.green {
+        60.0 * ((color.blue - color.red) / chroma + 2.0)
+    } else {
+        60.0 * ((color.red - color.green) / chroma + 4.0)
+    };
+
+    (hue, chroma, max, min)
+}
+
+/// Creates a [Color] from a hue in degrees, a chroma and the amount to add to every channel.
+fn color_from_hue_and_chroma(hue: f32, chroma: f32, offset: f32, alpha: f32) -> Color {
+    let hue = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (hue.rem_euclid(2.0) - 1.0).abs());
+
+    let (red, green, blue) = match hue as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+
+    Color::new(red + offset, green + offset, blue + offset, alpha)
+}
+
+impl From<Color> for Hsl {
+    fn from(color: Color) -> Self {
+        let (hue, chroma, max, min) = hue_and_chroma(&color);
+        let lightness = (max + min) * 0.5;
+        let saturation = if lightness == 0.0 || lightness == 1.0 {
+            0.0
+        } else {
+            chroma / (1.0 - (2.0 * lightness - 1.0).abs())
+        };
+
+        Self::new(hue, saturation, lightness, color.alpha)
+    }
+}
+
+impl From<Hsl> for Color {
+    fn from(hsl: Hsl) -> Self {
+        let chroma = (1.0 - (2.0 * hsl.lightness - 1.0).abs()) * hsl.saturation;
+        color_from_hue_and_chroma(hsl.hue, chroma, hsl.lightness - chroma * 0.5, hsl.alpha)
+    }
+}
+
+impl From<Color> for Hsv {
+    fn from(color: Color) -> Self {
+        let (hue, chroma, max, _) = hue_and_chroma(&color);
+        let saturation = if max == 0.0 { 0.0 } else { chroma / max };
+
+        Self::new(hue, saturation, max, color.alpha)
+    }
+}
+
+impl From<Hsv> for Color {
+    fn from(hsv: Hsv) -> Self {
+        let chroma = hsv.value * hsv.saturation;
+        color_from_hue_and_chroma(hsv.hue, chroma, hsv.value - chroma, hsv.alpha)
+    }
+}
+
+impl From<Color> for Oklab {
+    fn from(color: Color) -> Self {
+        let linear = color.to_linear();
+        let (r, g, b) = (linear.red, linear.green, linear.blue);
+
+        let l = (0.41222147 * r + 0.53633254 * g + 0.051445995 * b).cbrt();
+        let m = (0.2119035 * r + 0.6806995 * g + 0.10739696 * b).cbrt();
+        let s = (0.08830246 * r + 0.28171884 * g + 0.6299787 * b).cbrt();
+
+        Self::new(
+            0.21045426 * l + 0.7936178 * m - 0.004072047 * s,
+            1.9779985 * l - 2.4285922 * m + 0.4505937 * s,
+            0.025904037 * l + 0.78277177 * m - 0.80867577 * s,
+            color.alpha,
+        )
+    }
+}
+
+impl From<Oklab> for Color {
+    fn from(oklab: Oklab) -> Self {
+        let l = oklab.lightness + 0.39633778 * oklab.a + 0.21580376 * oklab.b;
+        let m = oklab.lightness - 0.105561346 * oklab.a - 0.06385417 * oklab.b;
+        let s = oklab.lightness - 0.08948418 * oklab.a - 1.2914855 * oklab.b;
+        let (l, m, s) = (l * l * l, m * m * m, s * s * s);
+
+        Color::new(
+            linear_to_srgb(4.0767417 * l - 3.3077116 * m + 0.23096994 * s),
+            linear_to_srgb(-1.268438 * l + 2.6097574 * m - 0.34131938 * s),
+            linear_to_srgb(-0.0041960863 * l - 0.7034186 * m + 1.7076147 * s),
+            oklab.alpha,
+        )
+    }
+}
+
+impl From<Oklab> for Oklch {
+    fn from(oklab: Oklab) -> Self {
+        Self::new(
+            oklab.lightness,
+            oklab.a.hypot(oklab.b),
+            oklab.b.atan2(oklab.a).to_degrees().rem_euclid(360.0),
+            oklab.alpha,
+        )
+    }
+}
+
+impl From<Oklch> for Oklab {
+    fn from(oklch: Oklch) -> Self {
+        let (sin, cos) = oklch.hue.to_radians().sin_cos();
+        Self::new(
+            oklch.lightness,
+            oklch.chroma * cos,
+            oklch.chroma * sin,
+            oklch.alpha,
+        )
+    }
+}
+
+impl From<Color> for Oklch {
+    #[inline]
+    fn from(color: Color) -> Self {
+        Oklab::from(color).into()
+    }
+}
+
+impl From<Oklch> for Color {
+    #[inline]
+    fn from(oklch: Oklch) -> Self {
+        Oklab::from(oklch).into()
+    }
+}
@@ -28,4 +28,20 @@ impl Dimension {
     pub const fn area(&self) -> u32 {
         self.width * self.height
     }
+
+    /// Returns the number of mip levels in a full mip chain of this [Dimension], each level half
+    /// the size of the one before, down to 1x1.
+    #[inline]
+    pub const fn max_mip_level_count(&self) -> u32 {
+        let largest = if self.width > self.height {
+            self.width
+        } else {
+            self.height
+        };
+        if largest == 0 {
+            1
+        } else {
+            u32::BITS - largest.leading_zeros()
+        }
+    }
 }
@@ -1,3 +1,5 @@
+use crate::{Dimension, PixelRect};
+
 /// An error from G2d.
 #[derive(Clone, Debug)]
 pub enum Error {
@@ -14,9 +16,80 @@ pub enum Error {
     /// The [Texture](crate::Texture) didn't have the correct usage(s) for an operation.
     LackingTextureUsage(wgpu::TextureUsages),
 
+    /// A region didn't fit within the [Texture](crate::Texture) it was used with.
+    RegionOutOfBounds { region: PixelRect, size: Dimension },
+
+    /// The stride of the data for a region of a [Texture](crate::Texture) was shorter than a row
+    /// of the region, in bytes.
+    BytesPerRowTooSmall { minimum: u32, got: u32 },
+
+    /// G2d doesn't support the format of a [Texture](crate::Texture) for an operation.
+    UnsupportedTextureFormat(wgpu::TextureFormat),
+
+    /// A [Texture](crate::Texture) was copied into another with an incompatible format.
+    TextureFormatMismatch {
+        src: wgpu::TextureFormat,
+        dst: wgpu::TextureFormat,
+    },
+
+    /// A [Texture](crate::Texture) was drawn onto a [Canvas](crate::Canvas) of itself.
+    TextureDrawnOntoItself,
+
     /// The G2d [Handle](crate::Handle) failed to create a [`wgpu::Surface`] for its window.
     FailedToCreateSurface(String),
 
     /// G2d failed to get the texture of a [`wgpu::Surface`].
     FailedToGetSurfaceTexture(String),
+
+    /// A string could not be parsed as a [Color](crate::Color).
+    InvalidColor(String),
+
+    /// A layer was popped from a [Canvas](crate::Canvas) without a matching push, e.g.
+    /// [Canvas::pop_mask](crate::Canvas::pop_mask) without
+    /// [Canvas::push_mask](crate::Canvas::push_mask).
+    UnbalancedLayer,
+
+    /// A [ConvolutionKernel](crate::ConvolutionKernel) had the wrong number of values for its size,
+    /// or was too big.
+    InvalidConvolutionKernel {
+        width: u32,
+        height: u32,
+        values: usize,
+    },
+
+    /// A [Paint](crate::Paint) gradient had more than
+    /// [LinearGradient::MAX_STOPS](crate::LinearGradient::MAX_STOPS) stops.
+    TooManyGradientStops(usize),
+
+    /// The data of a [Font](crate::Font) could not be parsed.
+    InvalidFont(String),
+
+    /// The descriptor of a [BitmapFont](crate::BitmapFont) could not be parsed, or didn't match
+    /// the pages it was loaded with.
+    InvalidBitmapFont(String),
+
+    /// The glyphs of a single draw call didn't fit in the [GlyphAtlas](crate::GlyphAtlas), even
+    /// after evicting every glyph the draw call doesn't use.
+    GlyphAtlasFull,
+
+    /// An image inserted into a [TextureAtlas](crate::TextureAtlas) was larger than its pages,
+    /// once extruded.
+    TooLargeForAtlas(Dimension),
+
+    /// An image inserted into a [TextureAtlas](crate::TextureAtlas) had a width or height of
+    /// zero.
+    EmptyAtlasImage(Dimension),
+
+    /// The markers of a [NinePatch](crate::NinePatch) image could not be read.
+    InvalidNinePatch(String),
+
+    /// An image file could not be read or decoded.  Only returned with the `image` feature.
+    FailedToLoadImage(String),
+
+    /// [Pixels](crate::Pixels) could not be encoded or written to an image file.  Only returned
+    /// with the `image` feature.
+    FailedToSaveImage(String),
+
+    /// A [FontCollection](crate::FontCollection) had no font which could be loaded.
+    NoFontFound,
 }
@@ -0,0 +1,160 @@
+use crate::{Color, Dimension, Handle, PipelineShader, Vec2};
+
+use super::{create_wgpu_filter_texture, filter_pass, size_of};
+
+/// The largest standard deviation, in texels, blurred at the current resolution.  Larger blurs
+/// are done on downsampled copies of the texture, which keeps the number of taps bounded.
+const MAX_SIGMA: f32 = 4.0;
+
+/// The uniform of the blur shader.
+#[derive(bytemuck::Zeroable, bytemuck::Pod, Clone, Copy, Debug)]
+#[repr(C)]
+struct BlurUniform {
+    target_size: [f32; 2],
+    direction: [f32; 2],
+    sigma: f32,
+    radius: u32,
+}
+
+/// The uniform of the drop shadow shader.
+#[derive(bytemuck::Zeroable, bytemuck::Pod, Clone, Copy, Debug)]
+#[repr(C)]
+struct DropShadowUniform {
+    color: Color,
+    offset: [f32; 2],
+    _padding: [f32; 2],
+}
+
+/// Resamples *input* to a new texture of the provided size, with bilinear filtering.
+fn resample<H: Handle>(
+    handle: &H,
+    encoder: &mut wgpu::CommandEncoder,
+    input: &wgpu::Texture,
+    size: Dimension,
+) -> wgpu::Texture {
+    let output = create_wgpu_filter_texture(handle, size, input.format());
+    let uniform = BlurUniform {
+        target_size: [size.width as f32, size.height as f32],
+        direction: [0.0, 0.0],
+        sigma: 0.0,
+        radius: 0,
+    };
+    filter_pass(
+        handle,
+        encoder,
+        PipelineShader::FilterBlur,
+        bytemuck::bytes_of(&uniform),
+        &[input],
+        &output,
+    );
+    output
+}
+
+/// Blurs *input* along one axis with a standard deviation of *sigma* texels.
+fn blur_pass<H: Handle>(
+    handle: &H,
+    encoder: &mut wgpu::CommandEncoder,
+    input: &wgpu::Texture,
+    direction: Vec2,
+    sigma: f32,
+) -> wgpu::Texture {
+    let size = size_of(input);
+    let output = create_wgpu_filter_texture(handle, size, input.format());
+    let uniform = BlurUniform {
+        target_size: [size.width as f32, size.height as f32],
+        direction: [
+            direction.x / size.width as f32,
+            direction.y / size.height as f32,
+        ],
+        sigma,
+        // Three standard deviations hold over 99.7% of the weight.
+        radius: (sigma * 3.0).ceil() as u32,
+    };
+    filter_pass(
+        handle,
+        encoder,
+        PipelineShader::FilterBlur,
+        bytemuck::bytes_of(&uniform),
+        &[input],
+        &output,
+    );
+    output
+}
+
+/// Applies a Gaussian blur with a standard deviation of *std_deviation* pixels to *input*.
+///
+/// Large blurs are done at a lower resolution and scaled back up, which is indistinguishable
+/// since a blurred image has no fine details left.
+pub(super) fn gaussian_blur<H: Handle>(
+    handle: &H,
+    encoder: &mut wgpu::CommandEncoder,
+    input: &wgpu::Texture,
+    std_deviation: f32,
+) -> wgpu::Texture {
+    let full_size = size_of(input);
+    let std_deviation = std_deviation.max(0.0);
+
+    // Halve the resolution until the blur is small enough.  Averaging 2x2 texels blurs by
+    // itself, which is subtracted from the remaining blur.
+    let mut scale = 1.0;
+    let mut variance = std_deviation * std_deviation;
+    let mut downsampled: Option<wgpu::Texture> = None;
+    loop {
+        let current = downsampled.as_ref().unwrap_or(input);
+        let size = size_of(current);
+        if variance.sqrt() / scale <= MAX_SIGMA || (size.width == 1 && size.height == 1) {
+            break;
+        }
+
+        let half_size = Dimension::new(size.width.div_ceil(2), size.height.div_ceil(2));
+        let next = resample(handle, encoder, current, half_size);
+        variance -= scale * scale / 4.0;
+        scale *= 2.0;
+        downsampled = Some(next);
+    }
+
+    let current = downsampled.as_ref().unwrap_or(input);
+    let sigma = variance.max(0.0).sqrt() / scale;
+    if sigma <= 0.0 {
+        return resample(handle, encoder, current, full_size);
+    }
+
+    let horizontal = blur_pass(handle, encoder, current, Vec2::new(1.0, 0.0), sigma);
+    let blurred = blur_pass(handle, encoder, &horizontal, Vec2::new(0.0, 1.0), sigma);
+
+    if size_of(&blurred) == full_size {
+        blurred
+    } else {
+        resample(handle, encoder, &blurred, full_size)
+    }
+}
+
+/// Draws a shadow of *input* beneath it: its alpha blurred by *blur* pixels, filled with *color*
+/// and moved by *offset* pixels.
+pub(super) fn drop_shadow<H: Handle>(
+    handle: &H,
+    encoder: &mut wgpu::CommandEncoder,
+    input: &wgpu::Texture,
+    offset: Vec2,
+    blur: f32,
+    color: Color,
+) -> wgpu::Texture {
+    let size = size_of(input);
+    let blurred = gaussian_blur(handle, encoder, input, blur);
+
+    let output = create_wgpu_filter_texture(handle, size, input.format());
+    let uniform = DropShadowUniform {
+        color: color.premultiplied(),
+        offset: [offset.x / size.width as f32, offset.y / size.height as f32],
+        _padding: [0.0; 2],
+    };
+    filter_pass(
+        handle,
+        encoder,
+        PipelineShader::FilterDropShadow,
+        bytemuck::bytes_of(&uniform),
+        &[input, &blurred],
+        &output,
+    );
+    output
+}
@@ -0,0 +1,217 @@
+use crate::{Handle, PipelineShader};
+
+use super::{create_wgpu_filter_texture, filter_pass, size_of};
+
+/// The color space a [Filter](super::Filter) does its math in, like the SVG
+/// `color-interpolation-filters` property.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum FilterColorSpace {
+    /// sRGB-encoded channels.  Matches the CSS filter functions as browsers implement them.
+    #[default]
+    Srgb,
+
+    /// Linear-light channels.  The default of SVG filter primitives.
+    LinearRgb,
+}
+
+impl FilterColorSpace {
+    /// Returns how a shader should convert the colors it samples from a texture with the provided
+    /// format to get colors in this space: `0` for no conversion, `1` to encode linear colors to
+    /// sRGB and `2` to decode sRGB colors to linear.
+    ///
+    /// Textures with an sRGB format are sampled as linear colors, other textures are sampled as
+    /// they are stored, which G2d treats as sRGB.
+    pub(super) fn wgpu_conversion(&self, format: wgpu::TextureFormat) -> u32 {
+        match (self, format.is_srgb()) {
+            (Self::Srgb, true) => 1,
+            (Self::LinearRgb, false) => 2,
+            _ => 0,
+        }
+    }
+}
+
+/// A 4x5 matrix transforming the unpremultiplied colors of a texture, like the SVG
+/// `feColorMatrix` primitive.
+///
+/// Each row computes one output channel (red, green, blue and alpha, in order) as the dot product
+/// of its first four values with the input channels, plus its fifth value.  Channels range from
+/// `0.0` to `1.0`, and so do the offsets.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ColorMatrix(pub [f32; 20]);
+
+impl ColorMatrix {
+    /// Leaves every color unchanged.
+    #[rustfmt::skip]
+    pub const IDENTITY: Self = Self([
+        1.0, 0.0, 0.0, 0.0, 0.0,
+        0.0, 1.0, 0.0, 0.0, 0.0,
+        0.0, 0.0, 1.0, 0.0, 0.0,
+        0.0, 0.0, 0.0, 1.0, 0.0,
+    ]);
+
+    /// Creates a [ColorMatrix] which applies a linear function to the red, green and blue
+    /// channels: `channel * slope + intercept`.
+    #[rustfmt::skip]
+    const fn linear(slope: f32, intercept: f32) -> Self {
+        Self([
+            slope, 0.0, 0.0, 0.0, intercept,
+            0.0, slope, 0.0, 0.0, intercept,
+            0.0, 0.0, slope, 0.0, intercept,
+            0.0, 0.0, 0.0, 1.0, 0.0,
+        ])
+    }
+
+    /// Creates a [ColorMatrix] from the 3x3 matrix applied to the red, green and blue channels.
+    #[rustfmt::skip]
+    const fn rgb([r, g, b]: [[f32; 3]; 3]) -> Self {
+        Self([
+            r[0], r[1], r[2], 0.0, 0.0,
+            g[0], g[1], g[2], 0.0, 0.0,
+            b[0], b[1], b[2], 0.0, 0.0,
+            0.0, 0.0, 0.0, 1.0, 0.0,
+        ])
+    }
+
+    /// Multiplies every color by *amount*, like CSS `brightness()`.  `1.0` leaves colors
+    /// unchanged, `0.0` makes them black.
+    #[inline]
+    pub const fn brightness(amount: f32) -> Self {
+        Self::linear(amount, 0.0)
+    }
+
+    /// Scales every color away from middle gray by *amount*, like CSS `contrast()`.  `1.0` leaves
+    /// colors unchanged, `0.0` makes them gray.
+    #[inline]
+    pub fn contrast(amount: f32) -> Self {
+        Self::linear(amount, 0.5 - 0.5 * amount)
+    }
+
+    /// Changes the saturation of colors, like CSS `saturate()`.  `1.0` leaves colors unchanged,
+    /// `0.0` removes all color and larger values over-saturate.
+    pub fn saturate(amount: f32) -> Self {
+        let s = amount;
+        Self::rgb([
+            [0.213 + 0.787 * s, 0.715 - 0.715 * s, 0.072 - 0.072 * s],
+            [0.213 - 0.213 * s, 0.715 + 0.285 * s, 0.072 - 0.072 * s],
+            [0.213 - 0.213 * s, 0.715 - 0.715 * s, 0.072 + 0.928 * s],
+        ])
+    }
+
+    /// Rotates the hue of colors by *degrees*, like CSS `hue-rotate()`.
+    pub fn hue_rotate(degrees: f32) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Self::rgb([
+            [
+                0.213 + cos * 0.787 - sin * 0.213,
+                0.715 - cos * 0.715 - sin * 0.715,
+                0.072 - cos * 0.072 + sin * 0.928,
+            ],
+            [
+                0.213 - cos * 0.213 + sin * 0.143,
+                0.715 + cos * 0.285 + sin * 0.140,
+                0.072 - cos * 0.072 - sin * 0.283,
+            ],
+            [
+                0.213 - cos * 0.213 - sin * 0.787,
+                0.715 - cos * 0.715 + sin * 0.715,
+                0.072 + cos * 0.928 + sin * 0.072,
+            ],
+        ])
+    }
+
+    /// Converts colors to shades of gray, like CSS `grayscale()`.  *amount* goes from `0.0`,
+    /// unchanged, to `1.0`, completely gray.
+    pub fn grayscale(amount: f32) -> Self {
+        let a = 1.0 - amount.clamp(0.0, 1.0);
+        Self::rgb([
+            [
+                0.2126 + 0.7874 * a,
+                0.7152 - 0.7152 * a,
+                0.0722 - 0.0722 * a,
+            ],
+            [
+                0.2126 - 0.2126 * a,
+                0.7152 + 0.2848 * a,
+                0.0722 - 0.0722 * a,
+            ],
+            [
+                0.2126 - 0.2126 * a,
+                0.7152 - 0.7152 * a,
+                0.0722 + 0.9278 * a,
+            ],
+        ])
+    }
+
+    /// Gives colors an old-photograph brown tint, like CSS `sepia()`.  *amount* goes from `0.0`,
+    /// unchanged, to `1.0`, completely sepia.
+    pub fn sepia(amount: f32) -> Self {
+        let a = 1.0 - amount.clamp(0.0, 1.0);
+        Self::rgb([
+            [0.393 + 0.607 * a, 0.769 - 0.769 * a, 0.189 - 0.189 * a],
+            [0.349 - 0.349 * a, 0.686 + 0.314 * a, 0.168 - 0.168 * a],
+            [0.272 - 0.272 * a, 0.534 - 0.534 * a, 0.131 + 0.869 * a],
+        ])
+    }
+
+    /// Inverts colors, like CSS `invert()`.  *amount* goes from `0.0`, unchanged, to `1.0`,
+    /// completely inverted.
+    #[inline]
+    pub fn invert(amount: f32) -> Self {
+        let amount = amount.clamp(0.0, 1.0);
+        Self::linear(1.0 - 2.0 * amount, amount)
+    }
+
+    /// Multiplies the alpha of colors by *amount*, like CSS `opacity()`.
+    #[inline]
+    pub fn opacity(amount: f32) -> Self {
+        let mut matrix = Self::IDENTITY;
+        matrix.0[18] = amount.clamp(0.0, 1.0);
+        matrix
+    }
+}
+
+impl Default for ColorMatrix {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// The uniform of the color matrix shader.
+#[derive(bytemuck::Zeroable, bytemuck::Pod, Clone, Copy, Debug)]
+#[repr(C)]
+struct ColorMatrixUniform {
+    rows: [[f32; 4]; 4],
+    offsets: [f32; 4],
+    conversion: u32,
+    _padding: [u32; 3],
+}
+
+/// Transforms the colors of *input* with a [ColorMatrix].
+pub(super) fn color_matrix<H: Handle>(
+    handle: &H,
+    encoder: &mut wgpu::CommandEncoder,
+    input: &wgpu::Texture,
+    matrix: &ColorMatrix,
+    color_space: FilterColorSpace,
+) -> wgpu::Texture {
+    let m = &matrix.0;
+    let row = |i: usize| [m[i * 5], m[i * 5 + 1], m[i * 5 + 2], m[i * 5 + 3]];
+    let uniform = ColorMatrixUniform {
+        rows: [row(0), row(1), row(2), row(3)],
+        offsets: [m[4], m[9], m[14], m[19]],
+        conversion: color_space.wgpu_conversion(input.format()),
+        _padding: [0; 3],
+    };
+
+    let output = create_wgpu_filter_texture(handle, size_of(input), input.format());
+    filter_pass(
+        handle,
+        encoder,
+        PipelineShader::FilterColorMatrix,
+        bytemuck::bytes_of(&uniform),
+        &[input],
+        &output,
+    );
+    output
+}
@@ -0,0 +1,167 @@
+use crate::{Error, Handle, PipelineShader};
+
+use super::{create_wgpu_filter_texture, filter_pass, size_of, FilterColorSpace};
+
+/// A matrix of weights each pixel of a texture is replaced by the weighted sum of its neighbours
+/// with, like the SVG `feConvolveMatrix` primitive.
+///
+/// Pixels outside of the texture repeat its edges.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConvolutionKernel {
+    width: u32,
+    height: u32,
+    values: Vec<f32>,
+    divisor: f32,
+    bias: f32,
+    preserve_alpha: bool,
+}
+
+impl ConvolutionKernel {
+    /// The largest width and height of a [ConvolutionKernel].
+    pub const MAX_SIZE: u32 = 16;
+
+    /// Creates a [ConvolutionKernel] from its weights, in rows from top to bottom.
+    ///
+    /// Like in SVG, the kernel is rotated by 180° before being applied, its center is at
+    /// `(width / 2, height / 2)` rounded down, and the sum is divided by the sum of the weights (or
+    /// `1.0` if they sum up to zero).
+    ///
+    /// # Fails
+    /// Fails if *values* doesn't hold `width * height` weights, or if either dimension is `0` or
+    /// larger than [ConvolutionKernel::MAX_SIZE].
+    pub fn new(width: u32, height: u32, values: Vec<f32>) -> Result<Self, Error> {
+        let valid_size = (1..=Self::MAX_SIZE).contains(&width)
+            && (1..=Self::MAX_SIZE).contains(&height)
+            && values.len() == (width * height) as usize;
+        if !valid_size {
+            return Err(Error::InvalidConvolutionKernel {
+                width,
+                height,
+                values: values.len(),
+            });
+        }
+
+        let sum: f32 = values.iter().sum();
+        Ok(Self {
+            width,
+            height,
+            values,
+            divisor: if sum == 0.0 { 1.0 } else { sum },
+            bias: 0.0,
+            preserve_alpha: false,
+        })
+    }
+
+    /// Creates a 3x3 [ConvolutionKernel] from its weights.
+    #[inline]
+    fn square(values: [f32; 9]) -> Self {
+        Self::new(3, 3, values.to_vec()).expect("3x3 kernels are valid")
+    }
+
+    /// Makes edges crisper.
+    #[inline]
+    pub fn sharpen() -> Self {
+        Self::square([0.0, -1.0, 0.0, -1.0, 5.0, -1.0, 0.0, -1.0, 0.0])
+    }
+
+    /// Keeps the edges of shapes, turning flat areas black.  Keeps the alpha of the texture.
+    #[inline]
+    pub fn edge_detect() -> Self {
+        Self::square([-1.0, -1.0, -1.0, -1.0, 8.0, -1.0, -1.0, -1.0, -1.0])
+            .with_preserve_alpha(true)
+    }
+
+    /// Makes the texture look like it is pressed into paper, lit from the top-left.  Keeps the
+    /// alpha of the texture.
+    #[inline]
+    pub fn emboss() -> Self {
+        Self::square([-2.0, -1.0, 0.0, -1.0, 1.0, 1.0, 0.0, 1.0, 2.0]).with_preserve_alpha(true)
+    }
+
+    /// Sets the value the weighted sum is divided by.  Ignored if `0.0`.
+    #[inline]
+    pub fn with_divisor(mut self, divisor: f32) -> Self {
+        if divisor != 0.0 {
+            self.divisor = divisor;
+        }
+        self
+    }
+
+    /// Sets the value added to each channel after dividing the weighted sum.
+    #[inline]
+    pub fn with_bias(mut self, bias: f32) -> Self {
+        self.bias = bias;
+        self
+    }
+
+    /// Sets whether the alpha channel is left untouched.  If it is, the kernel applies to
+    /// unpremultiplied colors, otherwise it applies to premultiplied colors.
+    #[inline]
+    pub fn with_preserve_alpha(mut self, preserve_alpha: bool) -> Self {
+        self.preserve_alpha = preserve_alpha;
+        self
+    }
+
+    /// Returns the width of this [ConvolutionKernel].
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Returns the height of this [ConvolutionKernel].
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the weights of this [ConvolutionKernel], in rows from top to bottom.
+    #[inline]
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+}
+
+/// The uniform of the convolution shader.
+#[derive(bytemuck::Zeroable, bytemuck::Pod, Clone, Copy, Debug)]
+#[repr(C)]
+struct ConvolveUniform {
+    size: [u32; 2],
+    divisor: f32,
+    bias: f32,
+    preserve_alpha: u32,
+    conversion: u32,
+    _padding: [u32; 2],
+    values: [[f32; 4]; (ConvolutionKernel::MAX_SIZE * ConvolutionKernel::MAX_SIZE / 4) as usize],
+}
+
+/// Convolves *input* with a [ConvolutionKernel].
+pub(super) fn convolve<H: Handle>(
+    handle: &H,
+    encoder: &mut wgpu::CommandEncoder,
+    input: &wgpu::Texture,
+    kernel: &ConvolutionKernel,
+    color_space: FilterColorSpace,
+) -> wgpu::Texture {
+    let mut uniform = ConvolveUniform {
+        size: [kernel.width, kernel.height],
+        divisor: kernel.divisor,
+        bias: kernel.bias,
+        preserve_alpha: kernel.preserve_alpha as u32,
+        conversion: color_space.wgpu_conversion(input.format()),
+        _padding: [0; 2],
+        values: bytemuck::Zeroable::zeroed(),
+    };
+    bytemuck::cast_slice_mut::<_, f32>(&mut uniform.values)[..kernel.values.len()]
+        .copy_from_slice(&kernel.values);
+
+    let output = create_wgpu_filter_texture(handle, size_of(input), input.format());
+    filter_pass(
+        handle,
+        encoder,
+        PipelineShader::FilterConvolve,
+        bytemuck::bytes_of(&uniform),
+        &[input],
+        &output,
+    );
+    output
+}
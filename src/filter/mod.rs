@@ -0,0 +1,331 @@
+mod blur;
+mod color;
+mod convolve;
+
+pub use color::*;
+pub use convolve::*;
+
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+
+use crate::{BlendMode, Color, Dimension, Handle, PipelineKey, PipelineShader, StencilMode, Vec2};
+
+/// An image effect applied to a whole [Texture](crate::Texture) or layer.
+///
+/// Filters operate on premultiplied colors, as G2d draws them into transparent textures.  See
+/// [Canvas::push_layer](crate::Canvas::push_layer) and
+/// [Handle::apply_filters](crate::Handle::apply_filters).
+#[derive(Clone, Debug, PartialEq)]
+pub enum Filter {
+    /// A Gaussian blur with the provided standard deviation, in pixels, like the CSS `blur()`
+    /// function.
+    Blur(f32),
+
+    /// A blurred copy of the alpha of the texture, filled with *color* and drawn beneath it, like
+    /// the CSS `drop-shadow()` function.
+    DropShadow {
+        /// How far the shadow is moved, in pixels.
+        offset: Vec2,
+
+        /// The standard deviation of the blur of the shadow, in pixels.
+        blur: f32,
+        color: Color,
+    },
+
+    /// Transforms the unpremultiplied colors with a [ColorMatrix], like the SVG `feColorMatrix`
+    /// primitive.
+    ColorMatrix {
+        matrix: ColorMatrix,
+        color_space: FilterColorSpace,
+    },
+
+    /// Replaces every pixel with the weighted sum of its neighbours, like the SVG
+    /// `feConvolveMatrix` primitive.
+    Convolve {
+        kernel: ConvolutionKernel,
+        color_space: FilterColorSpace,
+    },
+}
+
+impl Filter {
+    /// Creates a [Filter::Blur].
+    #[inline]
+    pub const fn blur(std_deviation: f32) -> Self {
+        Self::Blur(std_deviation)
+    }
+
+    /// Creates a [Filter::DropShadow].
+    #[inline]
+    pub const fn drop_shadow(offset: Vec2, blur: f32, color: Color) -> Self {
+        Self::DropShadow {
+            offset,
+            blur,
+            color,
+        }
+    }
+
+    /// Creates a [Filter::ColorMatrix] working in sRGB.
+    #[inline]
+    pub const fn color_matrix(matrix: ColorMatrix) -> Self {
+        Self::ColorMatrix {
+            matrix,
+            color_space: FilterColorSpace::Srgb,
+        }
+    }
+
+    /// Creates a [Filter::Convolve] working in sRGB.
+    #[inline]
+    pub const fn convolve(kernel: ConvolutionKernel) -> Self {
+        Self::Convolve {
+            kernel,
+            color_space: FilterColorSpace::Srgb,
+        }
+    }
+
+    /// Like CSS `brightness()`, see [ColorMatrix::brightness].
+    #[inline]
+    pub const fn brightness(amount: f32) -> Self {
+        Self::color_matrix(ColorMatrix::brightness(amount))
+    }
+
+    /// Like CSS `contrast()`, see [ColorMatrix::contrast].
+    #[inline]
+    pub fn contrast(amount: f32) -> Self {
+        Self::color_matrix(ColorMatrix::contrast(amount))
+    }
+
+    /// Like CSS `saturate()`, see [ColorMatrix::saturate].
+    #[inline]
+    pub fn saturate(amount: f32) -> Self {
+        Self::color_matrix(ColorMatrix::saturate(amount))
+    }
+
+    /// Like CSS `hue-rotate()`, see [ColorMatrix::hue_rotate].
+    #[inline]
+    pub fn hue_rotate(degrees: f32) -> Self {
+        Self::color_matrix(ColorMatrix::hue_rotate(degrees))
+    }
+
+    /// Like CSS `grayscale()`, see [ColorMatrix::grayscale].
+    #[inline]
+    pub fn grayscale(amount: f32) -> Self {
+        Self::color_matrix(ColorMatrix::grayscale(amount))
+    }
+
+    /// Like CSS `sepia()`, see [ColorMatrix::sepia].
+    #[inline]
+    pub fn sepia(amount: f32) -> Self {
+        Self::color_matrix(ColorMatrix::sepia(amount))
+    }
+
+    /// Like CSS `invert()`, see [ColorMatrix::invert].
+    #[inline]
+    pub fn invert(amount: f32) -> Self {
+        Self::color_matrix(ColorMatrix::invert(amount))
+    }
+
+    /// Like CSS `opacity()`, see [ColorMatrix::opacity].
+    #[inline]
+    pub fn opacity(amount: f32) -> Self {
+        Self::color_matrix(ColorMatrix::opacity(amount))
+    }
+
+    /// Sets the [FilterColorSpace] of a [Filter::ColorMatrix] or [Filter::Convolve].
+    ///
+    /// Blurs and drop shadows always blend in the space textures are sampled in: linear for sRGB
+    /// formats, as stored for the others.
+    #[inline]
+    pub fn with_color_space(mut self, space: FilterColorSpace) -> Self {
+        if let Self::ColorMatrix { color_space, .. } | Self::Convolve { color_space, .. } =
+            &mut self
+        {
+            *color_space = space;
+        }
+        self
+    }
+
+    /// Returns how far, in pixels, this [Filter] reads beyond the pixel it outputs.
+    pub(crate) fn margin(&self) -> f32 {
+        match *self {
+            // Three standard deviations hold over 99.7% of the weight of a Gaussian.
+            Self::Blur(std_deviation) => std_deviation.abs() * 3.0,
+            Self::DropShadow { offset, blur, .. } => {
+                blur.abs() * 3.0 + offset.x.abs().max(offset.y.abs())
+            }
+            Self::ColorMatrix { .. } => 0.0,
+            Self::Convolve { ref kernel, .. } => kernel.width().max(kernel.height()) as f32,
+        }
+    }
+}
+
+/// Applies *filters* to a [`wgpu::Texture`] in order, returning a new texture of the same size and
+/// format with the result.
+///
+/// The source texture must have the `TEXTURE_BINDING` usage.
+pub(crate) fn apply_wgpu_filters<H: Handle>(
+    handle: &H,
+    wgpu_texture: &wgpu::Texture,
+    filters: &[Filter],
+) -> wgpu::Texture {
+    let mut encoder =
+        handle
+            .wgpu_device()
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Filter Encoder"),
+            });
+
+    let mut output: Option<wgpu::Texture> = None;
+    for filter in filters {
+        let input = output.as_ref().unwrap_or(wgpu_texture);
+        let filtered = match filter {
+            &Filter::Blur(std_deviation) => {
+                blur::gaussian_blur(handle, &mut encoder, input, std_deviation)
+            }
+            &Filter::DropShadow {
+                offset,
+                blur,
+                color,
+            } => blur::drop_shadow(handle, &mut encoder, input, offset, blur, color),
+            Filter::ColorMatrix {
+                matrix,
+                color_space,
+            } => color::color_matrix(handle, &mut encoder, input, matrix, *color_space),
+            Filter::Convolve {
+                kernel,
+                color_space,
+            } => convolve::convolve(handle, &mut encoder, input, kernel, *color_space),
+        };
+        output = Some(filtered);
+    }
+
+    // Filters always return a new texture, even if there is nothing to do.
+    let output = output.unwrap_or_else(|| {
+        let copy = create_wgpu_filter_texture(handle, size_of(wgpu_texture), wgpu_texture.format());
+        encoder.copy_texture_to_texture(
+            wgpu_texture.as_image_copy(),
+            copy.as_image_copy(),
+            wgpu_texture.size(),
+        );
+        copy
+    });
+
+    handle
+        .wgpu_queue()
+        .submit(std::iter::once(encoder.finish()));
+
+    output
+}
+
+/// Returns the size of a [`wgpu::Texture`].
+#[inline]
+fn size_of(wgpu_texture: &wgpu::Texture) -> Dimension {
+    Dimension::new(wgpu_texture.width(), wgpu_texture.height())
+}
+
+/// Creates a texture which filters can read from and render to.
+pub(crate) fn create_wgpu_filter_texture<H: Handle>(
+    handle: &H,
+    size: Dimension,
+    format: wgpu::TextureFormat,
+) -> wgpu::Texture {
+    handle
+        .wgpu_device()
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("Filter Texture"),
+            size: size.to_extent_3d(),
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC
+                | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        })
+}
+
+/// Runs a filter shader over the whole of *target*.
+///
+/// Filter shaders take a uniform at binding 0, a linear sampler at binding 1 and their input
+/// textures from binding 2 onwards.
+fn filter_pass<H: Handle>(
+    handle: &H,
+    encoder: &mut wgpu::CommandEncoder,
+    shader: PipelineShader,
+    uniform: &[u8],
+    inputs: &[&wgpu::Texture],
+    target: &wgpu::Texture,
+) {
+    let wgpu_device = handle.wgpu_device();
+    let wgpu_pipeline = handle.pipeline_cache().get(
+        wgpu_device,
+        PipelineKey {
+            shader,
+            blend_mode: BlendMode::Replace,
+            format: target.format(),
+            sample_count: 1,
+            stencil: StencilMode::None,
+            clipped: false,
+        },
+    );
+
+    let wgpu_uniform_buffer = wgpu_device.create_buffer_init(&BufferInitDescriptor {
+        label: None,
+        contents: uniform,
+        usage: wgpu::BufferUsages::UNIFORM,
+    });
+    let wgpu_sampler = wgpu_device.create_sampler(&wgpu::SamplerDescriptor {
+        mag_filter: wgpu::FilterMode::Linear,
+        min_filter: wgpu::FilterMode::Linear,
+        ..Default::default()
+    });
+    let wgpu_input_views: Vec<_> = inputs
+        .iter()
+        .map(|input| input.create_view(&wgpu::TextureViewDescriptor::default()))
+        .collect();
+
+    let mut entries = vec![
+        wgpu::BindGroupEntry {
+            binding: 0,
+            resource: wgpu_uniform_buffer.as_entire_binding(),
+        },
+        wgpu::BindGroupEntry {
+            binding: 1,
+            resource: wgpu::BindingResource::Sampler(&wgpu_sampler),
+        },
+    ];
+    entries.extend(
+        wgpu_input_views
+            .iter()
+            .zip(2..)
+            .map(|(view, binding)| wgpu::BindGroupEntry {
+                binding,
+                resource: wgpu::BindingResource::TextureView(view),
+            }),
+    );
+    let wgpu_bind_group = wgpu_device.create_bind_group(&wgpu::BindGroupDescriptor {
+        label: None,
+        layout: &wgpu_pipeline.get_bind_group_layout(0),
+        entries: &entries,
+    });
+
+    let wgpu_target_view = target.create_view(&wgpu::TextureViewDescriptor::default());
+    let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Filter Pass"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view: &wgpu_target_view,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+
+    render_pass.set_pipeline(&wgpu_pipeline);
+    render_pass.set_bind_group(0, &wgpu_bind_group, &[]);
+    render_pass.draw(0..3, 0..1);
+}
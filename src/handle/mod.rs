@@ -1,6 +1,8 @@
+mod pipeline;
 mod window;
 mod windowless;
 
+pub use pipeline::*;
 use wgpu::{
     util::{BufferInitDescriptor, DeviceExt},
     TextureUsages,
@@ -8,7 +10,14 @@ use wgpu::{
 pub use window::*;
 pub use windowless::*;
 
-use crate::{Dimension, Error, OwnedTexture, Paint, Vertex, VertexBuffer};
+#[cfg(feature = "image")]
+use crate::codec;
+use crate::{
+    filter::apply_wgpu_filters,
+    texture::{blit_wgpu_texture, copy_wgpu_texture, generate_wgpu_mipmaps},
+    Dimension, Error, Filter, GlyphAtlas, MipmapFilter, OwnedTexture, PixelRect, ResampleFilter,
+    Texture, TextureDescriptor, TextureFormat, Vertex, VertexBuffer,
+};
 
 /// Creates a [`wgpu::Instance`] with the default settings for G2d.
 #[inline]
@@ -55,66 +64,27 @@ pub(crate) async fn request_wgpu_device(
         .map_err(|err| Error::FailedToAcquireDevice(err.to_string()))
 }
 
-/// Creates a [`wgpu::RenderPipeline`] for with the provided options.
-pub(crate) fn create_wgpu_render_pipeline(
-    wgpu_device: &wgpu::Device,
-    wgpu_render_pipeline_layout: &wgpu::PipelineLayout,
-    wgpu_shader: &wgpu::ShaderModule,
-) -> wgpu::RenderPipeline {
-    wgpu_device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-        label: Some("Render Pipeline"),
-        layout: Some(wgpu_render_pipeline_layout),
-        vertex: wgpu::VertexState {
-            module: &wgpu_shader,
-            entry_point: "vs_main",
-            // NOTE: the kind of [Handle] does not affect the layout of a [VertexBuffer].  It is
-            //       simply needed to access the `wgpu_desc` method.
-            buffers: &[VertexBuffer::<WindowlessHandle>::wgpu_desc()],
-        },
-        fragment: Some(wgpu::FragmentState {
-            module: &wgpu_shader,
-            entry_point: "fs_main",
-            targets: &[Some(wgpu::ColorTargetState {
-                format: wgpu::TextureFormat::Bgra8UnormSrgb,
-                blend: Some(wgpu::BlendState::REPLACE),
-                write_mask: wgpu::ColorWrites::ALL,
-            })],
-        }),
-        primitive: wgpu::PrimitiveState {
-            topology: wgpu::PrimitiveTopology::TriangleList,
-            strip_index_format: None,
-            front_face: wgpu::FrontFace::Ccw,
-            cull_mode: Some(wgpu::Face::Back),
-            // Setting this to anything other than Fill requires Features::NON_FILL_POLYGON_MODE
-            polygon_mode: wgpu::PolygonMode::Fill,
-            // Requires Features::DEPTH_CLIP_CONTROL
-            unclipped_depth: false,
-            // Requires Features::CONSERVATIVE_RASTERIZATION
-            conservative: false,
-        },
-        depth_stencil: None,
-        multisample: wgpu::MultisampleState {
-            count: 1,
-            mask: !0,
-            alpha_to_coverage_enabled: false,
-        },
-        multiview: None,
-    })
+/// Converts a G2d [TextureDescriptor] to a [`wgpu::TextureDescriptor`].
+fn wgpu_texture_descriptor(descriptor: &TextureDescriptor) -> wgpu::TextureDescriptor<'static> {
+    wgpu::TextureDescriptor {
+        size: descriptor.size.to_extent_3d(),
+        mip_level_count: descriptor.mip_level_count(),
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: descriptor.format.to_wgpu_format(),
+        usage: wgpu::TextureUsages::all().difference(TextureUsages::STORAGE_BINDING), // TODO: all?
+        label: None,
+        view_formats: &[],
+    }
 }
 
-/// Creates a [`wgpu::RenderPipeline`] for rendering basic color-filled shapes.
-pub(crate) fn paint_fill_pipeline(wgpu_device: &wgpu::Device) -> wgpu::RenderPipeline {
-    let wgpu_shader =
-        wgpu_device.create_shader_module(wgpu::include_wgsl!("../shaders/paint_fill.wgsl"));
-
-    let wgpu_render_pipeline_layout =
-        wgpu_device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Render Pipeline Layout"),
-            bind_group_layouts: &[],
-            push_constant_ranges: &[],
-        });
-
-    create_wgpu_render_pipeline(wgpu_device, &wgpu_render_pipeline_layout, &wgpu_shader)
+/// Checks that a region fits within a [Texture] of the provided size.
+fn check_region(region: PixelRect, size: Dimension) -> Result<(), Error> {
+    if region.fits_within(size) {
+        Ok(())
+    } else {
+        Err(Error::RegionOutOfBounds { region, size })
+    }
 }
 
 /// A handle to the G2d API.
@@ -128,17 +98,20 @@ pub trait Handle: Sized {
     /// Creates a new [Texture](crate::Texture) with the provided size.  Leaves the texture blank.
     ///
     /// NOTE: G2d textures default to the `Rgba8UnormSrgb` format.
+    #[inline]
     fn make_blank_texture(&self, size: Dimension) -> OwnedTexture<'_, Self> {
-        let wgpu_texture = self.wgpu_device().create_texture(&wgpu::TextureDescriptor {
-            size: size.to_extent_3d(),
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8UnormSrgb,
-            usage: wgpu::TextureUsages::all().difference(TextureUsages::STORAGE_BINDING), // TODO: all?
-            label: None,
-            view_formats: &[],
-        });
+        self.make_blank_texture_with_descriptor(&TextureDescriptor::new(size))
+    }
+
+    /// Creates a new [Texture](crate::Texture) from the provided [TextureDescriptor].  Leaves the
+    /// texture blank.
+    fn make_blank_texture_with_descriptor(
+        &self,
+        descriptor: &TextureDescriptor,
+    ) -> OwnedTexture<'_, Self> {
+        let wgpu_texture = self
+            .wgpu_device()
+            .create_texture(&wgpu_texture_descriptor(descriptor));
 
         OwnedTexture::from_raw_parts(self, wgpu_texture)
     }
@@ -149,32 +122,107 @@ pub trait Handle: Sized {
     ///
     /// # Fails
     /// Fails if the data is too big or small for the provided size.
+    #[inline]
     fn make_texture(&self, size: Dimension, data: &[u8]) -> Result<OwnedTexture<'_, Self>, Error> {
-        if data.len() as u32 != size.area() * 4 {
+        self.make_texture_with_descriptor(&TextureDescriptor::new(size), data)
+    }
+
+    /// Creates a [Texture](crate::Texture) from the provided [TextureDescriptor] and data.
+    ///
+    /// Expects the data to be in the [TextureFormat](crate::TextureFormat) of the descriptor.
+    /// If the descriptor has mipmaps, the data fills the first mip level, and the others are
+    /// generated from it with [MipmapFilter::Box].
+    ///
+    /// # Fails
+    /// Fails if the data is too big or small for the provided size and format.
+    fn make_texture_with_descriptor(
+        &self,
+        descriptor: &TextureDescriptor,
+        data: &[u8],
+    ) -> Result<OwnedTexture<'_, Self>, Error> {
+        if data.len() as u32 != descriptor.data_len() {
             return Err(Error::TextureDataSizeMismatch {
-                expected: size.area() * 4,
+                expected: descriptor.data_len(),
                 got: data.len() as u32,
             });
         }
 
+        if descriptor.mipmaps {
+            let texture = self.make_blank_texture_with_descriptor(descriptor);
+            texture.canvas().write(data)?;
+            self.generate_mipmaps(&texture, MipmapFilter::Box)?;
+            return Ok(texture);
+        }
+
         let wgpu_texture = self.wgpu_device().create_texture_with_data(
             self.wgpu_queue(),
-            &wgpu::TextureDescriptor {
-                size: size.to_extent_3d(),
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Rgba8UnormSrgb,
-                usage: wgpu::TextureUsages::all().difference(TextureUsages::STORAGE_BINDING), // TODO: all?
-                label: None,
-                view_formats: &[],
-            },
+            &wgpu_texture_descriptor(descriptor),
             data,
         );
 
         Ok(OwnedTexture::from_raw_parts(self, wgpu_texture))
     }
 
+    /// Creates a [Texture](crate::Texture) from an image file in memory, in the provided
+    /// [TextureFormat](crate::TextureFormat).  PNG, JPEG, GIF, BMP, QOI and WebP files are
+    /// supported, with the format guessed from the contents.
+    ///
+    /// The image is converted to straight alpha and to the color space of the format: formats
+    /// storing linear data get linear colors, and `R8Unorm` gets the luminance of the image.
+    ///
+    /// Only available with the `image` feature.
+    ///
+    /// # Fails
+    /// Fails if the image can't be decoded.
+    #[cfg(feature = "image")]
+    fn load_texture_from_bytes(
+        &self,
+        bytes: &[u8],
+        format: TextureFormat,
+    ) -> Result<OwnedTexture<'_, Self>, Error> {
+        let (size, data) = codec::decode_image(codec::read_image_from_bytes(bytes)?, format);
+        self.make_texture_with_descriptor(&TextureDescriptor::new(size).with_format(format), &data)
+    }
+
+    /// Creates a [Texture](crate::Texture) from an image file, like
+    /// [Handle::load_texture_from_bytes].  The format of the file is guessed from its contents or
+    /// extension.
+    ///
+    /// Only available with the `image` feature.
+    ///
+    /// # Fails
+    /// Fails if the file can't be read or decoded.
+    #[cfg(feature = "image")]
+    fn load_texture_from_path(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        format: TextureFormat,
+    ) -> Result<OwnedTexture<'_, Self>, Error> {
+        let (size, data) = codec::decode_image(codec::read_image_from_path(path.as_ref())?, format);
+        self.make_texture_with_descriptor(&TextureDescriptor::new(size).with_format(format), &data)
+    }
+
+    /// Fills the mip levels of a [Texture] after the first by repeatedly downsampling the first
+    /// level with the provided [MipmapFilter].  Does nothing if the texture has a single level.
+    ///
+    /// Colors are filtered in linear space for sRGB formats, and weighted by their alpha.
+    ///
+    /// # Fails
+    /// Fails if the texture does not have the `RENDER_ATTACHMENT` and `TEXTURE_BINDING` usages.
+    fn generate_mipmaps(
+        &self,
+        texture: &impl Texture<Self>,
+        filter: MipmapFilter,
+    ) -> Result<(), Error> {
+        let usages = TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING;
+        if !texture.wgpu_texture_usage().contains(usages) {
+            return Err(Error::LackingTextureUsage(usages));
+        }
+
+        generate_wgpu_mipmaps(self, texture.wgpu_texture(), filter);
+        Ok(())
+    }
+
     /// Creates a new [VertexBuffer], initialized with the provided data.
     fn make_vertex_buffer(&self, data: &[Vertex]) -> VertexBuffer<'_, Self> {
         let wgpu_buffer = self
@@ -188,6 +236,166 @@ pub trait Handle: Sized {
         VertexBuffer::from_raw_parts(self, wgpu_buffer, data.len() as wgpu::BufferAddress)
     }
 
-    /// Returns the [`wgpu::RenderPipeline`] for the provided [Paint] type.
-    fn wgpu_render_pipeline_for_paint(&self, paint: &Paint) -> &wgpu::RenderPipeline;
+    /// Applies [Filters](Filter) to a [Texture] in order, returning the result as a new texture of
+    /// the same size and format.  The source texture is left untouched.
+    ///
+    /// # Fails
+    /// Fails if the texture does not have the `TEXTURE_BINDING` usage.
+    fn apply_filters(
+        &self,
+        texture: &impl Texture<Self>,
+        filters: &[Filter],
+    ) -> Result<OwnedTexture<'_, Self>, Error> {
+        if !texture
+            .wgpu_texture_usage()
+            .contains(TextureUsages::TEXTURE_BINDING)
+        {
+            return Err(Error::LackingTextureUsage(TextureUsages::TEXTURE_BINDING));
+        }
+
+        let wgpu_texture = apply_wgpu_filters(self, texture.wgpu_texture(), filters);
+        Ok(OwnedTexture::from_raw_parts(self, wgpu_texture))
+    }
+
+    /// Copies the *src_rect* region of a [Texture] into another, with its top-left corner at
+    /// *dst_point*.  Pixels are copied exactly, without filtering or blending, and only the first
+    /// mip level of either texture is used.
+    ///
+    /// Both textures may be the same, even if the regions overlap.
+    ///
+    /// # Fails
+    /// - Fails if *src* does not have the `COPY_SRC` usage, or *dst* the `COPY_DST` usage.
+    /// - Fails if the formats of the textures differ by more than being sRGB.
+    /// - Fails if the region doesn't fit within either texture.
+    fn copy_texture(
+        &self,
+        src: &impl Texture<Self>,
+        src_rect: PixelRect,
+        dst: &impl Texture<Self>,
+        dst_point: [u32; 2],
+    ) -> Result<(), Error> {
+        if !src.wgpu_texture_usage().contains(TextureUsages::COPY_SRC) {
+            return Err(Error::LackingTextureUsage(TextureUsages::COPY_SRC));
+        }
+        if !dst.wgpu_texture_usage().contains(TextureUsages::COPY_DST) {
+            return Err(Error::LackingTextureUsage(TextureUsages::COPY_DST));
+        }
+        if src.wgpu_texture_format().remove_srgb_suffix()
+            != dst.wgpu_texture_format().remove_srgb_suffix()
+        {
+            return Err(Error::TextureFormatMismatch {
+                src: src.wgpu_texture_format(),
+                dst: dst.wgpu_texture_format(),
+            });
+        }
+        let [x, y] = dst_point;
+        check_region(src_rect, src.size())?;
+        check_region(
+            PixelRect::new(x, y, src_rect.width, src_rect.height),
+            dst.size(),
+        )?;
+
+        copy_wgpu_texture(
+            self,
+            src.wgpu_texture(),
+            src_rect,
+            dst.wgpu_texture(),
+            dst_point,
+        );
+        Ok(())
+    }
+
+    /// Scales the *src_rect* region of a [Texture] onto the *dst_rect* region of another with the
+    /// provided [ResampleFilter], replacing the pixels of the destination region.  Only the first
+    /// mip level of either texture is used.
+    ///
+    /// Colors are filtered in linear space for sRGB formats, and weighted by their alpha.  Both
+    /// textures may be the same, even if the regions overlap.
+    ///
+    /// # Fails
+    /// - Fails if *src* does not have the `TEXTURE_BINDING` usage, or *dst* the
+    ///   `RENDER_ATTACHMENT` usage.
+    /// - Fails if G2d doesn't support the format of either texture.
+    /// - Fails if either region doesn't fit within its texture.
+    fn blit(
+        &self,
+        src: &impl Texture<Self>,
+        src_rect: PixelRect,
+        dst: &impl Texture<Self>,
+        dst_rect: PixelRect,
+        filter: ResampleFilter,
+    ) -> Result<(), Error> {
+        if !src
+            .wgpu_texture_usage()
+            .contains(TextureUsages::TEXTURE_BINDING)
+        {
+            return Err(Error::LackingTextureUsage(TextureUsages::TEXTURE_BINDING));
+        }
+        if !dst
+            .wgpu_texture_usage()
+            .contains(TextureUsages::RENDER_ATTACHMENT)
+        {
+            return Err(Error::LackingTextureUsage(TextureUsages::RENDER_ATTACHMENT));
+        }
+        for format in [src.wgpu_texture_format(), dst.wgpu_texture_format()] {
+            if TextureFormat::from_wgpu_format(format).is_none() {
+                return Err(Error::UnsupportedTextureFormat(format));
+            }
+        }
+        check_region(src_rect, src.size())?;
+        check_region(dst_rect, dst.size())?;
+
+        blit_wgpu_texture(
+            self,
+            src.wgpu_texture(),
+            src_rect,
+            dst.wgpu_texture(),
+            dst_rect,
+            filter,
+        );
+        Ok(())
+    }
+
+    /// Scales a [Texture] to a new size with the provided [ResampleFilter], like [Handle::blit],
+    /// returning the result as a new texture of the same format.  The source texture is left
+    /// untouched.
+    ///
+    /// If the source texture has mipmaps, the result gets a full mip chain, generated with
+    /// [MipmapFilter::Box].
+    ///
+    /// # Fails
+    /// - Fails if the texture does not have the `TEXTURE_BINDING` usage.
+    /// - Fails if G2d doesn't support the format of the texture.
+    fn resize_texture(
+        &self,
+        texture: &impl Texture<Self>,
+        new_size: Dimension,
+        filter: ResampleFilter,
+    ) -> Result<OwnedTexture<'_, Self>, Error> {
+        let format = TextureFormat::from_wgpu_format(texture.wgpu_texture_format()).ok_or(
+            Error::UnsupportedTextureFormat(texture.wgpu_texture_format()),
+        )?;
+        let resized = self.make_blank_texture_with_descriptor(
+            &TextureDescriptor::new(new_size)
+                .with_format(format)
+                .with_mipmaps(texture.mip_level_count() > 1),
+        );
+
+        self.blit(
+            texture,
+            PixelRect::from_size(texture.size()),
+            &resized,
+            PixelRect::from_size(new_size),
+            filter,
+        )?;
+        self.generate_mipmaps(&resized, MipmapFilter::Box)?;
+        Ok(resized)
+    }
+
+    /// Returns the [PipelineCache] holding the [`wgpu::RenderPipeline`]s this [Handle] has
+    /// created.
+    fn pipeline_cache(&self) -> &PipelineCache;
+
+    /// Returns the [GlyphAtlas] caching the glyphs this [Handle] has drawn.
+    fn glyph_atlas(&self) -> &GlyphAtlas;
 }
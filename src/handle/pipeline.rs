@@ -0,0 +1,508 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    shadow::BoxShadowInstance, shape::ShapeInstance, sprite::SpriteInstance, text::GlyphInstance,
+    BlendMode, Vec2, VertexBuffer, WindowlessHandle,
+};
+
+/// The vertex attributes of the [PipelineShader::ClipPath] shader.
+const CLIP_PATH_WGPU_ATTRIBS: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![
+    0 => Float32x2, // position
+];
+
+/// The instance attributes of the [PipelineShader::ClipPath] shader.
+const CLIP_PATH_SAMPLE_WGPU_ATTRIBS: [wgpu::VertexAttribute; 1] = wgpu::vertex_attr_array![
+    1 => Float32x2, // sample
+];
+
+/// The shader program used by a [`wgpu::RenderPipeline`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub(crate) enum PipelineShader {
+    /// Draws a [VertexBuffer] with a [Paint](crate::Paint).
+    PaintFill,
+
+    /// Draws [Shapes](crate::Shape) with signed distance fields.
+    SdfShape,
+
+    /// Draws glyphs from a [GlyphAtlas](crate::GlyphAtlas) with a [Paint](crate::Paint).
+    Text,
+
+    /// Draws glyphs from their signed distance fields in a [GlyphAtlas](crate::GlyphAtlas), see
+    /// [Canvas::draw_sdf_text_layout](crate::Canvas::draw_sdf_text_layout).
+    SdfText,
+
+    /// Draws color glyphs, like emoji, from a [GlyphAtlas](crate::GlyphAtlas).
+    ColorText,
+
+    /// Draws glyphs from the pages of a [BitmapFont](crate::BitmapFont) with a
+    /// [Paint](crate::Paint).
+    BitmapText,
+
+    /// Draws rectangles of a texture, see [Canvas::draw_texture](crate::Canvas::draw_texture).
+    Sprite,
+
+    /// Draws analytically blurred [BoxShadows](crate::BoxShadow).
+    BoxShadow,
+
+    /// Writes the triangles of a clip path to the stencil buffer, and covers the clip mask with
+    /// the pixels inside every clip path.
+    ClipPath,
+
+    /// Composites a layer through a mask, see [Canvas::push_mask](crate::Canvas::push_mask).
+    LayerMask,
+
+    /// Composites a layer onto the texture below it.
+    LayerComposite,
+
+    /// One direction of a separable Gaussian blur, see [Filter::Blur](crate::Filter::Blur).
+    FilterBlur,
+
+    /// Draws a blurred shadow beneath a texture, see
+    /// [Filter::DropShadow](crate::Filter::DropShadow).
+    FilterDropShadow,
+
+    /// Transforms colors with a matrix, see [Filter::ColorMatrix](crate::Filter::ColorMatrix).
+    FilterColorMatrix,
+
+    /// Convolves a texture with a kernel, see [Filter::Convolve](crate::Filter::Convolve).
+    FilterConvolve,
+
+    /// Downsamples a mip level into the next, see
+    /// [Handle::generate_mipmaps](crate::Handle::generate_mipmaps).
+    Mipmap,
+
+    /// Resamples a texture along one axis, see [Handle::blit](crate::Handle::blit).
+    Resample,
+}
+
+impl PipelineShader {
+    /// Returns `true` if this shader outputs colors with premultiplied alpha.
+    #[inline]
+    const fn is_premultiplied(&self) -> bool {
+        match self {
+            Self::PaintFill | Self::ClipPath | Self::Mipmap | Self::Resample => false,
+            Self::SdfShape
+            | Self::Text
+            | Self::SdfText
+            | Self::ColorText
+            | Self::BitmapText
+            | Self::Sprite
+            | Self::BoxShadow
+            | Self::LayerMask
+            | Self::LayerComposite
+            | Self::FilterBlur
+            | Self::FilterDropShadow
+            | Self::FilterColorMatrix
+            | Self::FilterConvolve => true,
+        }
+    }
+}
+
+/// How a [`wgpu::RenderPipeline`] uses the stencil buffer.
+///
+/// The stencil buffer is used to render the clip paths of a [Canvas](crate::Canvas) into its clip
+/// mask, one sample at a time.  The highest bit of a stencil value is set if the sample is inside
+/// every clip path, the lower 7 bits count the winding of the clip path being added.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub(crate) enum StencilMode {
+    /// The render pass has no stencil buffer.
+    None,
+
+    /// Only draws inside the clip, and puts the pixels outside of it back inside for the next
+    /// sample.  Expects the stencil reference to be [StencilMode::INSIDE].
+    Cover,
+
+    /// Counts the winding of triangles, incrementing for front faces and decrementing for back
+    /// faces.
+    WindNonZero,
+
+    /// Flips the winding between zero and non-zero for every triangle.
+    WindEvenOdd,
+
+    /// Removes the pixels with a winding of zero from the clip.  Expects the stencil reference to
+    /// be `0`.
+    Intersect,
+
+    /// Resets the winding of every pixel to zero.
+    ResetWinding,
+}
+
+impl StencilMode {
+    /// The stencil bit set for pixels inside the clip.
+    pub const INSIDE: u32 = 0x80;
+
+    /// The stencil bits counting the winding of a clip path.
+    const WINDING: u32 = 0x7f;
+
+    /// The format of the stencil buffer.
+    pub const WGPU_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Stencil8;
+
+    /// Returns `true` if pipelines with this [StencilMode] only write to the stencil buffer.
+    #[inline]
+    const fn is_stencil_only(&self) -> bool {
+        !matches!(self, Self::None | Self::Cover)
+    }
+
+    /// Converts this [StencilMode] to a [`wgpu::DepthStencilState`].
+    fn to_wgpu_depth_stencil_state(self) -> Option<wgpu::DepthStencilState> {
+        let face_with_fail_op = |compare, fail_op, pass_op| wgpu::StencilFaceState {
+            compare,
+            fail_op,
+            depth_fail_op: wgpu::StencilOperation::Keep,
+            pass_op,
+        };
+        let face =
+            |compare, pass_op| face_with_fail_op(compare, wgpu::StencilOperation::Keep, pass_op);
+
+        let (front, back, read_mask, write_mask) = match self {
+            Self::None => return None,
+            Self::Cover => {
+                let face = face_with_fail_op(
+                    wgpu::CompareFunction::Equal,
+                    wgpu::StencilOperation::Replace,
+                    wgpu::StencilOperation::Keep,
+                );
+                (face, face, Self::INSIDE, Self::INSIDE)
+            }
+            Self::WindNonZero => (
+                face(
+                    wgpu::CompareFunction::Always,
+                    wgpu::StencilOperation::IncrementWrap,
+                ),
+                face(
+                    wgpu::CompareFunction::Always,
+                    wgpu::StencilOperation::DecrementWrap,
+                ),
+                0,
+                Self::WINDING,
+            ),
+            Self::WindEvenOdd => {
+                let face = face(
+                    wgpu::CompareFunction::Always,
+                    wgpu::StencilOperation::Invert,
+                );
+                (face, face, 0, Self::WINDING)
+            }
+            Self::Intersect => {
+                let face = face(wgpu::CompareFunction::Equal, wgpu::StencilOperation::Zero);
+                (face, face, Self::WINDING, Self::INSIDE)
+            }
+            Self::ResetWinding => {
+                let face = face(wgpu::CompareFunction::Always, wgpu::StencilOperation::Zero);
+                (face, face, 0, Self::WINDING)
+            }
+        };
+
+        Some(wgpu::DepthStencilState {
+            format: Self::WGPU_FORMAT,
+            depth_write_enabled: false,
+            depth_compare: wgpu::CompareFunction::Always,
+            stencil: wgpu::StencilState {
+                front,
+                back,
+                read_mask,
+                write_mask,
+            },
+            bias: wgpu::DepthBiasState::default(),
+        })
+    }
+}
+
+/// Everything that determines which [`wgpu::RenderPipeline`] a draw call needs.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub(crate) struct PipelineKey {
+    pub shader: PipelineShader,
+    pub blend_mode: BlendMode,
+
+    /// The format of the texture being drawn to.
+    pub format: wgpu::TextureFormat,
+
+    /// The sample count of the texture being drawn to.
+    pub sample_count: u32,
+    pub stencil: StencilMode,
+
+    /// Whether the output of the shader is multiplied by the clip mask of the
+    /// [Canvas](crate::Canvas) being drawn to, bound to group `1`.
+    pub clipped: bool,
+}
+
+/// A lazily populated cache of the [`wgpu::RenderPipeline`]s used by a [Handle](crate::Handle).
+///
+/// A pipeline is only valid for one texture format and sample count, so G2d creates one for every
+/// kind of texture it draws to.
+#[derive(Debug, Default)]
+pub struct PipelineCache {
+    wgpu_render_pipelines: Mutex<HashMap<PipelineKey, Arc<wgpu::RenderPipeline>>>,
+}
+
+impl PipelineCache {
+    /// Creates an empty [PipelineCache].
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the [`wgpu::RenderPipeline`] for the provided [PipelineKey], creating it if it
+    /// doesn't exist yet.
+    pub(crate) fn get(
+        &self,
+        wgpu_device: &wgpu::Device,
+        key: PipelineKey,
+    ) -> Arc<wgpu::RenderPipeline> {
+        self.wgpu_render_pipelines
+            .lock()
+            .unwrap()
+            .entry(key)
+            .or_insert_with(|| Arc::new(create_wgpu_render_pipeline(wgpu_device, &key)))
+            .clone()
+    }
+}
+
+/// Creates a [`wgpu::ShaderModule`] from a shader drawing to a [Canvas](crate::Canvas), prefixed
+/// with the `clip_coverage` function reading the clip mask if the [PipelineKey] is clipped.
+fn create_wgpu_canvas_shader_module(
+    wgpu_device: &wgpu::Device,
+    key: &PipelineKey,
+    label: &str,
+    source: &str,
+) -> wgpu::ShaderModule {
+    let clip_source = if key.clipped {
+        include_str!("../shaders/clip_mask.wgsl")
+    } else {
+        include_str!("../shaders/no_clip_mask.wgsl")
+    };
+
+    wgpu_device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some(label),
+        source: wgpu::ShaderSource::Wgsl(format!("{clip_source}{source}").into()),
+    })
+}
+
+/// Like `create_wgpu_canvas_shader_module`, for a shader filling shapes with a
+/// [Paint](crate::Paint), also prefixed with the code shared by those shaders.
+fn create_wgpu_paint_shader_module(
+    wgpu_device: &wgpu::Device,
+    key: &PipelineKey,
+    label: &str,
+    source: &str,
+) -> wgpu::ShaderModule {
+    create_wgpu_canvas_shader_module(
+        wgpu_device,
+        key,
+        label,
+        &format!("{}{source}", include_str!("../shaders/paint.wgsl")),
+    )
+}
+
+/// Creates a [`wgpu::RenderPipeline`] for the provided [PipelineKey].
+fn create_wgpu_render_pipeline(
+    wgpu_device: &wgpu::Device,
+    key: &PipelineKey,
+) -> wgpu::RenderPipeline {
+    let (wgpu_shader, buffers, cull_mode) = match key.shader {
+        PipelineShader::PaintFill => (
+            create_wgpu_paint_shader_module(
+                wgpu_device,
+                key,
+                "paint_fill.wgsl",
+                include_str!("../shaders/paint_fill.wgsl"),
+            ),
+            // NOTE: the kind of [Handle] does not affect the layout of a [VertexBuffer].  It is
+            //       simply needed to access the `wgpu_desc` method.
+            vec![VertexBuffer::<WindowlessHandle>::wgpu_desc()],
+            Some(wgpu::Face::Back),
+        ),
+        PipelineShader::SdfShape => (
+            create_wgpu_canvas_shader_module(
+                wgpu_device,
+                key,
+                "sdf_shape.wgsl",
+                include_str!("../shaders/sdf_shape.wgsl"),
+            ),
+            vec![ShapeInstance::wgpu_desc()],
+            None,
+        ),
+        PipelineShader::Text => (
+            create_wgpu_paint_shader_module(
+                wgpu_device,
+                key,
+                "text.wgsl",
+                include_str!("../shaders/text.wgsl"),
+            ),
+            vec![GlyphInstance::wgpu_desc()],
+            None,
+        ),
+        PipelineShader::SdfText => (
+            create_wgpu_paint_shader_module(
+                wgpu_device,
+                key,
+                "sdf_text.wgsl",
+                include_str!("../shaders/sdf_text.wgsl"),
+            ),
+            vec![GlyphInstance::wgpu_desc()],
+            None,
+        ),
+        PipelineShader::ColorText => (
+            create_wgpu_canvas_shader_module(
+                wgpu_device,
+                key,
+                "color_text.wgsl",
+                include_str!("../shaders/color_text.wgsl"),
+            ),
+            vec![GlyphInstance::wgpu_desc()],
+            None,
+        ),
+        PipelineShader::BitmapText => (
+            create_wgpu_paint_shader_module(
+                wgpu_device,
+                key,
+                "bitmap_text.wgsl",
+                include_str!("../shaders/bitmap_text.wgsl"),
+            ),
+            vec![GlyphInstance::wgpu_desc()],
+            None,
+        ),
+        PipelineShader::Sprite => (
+            create_wgpu_canvas_shader_module(
+                wgpu_device,
+                key,
+                "sprite.wgsl",
+                include_str!("../shaders/sprite.wgsl"),
+            ),
+            vec![SpriteInstance::wgpu_desc()],
+            None,
+        ),
+        PipelineShader::BoxShadow => (
+            create_wgpu_canvas_shader_module(
+                wgpu_device,
+                key,
+                "box_shadow.wgsl",
+                include_str!("../shaders/box_shadow.wgsl"),
+            ),
+            vec![BoxShadowInstance::wgpu_desc()],
+            None,
+        ),
+        PipelineShader::ClipPath => (
+            wgpu_device.create_shader_module(wgpu::include_wgsl!("../shaders/clip_path.wgsl")),
+            vec![
+                wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<Vec2>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &CLIP_PATH_WGPU_ATTRIBS,
+                },
+                wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<Vec2>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &CLIP_PATH_SAMPLE_WGPU_ATTRIBS,
+                },
+            ],
+            // Both faces are needed to count the winding.
+            None,
+        ),
+        PipelineShader::LayerMask => (
+            create_wgpu_canvas_shader_module(
+                wgpu_device,
+                key,
+                "layer_mask.wgsl",
+                include_str!("../shaders/layer_mask.wgsl"),
+            ),
+            // The vertices are generated from their index.
+            vec![],
+            None,
+        ),
+        PipelineShader::LayerComposite => (
+            create_wgpu_canvas_shader_module(
+                wgpu_device,
+                key,
+                "layer_composite.wgsl",
+                include_str!("../shaders/layer_composite.wgsl"),
+            ),
+            vec![],
+            None,
+        ),
+        PipelineShader::FilterBlur => (
+            wgpu_device.create_shader_module(wgpu::include_wgsl!("../shaders/filter_blur.wgsl")),
+            vec![],
+            None,
+        ),
+        PipelineShader::FilterDropShadow => (
+            wgpu_device
+                .create_shader_module(wgpu::include_wgsl!("../shaders/filter_drop_shadow.wgsl")),
+            vec![],
+            None,
+        ),
+        PipelineShader::FilterColorMatrix => (
+            wgpu_device
+                .create_shader_module(wgpu::include_wgsl!("../shaders/filter_color_matrix.wgsl")),
+            vec![],
+            None,
+        ),
+        PipelineShader::FilterConvolve => (
+            wgpu_device
+                .create_shader_module(wgpu::include_wgsl!("../shaders/filter_convolve.wgsl")),
+            vec![],
+            None,
+        ),
+        PipelineShader::Mipmap => (
+            wgpu_device.create_shader_module(wgpu::include_wgsl!("../shaders/mipmap.wgsl")),
+            vec![],
+            None,
+        ),
+        PipelineShader::Resample => (
+            wgpu_device.create_shader_module(wgpu::include_wgsl!("../shaders/resample.wgsl")),
+            vec![],
+            None,
+        ),
+    };
+
+    let write_mask = if key.stencil.is_stencil_only() {
+        wgpu::ColorWrites::empty()
+    } else {
+        wgpu::ColorWrites::ALL
+    };
+
+    wgpu_device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some(&format!("{:?} Render Pipeline", key.shader)),
+        // The bind group layouts are derived from the shader.
+        layout: None,
+        vertex: wgpu::VertexState {
+            module: &wgpu_shader,
+            entry_point: "vs_main",
+            buffers: &buffers,
+        },
+        fragment: Some(wgpu::FragmentState {
+            module: &wgpu_shader,
+            entry_point: "fs_main",
+            targets: &[Some(wgpu::ColorTargetState {
+                format: key.format,
+                blend: Some(
+                    key.blend_mode
+                        .to_wgpu_blend_state(key.shader.is_premultiplied()),
+                ),
+                write_mask,
+            })],
+        }),
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: wgpu::FrontFace::Ccw,
+            cull_mode,
+            // Setting this to anything other than Fill requires Features::NON_FILL_POLYGON_MODE
+            polygon_mode: wgpu::PolygonMode::Fill,
+            // Requires Features::DEPTH_CLIP_CONTROL
+            unclipped_depth: false,
+            // Requires Features::CONSERVATIVE_RASTERIZATION
+            conservative: false,
+        },
+        depth_stencil: key.stencil.to_wgpu_depth_stencil_state(),
+        multisample: wgpu::MultisampleState {
+            count: key.sample_count,
+            mask: !0,
+            alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+    })
+}
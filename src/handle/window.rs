@@ -1,6 +1,6 @@
 use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
 
-use crate::{Dimension, Error, Frame, Handle, Paint};
+use crate::{Dimension, Error, Frame, GlyphAtlas, Handle, PipelineCache};
 
 /// A [Handle] to the G2d API which is initialized for a specific window.
 #[derive(Debug)]
@@ -11,8 +11,8 @@ pub struct WindowHandle {
     wgpu_device: wgpu::Device,
     wgpu_queue: wgpu::Queue,
 
-    // Render pipelines for different paints
-    paint_fill_pipeline: wgpu::RenderPipeline,
+    pipeline_cache: PipelineCache,
+    glyph_atlas: GlyphAtlas,
 }
 
 impl WindowHandle {
@@ -55,8 +55,8 @@ impl WindowHandle {
         wgpu_surface.configure(&wgpu_device, &wgpu_surface_config);
 
         Ok(Self {
-            paint_fill_pipeline: super::paint_fill_pipeline(&wgpu_device),
-
+            pipeline_cache: PipelineCache::new(),
+            glyph_atlas: GlyphAtlas::default(),
             wgpu_surface,
             wgpu_surface_config,
             surface_size,
@@ -81,7 +81,7 @@ impl WindowHandle {
             self.wgpu_surface_config.width = new_size.width;
             self.wgpu_surface_config.height = new_size.height;
             self.wgpu_surface
-                .configure(&self.wgpu_device(), &self.wgpu_surface_config);
+                .configure(self.wgpu_device(), &self.wgpu_surface_config);
         }
     }
 
@@ -109,9 +109,11 @@ impl Handle for WindowHandle {
         &self.wgpu_queue
     }
 
-    fn wgpu_render_pipeline_for_paint(&self, paint: &Paint) -> &wgpu::RenderPipeline {
-        match paint {
-            Paint::Fill => &self.paint_fill_pipeline,
-        }
+    fn pipeline_cache(&self) -> &PipelineCache {
+        &self.pipeline_cache
+    }
+
+    fn glyph_atlas(&self) -> &GlyphAtlas {
+        &self.glyph_atlas
     }
 }
@@ -1,4 +1,4 @@
-use crate::{Error, Handle, Paint};
+use crate::{Error, GlyphAtlas, Handle, PipelineCache};
 
 /// A [Handle] to the G2d API which doesn't require a window.
 #[derive(Debug)]
@@ -6,8 +6,8 @@ pub struct WindowlessHandle {
     wgpu_device: wgpu::Device,
     wgpu_queue: wgpu::Queue,
 
-    // Render pipelines for different paints
-    paint_fill_pipeline: wgpu::RenderPipeline,
+    pipeline_cache: PipelineCache,
+    glyph_atlas: GlyphAtlas,
 }
 
 impl WindowlessHandle {
@@ -18,8 +18,8 @@ impl WindowlessHandle {
         let (wgpu_device, wgpu_queue) = super::request_wgpu_device(&wgpu_adapter).await?;
 
         Ok(Self {
-            paint_fill_pipeline: super::paint_fill_pipeline(&wgpu_device),
-
+            pipeline_cache: PipelineCache::new(),
+            glyph_atlas: GlyphAtlas::default(),
             wgpu_device,
             wgpu_queue,
         })
@@ -35,9 +35,11 @@ impl Handle for WindowlessHandle {
         &self.wgpu_queue
     }
 
-    fn wgpu_render_pipeline_for_paint(&self, paint: &Paint) -> &wgpu::RenderPipeline {
-        match paint {
-            Paint::Fill => &self.paint_fill_pipeline,
-        }
+    fn pipeline_cache(&self) -> &PipelineCache {
+        &self.pipeline_cache
+    }
+
+    fn glyph_atlas(&self) -> &GlyphAtlas {
+        &self.glyph_atlas
     }
 }
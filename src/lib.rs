@@ -1,27 +1,49 @@
 #![doc = include_str!("../README.md")]
 
+mod blend;
 mod canvas;
+#[cfg(feature = "image")]
+mod codec;
 mod color;
 mod dimension;
 mod error;
+mod filter;
 mod frame;
 mod handle;
+mod nine_slice;
 mod paint;
+mod path;
 mod pixels;
+mod rect;
+mod shadow;
+mod shape;
+mod sprite;
+mod text;
 mod texture;
+mod transform;
 mod vec2;
 mod vertex;
 mod vertex_buffer;
 
+pub use blend::*;
 pub use canvas::*;
 pub use color::*;
 pub use dimension::*;
 pub use error::*;
+pub use filter::*;
 pub use frame::*;
 pub use handle::*;
+pub use nine_slice::*;
 pub use paint::*;
+pub use path::*;
 pub use pixels::*;
+pub use rect::*;
+pub use shadow::*;
+pub use shape::*;
+pub use sprite::*;
+pub use text::*;
 pub use texture::*;
+pub use transform::*;
 pub use vec2::*;
 pub use vertex::*;
 pub use vertex_buffer::*;
@@ -0,0 +1,441 @@
+use crate::{sprite::SpriteInstance, Dimension, DrawTextureOptions, Error, Rect};
+
+/// The widths of the borders of a nine-slice texture, in pixels of the texture.
+///
+/// The borders split the texture into four corners, which are drawn unscaled, four edges, which
+/// are scaled along one axis, and a center, which is scaled along both.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct NineSliceInsets {
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+impl NineSliceInsets {
+    /// Creates new [NineSliceInsets] from the width of each border, in clockwise order starting
+    /// at the top, like CSS.
+    #[inline]
+    pub const fn new(top: f32, right: f32, bottom: f32, left: f32) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+
+    /// Creates [NineSliceInsets] with the same width for every border.
+    #[inline]
+    pub const fn uniform(inset: f32) -> Self {
+        Self::new(inset, inset, inset, inset)
+    }
+}
+
+/// How [Canvas::draw_nine_slice](crate::Canvas::draw_nine_slice) fills the edges and center of
+/// its destination, like the CSS `border-image-repeat` property.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum NineSliceMode {
+    /// Stretches the edges and center to fill their destinations.
+    #[default]
+    Stretch,
+
+    /// Tiles the edges and center at the scale of the corners, starting from the top-left and
+    /// cutting off the last tiles.
+    Repeat,
+
+    /// Tiles the edges and center, scaled so a whole number of tiles fills their destinations.
+    Round,
+}
+
+/// A nine-slice image with its borders, read from an Android nine-patch (`.9.png`) image.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NinePatch {
+    /// The size of the image, without the one-pixel border holding its markers.
+    pub size: Dimension,
+
+    /// The RGBA8 pixels of the image, without the one-pixel border holding its markers.
+    pub data: Vec<u8>,
+
+    /// The borders around the stretchable area marked on the top and left edges.
+    pub insets: NineSliceInsets,
+
+    /// The borders around the content area marked on the bottom and right edges, if marked.
+    pub padding: Option<NineSliceInsets>,
+}
+
+impl NinePatch {
+    /// Reads a [NinePatch] from the RGBA8 pixels of an Android nine-patch image, i.e. an image
+    /// with an extra one-pixel border where opaque black pixels mark the stretchable area (on the
+    /// top and left) and the content area (on the bottom and right).
+    ///
+    /// Only a single stretchable span is supported on each axis: the span from the first marker
+    /// to the last is used.
+    ///
+    /// # Fails
+    /// - Fails if the data is too big or small for the provided size.
+    /// - Fails if the image is smaller than 3x3, has border pixels which are neither transparent
+    ///   nor opaque black, or doesn't mark its stretchable area.
+    pub fn from_rgba8(size: Dimension, data: &[u8]) -> Result<Self, Error> {
+        if data.len() as u32 != size.area() * 4 {
+            return Err(Error::TextureDataSizeMismatch {
+                expected: size.area() * 4,
+                got: data.len() as u32,
+            });
+        }
+        if size.width < 3 || size.height < 3 {
+            return Err(Error::InvalidNinePatch(
+                "the image is smaller than 3x3".to_owned(),
+            ));
+        }
+
+        let (width, height) = (size.width as usize, size.height as usize);
+        let is_marker = |x: usize, y: usize| -> Result<bool, Error> {
+            let index = (y * width + x) * 4;
+            match data[index..index + 4] {
+                [_, _, _, 0] => Ok(false),
+                [0, 0, 0, 255] => Ok(true),
+                _ => Err(Error::InvalidNinePatch(format!(
+                    "the border pixel at ({x}, {y}) is neither transparent nor opaque black"
+                ))),
+            }
+        };
+
+        // Finds the first and last markers of a border, relative to the inside of the image.
+        let span = |markers: &mut dyn Iterator<Item = Result<bool, Error>>| {
+            let (mut first, mut last) = (None, None);
+            for (index, marker) in markers.enumerate() {
+                if marker? {
+                    first = first.or(Some(index));
+                    last = Some(index);
+                }
+            }
+            Ok::<_, Error>(first.zip(last))
+        };
+        let inner = Dimension::new(size.width - 2, size.height - 2);
+        let insets_from_spans = |horizontal: (usize, usize), vertical: (usize, usize)| {
+            NineSliceInsets::new(
+                vertical.0 as f32,
+                (inner.width as usize - horizontal.1 - 1) as f32,
+                (inner.height as usize - vertical.1 - 1) as f32,
+                horizontal.0 as f32,
+            )
+        };
+
+        let stretch_x = span(&mut (1..width - 1).map(|x| is_marker(x, 0)))?;
+        let stretch_y = span(&mut (1..height - 1).map(|y| is_marker(0, y)))?;
+        let content_x = span(&mut (1..width - 1).map(|x| is_marker(x, height - 1)))?;
+        let content_y = span(&mut (1..height - 1).map(|y| is_marker(width - 1, y)))?;
+
+        let (Some(stretch_x), Some(stretch_y)) = (stretch_x, stretch_y) else {
+            return Err(Error::InvalidNinePatch(
+                "the stretchable area is not marked".to_owned(),
+            ));
+        };
+
+        let data = (1..height - 1)
+            .flat_map(|y| &data[(y * width + 1) * 4..(y * width + width - 1) * 4])
+            .copied()
+            .collect();
+
+        Ok(Self {
+            size: inner,
+            data,
+            insets: insets_from_spans(stretch_x, stretch_y),
+            padding: content_x
+                .zip(content_y)
+                .map(|(content_x, content_y)| insets_from_spans(content_x, content_y)),
+        })
+    }
+}
+
+/// Returns the sprite instances drawing a texture of the provided size as a nine-slice onto the
+/// *dst* rectangle.
+pub(crate) fn nine_slice_instances(
+    texture_size: Dimension,
+    insets: NineSliceInsets,
+    dst: Rect,
+    mode: NineSliceMode,
+) -> Vec<SpriteInstance> {
+    let (width, height) = (texture_size.width as f32, texture_size.height as f32);
+    let insets = NineSliceInsets::new(
+        insets.top.clamp(0.0, height),
+        insets.right.clamp(0.0, width),
+        insets.bottom.clamp(0.0, height),
+        insets.left.clamp(0.0, width),
+    );
+
+    // Like CSS border images, scale every border down by the same factor when they don't fit in
+    // the destination.
+    let fit = |borders: f32, space: f32| {
+        if borders > 0.0 {
+            space.max(0.0) / borders
+        } else {
+            1.0
+        }
+    };
+    let scale = fit(insets.left + insets.right, dst.width)
+        .min(fit(insets.top + insets.bottom, dst.height))
+        .min(1.0);
+
+    let src_columns = [0.0, insets.left, width - insets.right, width];
+    let src_rows = [0.0, insets.top, height - insets.bottom, height];
+    let dst_columns = [
+        dst.x,
+        dst.x + insets.left * scale,
+        dst.x + dst.width - insets.right * scale,
+        dst.x + dst.width,
+    ];
+    let dst_rows = [
+        dst.y,
+        dst.y + insets.top * scale,
+        dst.y + dst.height - insets.bottom * scale,
+        dst.y + dst.height,
+    ];
+
+    let options = DrawTextureOptions::new();
+    let mut instances = Vec::new();
+    for row in 0..3 {
+        let rows = slice_spans(
+            mode,
+            row == 1,
+            (src_rows[row], src_rows[row + 1]),
+            (dst_rows[row], dst_rows[row + 1]),
+            scale,
+        );
+        for column in 0..3 {
+            let columns = slice_spans(
+                mode,
+                column == 1,
+                (src_columns[column], src_columns[column + 1]),
+                (dst_columns[column], dst_columns[column + 1]),
+                scale,
+            );
+            for (src_y, dst_y) in &rows {
+                for (src_x, dst_x) in &columns {
+                    instances.push(SpriteInstance::new(
+                        texture_size,
+                        Rect::new(src_x.0, src_y.0, src_x.1 - src_x.0, src_y.1 - src_y.0),
+                        Rect::new(dst_x.0, dst_y.0, dst_x.1 - dst_x.0, dst_y.1 - dst_y.0),
+                        &options,
+                    ));
+                }
+            }
+        }
+    }
+    instances
+}
+
+/// Splits a slice along one axis into the `(src, dst)` spans of its tiles, or a single stretched
+/// span if it isn't tiled.  Empty slices have no spans.
+fn slice_spans(
+    mode: NineSliceMode,
+    tiled: bool,
+    src: (f32, f32),
+    dst: (f32, f32),
+    scale: f32,
+) -> Vec<((f32, f32), (f32, f32))> {
+    let (src_length, dst_length) = (src.1 - src.0, dst.1 - dst.0);
+    if src_length <= 0.0 || dst_length <= 0.0 {
+        return Vec::new();
+    }
+
+    let tile = src_length * scale;
+    if !tiled || tile <= 0.0 {
+        return vec![(src, dst)];
+    }
+
+    match mode {
+        NineSliceMode::Stretch => vec![(src, dst)],
+        NineSliceMode::Repeat => {
+            let count = (dst_length / tile).ceil() as usize;
+            (0..count)
+                .map(|index| {
+                    let start = dst.0 + index as f32 * tile;
+                    let end = (start + tile).min(dst.1);
+                    ((src.0, src.0 + (end - start) / scale), (start, end))
+                })
+                .collect()
+        }
+        NineSliceMode::Round => {
+            let count = (dst_length / tile).round().max(1.0) as usize;
+            let tile = dst_length / count as f32;
+            (0..count)
+                .map(|index| {
+                    let start = dst.0 + index as f32 * tile;
+                    (src, (start, start + tile))
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLEAR: [u8; 4] = [0, 0, 0, 0];
+    const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+    /// A 5x5 nine-patch whose 3x3 image stretches its middle pixel on both axes, with content
+    /// padding on the right and top.  Image pixels hold their own coordinates.
+    fn nine_patch_pixels() -> Vec<[u8; 4]> {
+        let mut pixels = vec![CLEAR; 25];
+        for y in 1..4 {
+            for x in 1..4 {
+                pixels[y * 5 + x] = [x as u8, y as u8, 128, 255];
+            }
+        }
+        // The stretchable area, on the top and left.
+        pixels[2] = BLACK;
+        pixels[2 * 5] = BLACK;
+        // The content area, on the bottom and right.
+        pixels[4 * 5 + 1] = BLACK;
+        pixels[4 * 5 + 2] = BLACK;
+        pixels[3 * 5 + 4] = BLACK;
+        pixels
+    }
+
+    fn from_pixels(size: Dimension, pixels: &[[u8; 4]]) -> Result<NinePatch, Error> {
+        NinePatch::from_rgba8(size, &pixels.concat())
+    }
+
+    #[test]
+    fn reads_nine_patch_markers() {
+        let nine_patch = from_pixels(Dimension::new(5, 5), &nine_patch_pixels()).unwrap();
+
+        assert_eq!(nine_patch.size, Dimension::new(3, 3));
+        assert_eq!(nine_patch.insets, NineSliceInsets::uniform(1.0));
+        assert_eq!(
+            nine_patch.padding,
+            Some(NineSliceInsets::new(2.0, 1.0, 0.0, 0.0))
+        );
+
+        let expected: Vec<u8> = (1..4)
+            .flat_map(|y| (1..4).flat_map(move |x| [x, y, 128, 255]))
+            .collect();
+        assert_eq!(nine_patch.data, expected);
+    }
+
+    #[test]
+    fn content_area_is_optional() {
+        let mut pixels = nine_patch_pixels();
+        pixels[4 * 5 + 1] = CLEAR;
+        pixels[4 * 5 + 2] = CLEAR;
+        pixels[3 * 5 + 4] = CLEAR;
+
+        let nine_patch = from_pixels(Dimension::new(5, 5), &pixels).unwrap();
+        assert_eq!(nine_patch.insets, NineSliceInsets::uniform(1.0));
+        assert_eq!(nine_patch.padding, None);
+    }
+
+    #[test]
+    fn rejects_invalid_border_pixels() {
+        // Opaque, but not black.
+        let mut pixels = nine_patch_pixels();
+        pixels[3] = [255, 0, 0, 255];
+        assert!(matches!(
+            from_pixels(Dimension::new(5, 5), &pixels),
+            Err(Error::InvalidNinePatch(_))
+        ));
+
+        // Black, but translucent.
+        let mut pixels = nine_patch_pixels();
+        pixels[4 * 5 + 3] = [0, 0, 0, 128];
+        assert!(matches!(
+            from_pixels(Dimension::new(5, 5), &pixels),
+            Err(Error::InvalidNinePatch(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_unmarked_and_small_images() {
+        let mut pixels = nine_patch_pixels();
+        pixels[2] = CLEAR;
+        assert!(matches!(
+            from_pixels(Dimension::new(5, 5), &pixels),
+            Err(Error::InvalidNinePatch(_))
+        ));
+
+        assert!(matches!(
+            from_pixels(Dimension::new(2, 5), &[BLACK; 10]),
+            Err(Error::InvalidNinePatch(_))
+        ));
+        assert!(matches!(
+            from_pixels(Dimension::new(5, 5), &[BLACK; 24]),
+            Err(Error::TextureDataSizeMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn stretched_slices_have_one_span() {
+        for mode in [
+            NineSliceMode::Stretch,
+            NineSliceMode::Repeat,
+            NineSliceMode::Round,
+        ] {
+            assert_eq!(
+                slice_spans(mode, false, (2.0, 6.0), (0.0, 10.0), 1.0),
+                vec![((2.0, 6.0), (0.0, 10.0))]
+            );
+        }
+        assert_eq!(
+            slice_spans(NineSliceMode::Stretch, true, (2.0, 6.0), (0.0, 10.0), 1.0),
+            vec![((2.0, 6.0), (0.0, 10.0))]
+        );
+        assert!(slice_spans(NineSliceMode::Repeat, true, (2.0, 2.0), (0.0, 10.0), 1.0).is_empty());
+        assert!(slice_spans(NineSliceMode::Repeat, true, (2.0, 6.0), (5.0, 5.0), 1.0).is_empty());
+    }
+
+    #[test]
+    fn repeat_clips_the_last_tile() {
+        assert_eq!(
+            slice_spans(NineSliceMode::Repeat, true, (2.0, 6.0), (0.0, 10.0), 1.0),
+            vec![
+                ((2.0, 6.0), (0.0, 4.0)),
+                ((2.0, 6.0), (4.0, 8.0)),
+                ((2.0, 4.0), (8.0, 10.0)),
+            ]
+        );
+
+        // Tiles shrink with the corners, and the clipped source span grows back by the scale.
+        assert_eq!(
+            slice_spans(NineSliceMode::Repeat, true, (2.0, 6.0), (1.0, 6.0), 0.5),
+            vec![
+                ((2.0, 6.0), (1.0, 3.0)),
+                ((2.0, 6.0), (3.0, 5.0)),
+                ((2.0, 4.0), (5.0, 6.0)),
+            ]
+        );
+
+        // A destination of whole tiles isn't clipped.
+        assert_eq!(
+            slice_spans(NineSliceMode::Repeat, true, (0.0, 4.0), (0.0, 8.0), 1.0).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn round_fits_whole_tiles() {
+        // 9 / 4 rounds down to 2 tiles, stretched to 4.5.
+        assert_eq!(
+            slice_spans(NineSliceMode::Round, true, (2.0, 6.0), (0.0, 9.0), 1.0),
+            vec![((2.0, 6.0), (0.0, 4.5)), ((2.0, 6.0), (4.5, 9.0))]
+        );
+
+        // 11 / 4 rounds up to 3 tiles, squashed to 11 / 3.
+        let spans = slice_spans(NineSliceMode::Round, true, (2.0, 6.0), (0.0, 11.0), 1.0);
+        assert_eq!(spans.len(), 3);
+        for (index, (src, dst)) in spans.iter().enumerate() {
+            assert_eq!(*src, (2.0, 6.0));
+            assert!((dst.0 - index as f32 * 11.0 / 3.0).abs() < 1e-5);
+            assert!((dst.1 - dst.0 - 11.0 / 3.0).abs() < 1e-5);
+        }
+
+        // Destinations shorter than half a tile still get one tile.
+        assert_eq!(
+            slice_spans(NineSliceMode::Round, true, (2.0, 6.0), (0.0, 1.0), 1.0),
+            vec![((2.0, 6.0), (0.0, 1.0))]
+        );
+    }
+}
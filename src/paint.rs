@@ -1,5 +1,199 @@
+use crate::{Color, Error, Vec2};
+
 /// Instructions on how to fill a shape.
+#[derive(Clone, Debug, PartialEq)]
 pub enum Paint {
     /// Fills the shape with the color of the [Vertices](crate::Vertex).
+    ///
+    /// Shapes without vertex colors, like text, are filled with white.
     Fill,
+
+    /// Fills the shape with a single color.
+    Solid(Color),
+
+    /// Fills the shape with a [LinearGradient].
+    LinearGradient(LinearGradient),
+
+    /// Fills the shape with a [RadialGradient].
+    RadialGradient(RadialGradient),
+
+    /// Fills the shape with a [SweepGradient].
+    SweepGradient(SweepGradient),
+}
+
+impl Paint {
+    /// Creates a [Paint::LinearGradient].
+    #[inline]
+    pub fn linear_gradient(start: Vec2, end: Vec2, stops: Vec<GradientStop>) -> Self {
+        Self::LinearGradient(LinearGradient { start, end, stops })
+    }
+
+    /// Creates a [Paint::RadialGradient].
+    #[inline]
+    pub fn radial_gradient(center: Vec2, radius: f32, stops: Vec<GradientStop>) -> Self {
+        Self::RadialGradient(RadialGradient {
+            center,
+            radius,
+            stops,
+        })
+    }
+
+    /// Creates a [Paint::SweepGradient].
+    #[inline]
+    pub fn sweep_gradient(
+        center: Vec2,
+        start_angle: f32,
+        end_angle: f32,
+        stops: Vec<GradientStop>,
+    ) -> Self {
+        Self::SweepGradient(SweepGradient {
+            center,
+            start_angle,
+            end_angle,
+            stops,
+        })
+    }
+}
+
+impl From<Color> for Paint {
+    #[inline]
+    fn from(color: Color) -> Self {
+        Self::Solid(color)
+    }
+}
+
+/// A color at some point along a gradient.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct GradientStop {
+    /// Where the color is along the gradient, from `0.0` at its start to `1.0` at its end.
+    pub offset: f32,
+    pub color: Color,
+}
+
+impl GradientStop {
+    /// Creates a [GradientStop] from its offset and color.
+    #[inline]
+    pub const fn new(offset: f32, color: Color) -> Self {
+        Self { offset, color }
+    }
+}
+
+/// A gradient along the line from *start* to *end*, in pixels.
+///
+/// Colors are interpolated with premultiplied alpha, like in CSS.  Pixels before the first stop
+/// or after the last one take the color of that stop.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LinearGradient {
+    pub start: Vec2,
+    pub end: Vec2,
+
+    /// The colors of the gradient, sorted by offset.  At most [LinearGradient::MAX_STOPS].
+    pub stops: Vec<GradientStop>,
+}
+
+impl LinearGradient {
+    /// The largest number of [GradientStops](GradientStop) a gradient can have.
+    pub const MAX_STOPS: usize = 16;
+}
+
+/// A gradient going outwards from *center* to the circle of radius *radius*, in pixels.
+///
+/// Colors are interpolated like in a [LinearGradient].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RadialGradient {
+    pub center: Vec2,
+    pub radius: f32,
+
+    /// The colors of the gradient, sorted by offset.  At most [LinearGradient::MAX_STOPS].
+    pub stops: Vec<GradientStop>,
+}
+
+/// A gradient sweeping around *center* from *start_angle* to *end_angle*, like the CSS
+/// `conic-gradient` function.
+///
+/// Angles are in radians, clockwise from the positive x axis as the y axis points down.  The
+/// gradient sweeps counter-clockwise if *end_angle* is smaller than *start_angle*.  Colors are
+/// interpolated like in a [LinearGradient].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SweepGradient {
+    pub center: Vec2,
+    pub start_angle: f32,
+    pub end_angle: f32,
+
+    /// The colors of the gradient, sorted by offset.  At most [LinearGradient::MAX_STOPS].
+    pub stops: Vec<GradientStop>,
+}
+
+/// The uniform shaders read a [Paint] from, see `shaders/paint.wgsl`.
+#[derive(bytemuck::Zeroable, bytemuck::Pod, Clone, Copy, Debug)]
+#[repr(C)]
+pub(crate) struct PaintUniform {
+    /// `0` for [Paint::Fill], `1` for [Paint::Solid], `2` for [Paint::LinearGradient], `3` for
+    /// [Paint::RadialGradient] and `4` for [Paint::SweepGradient].
+    kind: u32,
+    stop_count: u32,
+    _padding: [u32; 2],
+    color: Color,
+
+    /// `[start_x, start_y, end_x, end_y]` for linear gradients, `[center_x, center_y, radius, 0]`
+    /// for radial ones and `[center_x, center_y, start_angle, end_angle]` for sweep ones.
+    geometry: [f32; 4],
+    offsets: [[f32; 4]; LinearGradient::MAX_STOPS / 4],
+    colors: [Color; LinearGradient::MAX_STOPS],
+}
+
+impl PaintUniform {
+    /// Creates the uniform for the provided [Paint].
+    ///
+    /// # Fails
+    /// Fails if a gradient has more than [LinearGradient::MAX_STOPS] stops.
+    pub fn new(paint: &Paint) -> Result<Self, Error> {
+        let mut uniform: Self = bytemuck::Zeroable::zeroed();
+
+        let stops = match paint {
+            Paint::Fill => return Ok(uniform),
+            Paint::Solid(color) => {
+                uniform.kind = 1;
+                uniform.color = *color;
+                return Ok(uniform);
+            }
+            Paint::LinearGradient(gradient) => {
+                uniform.kind = 2;
+                uniform.geometry = [
+                    gradient.start.x,
+                    gradient.start.y,
+                    gradient.end.x,
+                    gradient.end.y,
+                ];
+                &gradient.stops
+            }
+            Paint::RadialGradient(gradient) => {
+                uniform.kind = 3;
+                uniform.geometry = [gradient.center.x, gradient.center.y, gradient.radius, 0.0];
+                &gradient.stops
+            }
+            Paint::SweepGradient(gradient) => {
+                uniform.kind = 4;
+                uniform.geometry = [
+                    gradient.center.x,
+                    gradient.center.y,
+                    gradient.start_angle,
+                    gradient.end_angle,
+                ];
+                &gradient.stops
+            }
+        };
+
+        if stops.len() > LinearGradient::MAX_STOPS {
+            return Err(Error::TooManyGradientStops(stops.len()));
+        }
+
+        uniform.stop_count = stops.len() as u32;
+        for (index, stop) in stops.iter().enumerate() {
+            uniform.offsets[index / 4][index % 4] = stop.offset;
+            uniform.colors[index] = stop.color;
+        }
+
+        Ok(uniform)
+    }
 }
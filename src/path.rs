@@ -0,0 +1,259 @@
+use crate::{Rect, Vec2};
+
+/// The maximum distance, in pixels, between a curve and the lines it is flattened to.
+pub(crate) const FLATTEN_TOLERANCE: f32 = 0.25;
+
+/// Decides which areas enclosed by a [Path] count as its inside.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum FillRule {
+    /// A point is inside if the path winds around it a non-zero number of times.
+    #[default]
+    NonZero,
+
+    /// A point is inside if a ray from it crosses the path an odd number of times.
+    EvenOdd,
+}
+
+/// A single command of a [Path].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PathSegment {
+    /// Starts a new contour at the provided point.
+    MoveTo(Vec2),
+
+    /// A straight line to the provided point.
+    LineTo(Vec2),
+
+    /// A quadratic Bézier curve with a control point and an end point.
+    QuadTo(Vec2, Vec2),
+
+    /// A cubic Bézier curve with two control points and an end point.
+    CubicTo(Vec2, Vec2, Vec2),
+
+    /// Closes the current contour with a straight line back to its start.
+    Close,
+}
+
+/// A shape made of straight lines and Bézier curves, in pixels.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Path {
+    segments: Vec<PathSegment>,
+}
+
+impl Path {
+    /// Creates an empty [Path].
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a closed [Path] tracing the outline of a [Rect].
+    pub fn from_rect(rect: Rect) -> Self {
+        let (min, max) = (rect.min(), rect.max());
+        let mut path = Self::new();
+        path.move_to(min)
+            .line_to(Vec2::new(max.x, min.y))
+            .line_to(max)
+            .line_to(Vec2::new(min.x, max.y))
+            .close();
+        path
+    }
+
+    /// Creates a closed [Path] tracing a circle, approximated with cubic Bézier curves.
+    pub fn circle(center: Vec2, radius: f32) -> Self {
+        // The distance of the control points from the ends of a quarter circle.
+        const KAPPA: f32 = 0.552_284_8;
+        let k = radius * KAPPA;
+
+        let (cx, cy, r) = (center.x, center.y, radius);
+        let mut path = Self::new();
+        path.move_to(Vec2::new(cx + r, cy))
+            .cubic_to(
+                Vec2::new(cx + r, cy + k),
+                Vec2::new(cx + k, cy + r),
+                Vec2::new(cx, cy + r),
+            )
+            .cubic_to(
+                Vec2::new(cx - k, cy + r),
+                Vec2::new(cx - r, cy + k),
+                Vec2::new(cx - r, cy),
+            )
+            .cubic_to(
+                Vec2::new(cx - r, cy - k),
+                Vec2::new(cx - k, cy - r),
+                Vec2::new(cx, cy - r),
+            )
+            .cubic_to(
+                Vec2::new(cx + k, cy - r),
+                Vec2::new(cx + r, cy - k),
+                Vec2::new(cx + r, cy),
+            )
+            .close();
+        path
+    }
+
+    /// Starts a new contour at the provided point.
+    #[inline]
+    pub fn move_to(&mut self, point: Vec2) -> &mut Self {
+        self.segments.push(PathSegment::MoveTo(point));
+        self
+    }
+
+    /// Adds a straight line to the provided point.
+    #[inline]
+    pub fn line_to(&mut self, point: Vec2) -> &mut Self {
+        self.segments.push(PathSegment::LineTo(point));
+        self
+    }
+
+    /// Adds a quadratic Bézier curve.
+    #[inline]
+    pub fn quad_to(&mut self, control: Vec2, point: Vec2) -> &mut Self {
+        self.segments.push(PathSegment::QuadTo(control, point));
+        self
+    }
+
+    /// Adds a cubic Bézier curve.
+    #[inline]
+    pub fn cubic_to(&mut self, control1: Vec2, control2: Vec2, point: Vec2) -> &mut Self {
+        self.segments
+            .push(PathSegment::CubicTo(control1, control2, point));
+        self
+    }
+
+    /// Closes the current contour.
+    #[inline]
+    pub fn close(&mut self) -> &mut Self {
+        self.segments.push(PathSegment::Close);
+        self
+    }
+
+    /// Returns the [PathSegments](PathSegment) of this [Path].
+    #[inline]
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+
+    /// Returns `true` if this [Path] has no segments.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Returns a rectangle containing every point of this [Path], including control points.
+    pub fn bounds(&self) -> Rect {
+        let mut points = self.segments.iter().flat_map(|segment| match *segment {
+            PathSegment::MoveTo(p) | PathSegment::LineTo(p) => vec![p],
+            PathSegment::QuadTo(c, p) => vec![c, p],
+            PathSegment::CubicTo(c1, c2, p) => vec![c1, c2, p],
+            PathSegment::Close => vec![],
+        });
+
+        let Some(first) = points.next() else {
+            return Rect::default();
+        };
+
+        let (min, max) = points.fold((first, first), |(min, max), p| {
+            (
+                Vec2::new(min.x.min(p.x), min.y.min(p.y)),
+                Vec2::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        });
+        Rect::from_min_max(min, max)
+    }
+
+    /// Approximates this [Path] with straight lines, returning the points of each contour.
+    ///
+    /// Curves are split so they are never more than *tolerance* pixels away from the lines.
+    /// Contours are implicitly closed.
+    pub fn flatten(&self, tolerance: f32) -> Vec<Vec<Vec2>> {
+        let mut contours = Vec::new();
+        let mut contour: Vec<Vec2> = Vec::new();
+        let mut current = Vec2::default();
+
+        for segment in &self.segments {
+            match *segment {
+                PathSegment::MoveTo(p) => {
+                    if contour.len() > 1 {
+                        contours.push(std::mem::take(&mut contour));
+                    }
+                    contour.clear();
+                    contour.push(p);
+                    current = p;
+                }
+                PathSegment::LineTo(p) => {
+                    if contour.is_empty() {
+                        contour.push(current);
+                    }
+                    contour.push(p);
+                    current = p;
+                }
+                PathSegment::QuadTo(c, p) => {
+                    if contour.is_empty() {
+                        contour.push(current);
+                    }
+                    let steps = curve_steps((current - c * 2.0 + p).length(), 0.25, tolerance);
+                    contour.extend((1..=steps).map(|i| {
+                        let t = i as f32 / steps as f32;
+                        current.lerp(c, t).lerp(c.lerp(p, t), t)
+                    }));
+                    current = p;
+                }
+                PathSegment::CubicTo(c1, c2, p) => {
+                    if contour.is_empty() {
+                        contour.push(current);
+                    }
+                    let dd = (current - c1 * 2.0 + c2)
+                        .length()
+                        .max((c1 - c2 * 2.0 + p).length());
+                    let steps = curve_steps(dd, 0.75, tolerance);
+                    contour.extend((1..=steps).map(|i| {
+                        let t = i as f32 / steps as f32;
+                        let (a, b, c) = (current.lerp(c1, t), c1.lerp(c2, t), c2.lerp(p, t));
+                        a.lerp(b, t).lerp(b.lerp(c, t), t)
+                    }));
+                    current = p;
+                }
+                PathSegment::Close => {
+                    if let Some(&start) = contour.first() {
+                        current = start;
+                    }
+                    if contour.len() > 1 {
+                        contours.push(std::mem::take(&mut contour));
+                    }
+                    contour.clear();
+                }
+            }
+        }
+
+        if contour.len() > 1 {
+            contours.push(contour);
+        }
+
+        contours
+    }
+
+    /// Triangulates this [Path] as triangle fans, one for each contour.
+    ///
+    /// The triangles overlap, so they only describe the inside of the path when the winding of
+    /// each pixel is counted (see [FillRule]).
+    pub(crate) fn fan_triangles(&self, tolerance: f32) -> Vec<Vec2> {
+        let mut triangles = Vec::new();
+
+        for contour in self.flatten(tolerance) {
+            let pivot = contour[0];
+            for edge in contour[1..].windows(2) {
+                triangles.extend_from_slice(&[pivot, edge[0], edge[1]]);
+            }
+        }
+
+        triangles
+    }
+}
+
+/// Returns the number of lines needed to flatten a curve.
+///
+/// *deviation* is the magnitude of the curve's second difference and *factor* scales it to the
+/// error of a single line for the kind of curve.
+fn curve_steps(deviation: f32, factor: f32, tolerance: f32) -> u32 {
+    ((deviation * factor / tolerance.max(1e-3)).sqrt().ceil() as u32).clamp(1, 256)
+}
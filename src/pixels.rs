@@ -1,4 +1,4 @@
-use crate::Dimension;
+use crate::{linear_to_srgb, Color, Dimension, TextureFormat};
 
 /// The pixels of a texture.
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -6,6 +6,9 @@ pub struct Pixels {
     /// The size of the texture.
     size: Dimension,
 
+    /// The format of the pixels.
+    format: TextureFormat,
+
     /// The pixels of the texture.
     buffer: Vec<u8>,
 }
@@ -13,8 +16,84 @@ pub struct Pixels {
 impl Pixels {
     /// Creates a [Pixels] instance from its raw parts.
     #[inline]
-    pub fn from_raw_parts(size: Dimension, buffer: Vec<u8>) -> Self {
-        Self { size, buffer }
+    pub fn from_raw_parts(size: Dimension, format: TextureFormat, buffer: Vec<u8>) -> Self {
+        Self {
+            size,
+            format,
+            buffer,
+        }
+    }
+
+    /// Returns the size of the texture these [Pixels] were read from.
+    #[inline]
+    pub fn size(&self) -> Dimension {
+        self.size
+    }
+
+    /// Returns the [TextureFormat] of these [Pixels].
+    #[inline]
+    pub fn format(&self) -> TextureFormat {
+        self.format
+    }
+
+    /// Returns the [`wgpu::TextureFormat`] of these [Pixels].
+    #[inline]
+    pub fn wgpu_format(&self) -> wgpu::TextureFormat {
+        self.format.to_wgpu_format()
+    }
+
+    /// Returns the color of the pixel at (*x*, *y*), or [None] if it's out of bounds.
+    ///
+    /// The color has sRGB-encoded channels, like [Color::from_rgba8]: formats storing linear data
+    /// are converted to sRGB, and `R8Unorm` pixels become opaque grays.
+    pub fn color_at(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.size.width || y >= self.size.height {
+            return None;
+        }
+
+        let bytes_per_pixel = self.format.bytes_per_pixel() as usize;
+        let index = (y as usize * self.size.width as usize + x as usize) * bytes_per_pixel;
+        let pixel = &self.buffer[index..index + bytes_per_pixel];
+
+        let linear = |red: f32, green: f32, blue: f32, alpha: f32| {
+            Color::new(
+                linear_to_srgb(red),
+                linear_to_srgb(green),
+                linear_to_srgb(blue),
+                alpha,
+            )
+        };
+        let unorm = |channel: u8| channel as f32 / 255.0;
+        Some(match self.format {
+            TextureFormat::Rgba8UnormSrgb => {
+                Color::from_rgba8(pixel[0], pixel[1], pixel[2], pixel[3])
+            }
+            TextureFormat::Bgra8UnormSrgb => {
+                Color::from_rgba8(pixel[2], pixel[1], pixel[0], pixel[3])
+            }
+            TextureFormat::Rgba8Unorm => linear(
+                unorm(pixel[0]),
+                unorm(pixel[1]),
+                unorm(pixel[2]),
+                unorm(pixel[3]),
+            ),
+            TextureFormat::Bgra8Unorm => linear(
+                unorm(pixel[2]),
+                unorm(pixel[1]),
+                unorm(pixel[0]),
+                unorm(pixel[3]),
+            ),
+            TextureFormat::Rgba16Float => {
+                let channel = |index: usize| {
+                    f16_to_f32(u16::from_le_bytes([pixel[index * 2], pixel[index * 2 + 1]]))
+                };
+                linear(channel(0), channel(1), channel(2), channel(3))
+            }
+            TextureFormat::R8Unorm => {
+                let gray = unorm(pixel[0]);
+                Color::rgb(gray, gray, gray)
+            }
+        })
     }
 
     /// Creates a [`Vec<u8>`] from this [Pixels] buffer.
@@ -43,3 +122,17 @@ impl std::ops::DerefMut for Pixels {
         &mut self.buffer
     }
 }
+
+/// Converts the bits of a 16-bit float to a 32-bit float.
+pub(crate) fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = (bits >> 10) & 0x1f;
+    let mantissa = (bits & 0x3ff) as f32;
+
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent as i32 - 15),
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{Dimension, Vec2};
+
+/// An axis-aligned rectangle, in pixels.
+///
+/// The origin of the coordinate space is the top-left corner of a [Texture](crate::Texture), with
+/// *y* pointing down.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct Rect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rect {
+    /// Creates a new [Rect] from its top-left corner and its size.
+    #[inline]
+    pub const fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a [Rect] spanning the provided *min* and *max* corners.
+    #[inline]
+    pub fn from_min_max(min: Vec2, max: Vec2) -> Self {
+        Self::new(min.x, min.y, max.x - min.x, max.y - min.y)
+    }
+
+    /// The top-left corner of this [Rect].
+    #[inline]
+    pub fn min(&self) -> Vec2 {
+        Vec2::new(self.x, self.y)
+    }
+
+    /// The bottom-right corner of this [Rect].
+    #[inline]
+    pub fn max(&self) -> Vec2 {
+        Vec2::new(self.x + self.width, self.y + self.height)
+    }
+
+    /// The center of this [Rect].
+    #[inline]
+    pub fn center(&self) -> Vec2 {
+        Vec2::new(self.x + self.width * 0.5, self.y + self.height * 0.5)
+    }
+
+    /// Returns the overlapping area of this [Rect] and *other*.
+    ///
+    /// The result is empty if the rectangles don't overlap.
+    pub fn intersection(&self, other: &Rect) -> Self {
+        let (min, max) = (self.min(), self.max());
+        let (other_min, other_max) = (other.min(), other.max());
+
+        let min = Vec2::new(min.x.max(other_min.x), min.y.max(other_min.y));
+        let max = Vec2::new(max.x.min(other_max.x), max.y.min(other_max.y));
+
+        Self::from_min_max(min, Vec2::new(max.x.max(min.x), max.y.max(min.y)))
+    }
+
+    /// Returns `true` if this [Rect] and *other* overlap.
+    #[inline]
+    pub fn intersects(&self, other: &Rect) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Returns `true` if this [Rect] has no area.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.width <= 0.0 || self.height <= 0.0
+    }
+}
+
+/// An axis-aligned rectangle of whole pixels, like a region of a [Texture](crate::Texture).
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct PixelRect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl PixelRect {
+    /// Creates a new [PixelRect] from its top-left corner and its size.
+    #[inline]
+    pub const fn new(x: u32, y: u32, width: u32, height: u32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Creates a [PixelRect] covering the whole of an object of the provided size.
+    #[inline]
+    pub const fn from_size(size: Dimension) -> Self {
+        Self::new(0, 0, size.width, size.height)
+    }
+
+    /// Returns the size of this [PixelRect].
+    #[inline]
+    pub const fn size(&self) -> Dimension {
+        Dimension::new(self.width, self.height)
+    }
+
+    /// Returns `true` if this [PixelRect] lies within an object of the provided size.
+    #[inline]
+    pub const fn fits_within(&self, size: Dimension) -> bool {
+        self.x as u64 + self.width as u64 <= size.width as u64
+            && self.y as u64 + self.height as u64 <= size.height as u64
+    }
+
+    /// Returns `true` if this [PixelRect] has no area.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Converts this [PixelRect] to a [Rect].
+    #[inline]
+    pub fn to_rect(&self) -> Rect {
+        Rect::new(
+            self.x as f32,
+            self.y as f32,
+            self.width as f32,
+            self.height as f32,
+        )
+    }
+
+    /// Returns the top-left corner of this [PixelRect] as a [`wgpu::Origin3d`].
+    #[inline]
+    pub(crate) const fn to_origin_3d(self) -> wgpu::Origin3d {
+        wgpu::Origin3d {
+            x: self.x,
+            y: self.y,
+            z: 0,
+        }
+    }
+}
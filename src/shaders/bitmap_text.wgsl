@@ -0,0 +1,62 @@
+// Shaders for drawing glyphs from a page of a bitmap font.  Prefixed with `paint.wgsl`.
+
+struct Viewport {
+    size: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> paint: Paint;
+@group(0) @binding(1)
+var<uniform> viewport: Viewport;
+@group(0) @binding(2)
+var page: texture_2d<f32>;
+
+struct InstanceInput {
+    @location(0) origin: vec2<f32>,
+    @location(1) x_axis: vec2<f32>,
+    @location(2) y_axis: vec2<f32>,
+    @location(3) page_bounds: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) page_position: vec2<f32>,
+};
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    instance: InstanceInput,
+) -> VertexOutput {
+    // Two triangles covering the glyph.
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+    let position = instance.origin + instance.x_axis * corner.x + instance.y_axis * corner.y;
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(
+        position.x / viewport.size.x * 2.0 - 1.0,
+        1.0 - position.y / viewport.size.y * 2.0,
+        0.0,
+        1.0,
+    );
+    out.page_position = mix(instance.page_bounds.xy, instance.page_bounds.zw, corner);
+    return out;
+}
+
+// Fragment shader
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Sample the nearest texel, so scaled glyphs keep sharp pixels.
+    let texel = textureLoad(page, vec2<i32>(floor(in.page_position)), 0);
+    let color = paint_color(in.clip_position.xy, vec4<f32>(1.0));
+    return premultiply(color * texel) * clip_coverage(in.clip_position.xy);
+}
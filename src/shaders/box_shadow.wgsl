@@ -0,0 +1,159 @@
+// Shaders for box shadows, blurred analytically.
+//
+// Based on "Fast Rounded Rectangle Shadows" by Evan Wallace: the blur is integrated in closed
+// form along x, using an approximation of the error function, and sampled along y.
+
+struct Viewport {
+    size: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> viewport: Viewport;
+
+struct InstanceInput {
+    @location(0) bounds: vec4<f32>,
+    @location(1) box_rect: vec4<f32>,
+    @location(2) box_radii: vec4<f32>,
+    @location(3) shadow_rect: vec4<f32>,
+    @location(4) shadow_radii: vec4<f32>,
+    @location(5) color: vec4<f32>,
+    @location(6) sigma: f32,
+    @location(7) inset: u32,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) position: vec2<f32>,
+    @location(1) box_rect: vec4<f32>,
+    @location(2) box_radii: vec4<f32>,
+    @location(3) shadow_rect: vec4<f32>,
+    @location(4) shadow_radii: vec4<f32>,
+    @location(5) color: vec4<f32>,
+    @location(6) sigma: f32,
+    @location(7) @interpolate(flat) inset: u32,
+};
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    instance: InstanceInput,
+) -> VertexOutput {
+    // Two triangles covering the bounds of the shadow.
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let position = mix(instance.bounds.xy, instance.bounds.zw, corners[vertex_index]);
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(
+        position.x / viewport.size.x * 2.0 - 1.0,
+        1.0 - position.y / viewport.size.y * 2.0,
+        0.0,
+        1.0,
+    );
+    out.position = position;
+    out.box_rect = instance.box_rect;
+    out.box_radii = instance.box_radii;
+    out.shadow_rect = instance.shadow_rect;
+    out.shadow_radii = instance.shadow_radii;
+    out.color = instance.color;
+    out.sigma = instance.sigma;
+    out.inset = instance.inset;
+    return out;
+}
+
+// Fragment shader
+
+// Returns the radius of the corner of a box nearest to `p`, relative to the box's center.
+fn corner_radius(p: vec2<f32>, radii: vec4<f32>) -> f32 {
+    if p.x < 0.0 {
+        return select(radii.w, radii.x, p.y < 0.0);
+    }
+    return select(radii.z, radii.y, p.y < 0.0);
+}
+
+// The signed distance from `p` to a box centered on the origin.
+fn sd_rounded_box(p: vec2<f32>, half_size: vec2<f32>, radii: vec4<f32>) -> f32 {
+    let radius = corner_radius(p, radii);
+    let q = abs(p) - half_size + radius;
+    return min(max(q.x, q.y), 0.0) + length(max(q, vec2<f32>(0.0))) - radius;
+}
+
+// How much of the pixel at `p` a rounded box covers, anti-aliased.
+fn box_coverage(p: vec2<f32>, rect: vec4<f32>, radii: vec4<f32>) -> f32 {
+    let center = (rect.xy + rect.zw) * 0.5;
+    let distance = sd_rounded_box(p - center, (rect.zw - rect.xy) * 0.5, radii);
+    return clamp(0.5 - distance, 0.0, 1.0);
+}
+
+fn gaussian(x: f32, sigma: f32) -> f32 {
+    return exp(-(x * x) / (2.0 * sigma * sigma)) / (sqrt(2.0 * 3.14159265) * sigma);
+}
+
+// An approximation of the error function, with a maximum error of 5e-4.
+fn erf(x: vec2<f32>) -> vec2<f32> {
+    let s = sign(x);
+    let a = abs(x);
+    var r = 1.0 + (0.278393 + (0.230389 + 0.078108 * (a * a)) * a) * a;
+    r *= r;
+    return s - s / (r * r);
+}
+
+// The blurred coverage of one horizontal line through a rounded box, at height `y`.
+fn shadow_x(x: f32, y: f32, sigma: f32, corner: f32, half_size: vec2<f32>) -> f32 {
+    let delta = min(half_size.y - corner - abs(y), 0.0);
+    let curved = half_size.x - corner + sqrt(max(0.0, corner * corner - delta * delta));
+    let integral = 0.5 + 0.5 * erf((x + vec2<f32>(-curved, curved)) * (sqrt(0.5) / sigma));
+    return integral.y - integral.x;
+}
+
+// The coverage of a rounded box blurred with a standard deviation of `sigma`.
+fn shadow_coverage(p: vec2<f32>, rect: vec4<f32>, radii: vec4<f32>, sigma: f32) -> f32 {
+    if sigma < 0.25 {
+        return box_coverage(p, rect, radii);
+    }
+
+    let center = (rect.xy + rect.zw) * 0.5;
+    let half_size = max((rect.zw - rect.xy) * 0.5, vec2<f32>(0.0));
+    let point = p - center;
+    let corner = min(corner_radius(point, radii), min(half_size.x, half_size.y));
+
+    // Only the part of the box within three standard deviations of the point contributes.
+    let low = point.y - half_size.y;
+    let high = point.y + half_size.y;
+    let start = clamp(-3.0 * sigma, low, high);
+    let end = clamp(3.0 * sigma, low, high);
+
+    let step = (end - start) / 4.0;
+    var y = start + step * 0.5;
+    var value = 0.0;
+    for (var i = 0; i < 4; i++) {
+        value += shadow_x(point.x, point.y - y, sigma, corner, half_size) * gaussian(y, sigma) * step;
+        y += step;
+    }
+    return value;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let box = box_coverage(in.position, in.box_rect, in.box_radii);
+
+    var coverage: f32;
+    if in.inset == 1u {
+        // Inset shadows are everywhere inside the box except for the blurred hole.
+        let hole = shadow_coverage(in.position, in.shadow_rect, in.shadow_radii, in.sigma);
+        coverage = (1.0 - hole) * box;
+    } else {
+        // Outer shadows are never drawn beneath the box, like in CSS.
+        let shadow = shadow_coverage(in.position, in.shadow_rect, in.shadow_radii, in.sigma);
+        coverage = shadow * (1.0 - box);
+    }
+
+    let color = vec4<f32>(in.color.rgb * in.color.a, in.color.a);
+    return color * coverage * clip_coverage(in.clip_position.xy);
+}
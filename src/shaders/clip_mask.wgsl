@@ -0,0 +1,18 @@
+// Code shared by the shaders drawing to a canvas with clip paths.
+
+// The coverage of the clip paths of the canvas, one texel per pixel.
+@group(1) @binding(0)
+var clip_mask: texture_2d<f32>;
+
+// Returns the coverage of the clip paths at the pixel containing *position*, in pixels.
+//
+// Pixels outside of the clip paths are discarded rather than drawn with no coverage, so they are
+// left untouched whatever the blend mode.
+fn clip_coverage(position: vec2<f32>) -> f32 {
+    let coverage = textureLoad(clip_mask, vec2<i32>(position), 0).r;
+    if coverage <= 0.0 {
+        discard;
+    }
+    return coverage;
+}
+
@@ -0,0 +1,36 @@
+// Shaders for rendering clip paths into a clip mask, through the stencil buffer.
+
+struct Viewport {
+    size: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> viewport: Viewport;
+
+// The number of samples per pixel, the length of `CLIP_MASK_SAMPLES` in `canvas/clip.rs`.
+const SAMPLE_COUNT: f32 = 16.0;
+
+@vertex
+fn vs_main(
+    @location(0) position: vec2<f32>,
+    @location(1) sample: vec2<f32>,
+) -> @builtin(position) vec4<f32> {
+    // Moving the path by the opposite of the sample position makes the pixel centers land on
+    // the sample.
+    let moved = position - sample;
+    return vec4<f32>(
+        moved.x / viewport.size.x * 2.0 - 1.0,
+        1.0 - moved.y / viewport.size.y * 2.0,
+        0.0,
+        1.0,
+    );
+}
+
+// Fragment shader
+
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    // Color writes are disabled except when covering, where each sample adds its share of the
+    // coverage.
+    return vec4<f32>(1.0 / SAMPLE_COUNT, 0.0, 0.0, 1.0);
+}
@@ -0,0 +1,68 @@
+// Shaders for drawing color glyphs, like emoji, from the color page of a glyph atlas.
+
+struct ColorText {
+    // Maps the pixels of the text to the pixels of the canvas.
+    transform: mat3x3<f32>,
+    viewport_size: vec2<f32>,
+
+    // Multiplies the colors of the glyphs.
+    opacity: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> color_text: ColorText;
+@group(0) @binding(1)
+var atlas: texture_2d<f32>;
+@group(0) @binding(2)
+var atlas_sampler: sampler;
+
+struct InstanceInput {
+    @location(0) origin: vec2<f32>,
+    @location(1) x_axis: vec2<f32>,
+    @location(2) y_axis: vec2<f32>,
+    @location(3) atlas_bounds: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) atlas_position: vec2<f32>,
+};
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    instance: InstanceInput,
+) -> VertexOutput {
+    // Two triangles covering the glyph.
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+    let text_position = instance.origin + instance.x_axis * corner.x + instance.y_axis * corner.y;
+    let position = (color_text.transform * vec3<f32>(text_position, 1.0)).xy;
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(
+        position.x / color_text.viewport_size.x * 2.0 - 1.0,
+        1.0 - position.y / color_text.viewport_size.y * 2.0,
+        0.0,
+        1.0,
+    );
+    out.atlas_position = mix(instance.atlas_bounds.xy, instance.atlas_bounds.zw, corner);
+    return out;
+}
+
+// Fragment shader
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // The atlas holds premultiplied colors.
+    let atlas_size = vec2<f32>(textureDimensions(atlas));
+    let color = textureSample(atlas, atlas_sampler, in.atlas_position / atlas_size);
+    return color * color_text.opacity * clip_coverage(in.clip_position.xy);
+}
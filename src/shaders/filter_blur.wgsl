@@ -0,0 +1,59 @@
+// Shaders for one direction of a separable Gaussian blur.  Also used to resample textures.
+
+struct Blur {
+    target_size: vec2<f32>,
+    // The distance between two taps, in texture coordinates.
+    direction: vec2<f32>,
+    // The standard deviation, in taps.
+    sigma: f32,
+    // The number of taps on each side of the center.
+    radius: u32,
+};
+
+@group(0) @binding(0)
+var<uniform> blur: Blur;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var source_texture: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // A triangle covering the whole target.
+    var corners = array<vec2<f32>, 3>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(3.0, -1.0),
+        vec2<f32>(-1.0, 3.0),
+    );
+
+    return vec4<f32>(corners[vertex_index], 0.0, 1.0);
+}
+
+// Samples the source, treating everything outside of it as transparent.
+fn tap(uv: vec2<f32>) -> vec4<f32> {
+    let color = textureSampleLevel(source_texture, source_sampler, uv, 0.0);
+    if any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0)) {
+        return vec4<f32>(0.0);
+    }
+    return color;
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let uv = position.xy / blur.target_size;
+    if blur.radius == 0u {
+        return tap(uv);
+    }
+
+    var sum = vec4<f32>(0.0);
+    var total_weight = 0.0;
+    let radius = i32(blur.radius);
+    for (var i = -radius; i <= radius; i++) {
+        let x = f32(i);
+        let weight = exp(-x * x / (2.0 * blur.sigma * blur.sigma));
+        sum += tap(uv + blur.direction * x) * weight;
+        total_weight += weight;
+    }
+
+    return sum / total_weight;
+}
@@ -0,0 +1,81 @@
+// Shaders for transforming colors with a 4x5 matrix.
+
+struct ColorMatrix {
+    // The first four columns of each row of the matrix.
+    rows: array<vec4<f32>, 4>,
+    // The fifth column of the matrix.
+    offsets: vec4<f32>,
+    // 0 to use the sampled colors as-is, 1 to encode them to sRGB, 2 to decode them to linear.
+    conversion: u32,
+};
+
+@group(0) @binding(0)
+var<uniform> matrix: ColorMatrix;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var source_texture: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // A triangle covering the whole target.
+    var corners = array<vec2<f32>, 3>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(3.0, -1.0),
+        vec2<f32>(-1.0, 3.0),
+    );
+
+    return vec4<f32>(corners[vertex_index], 0.0, 1.0);
+}
+
+fn linear_to_srgb(value: vec3<f32>) -> vec3<f32> {
+    let low = value * 12.92;
+    let high = 1.055 * pow(value, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, value <= vec3<f32>(0.0031308));
+}
+
+fn srgb_to_linear(value: vec3<f32>) -> vec3<f32> {
+    let low = value / 12.92;
+    let high = pow((value + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, value <= vec3<f32>(0.04045));
+}
+
+// Converts a sampled color to the working space, or back if `inverse` is true.
+fn convert(rgb: vec3<f32>, inverse: bool) -> vec3<f32> {
+    var conversion = matrix.conversion;
+    if inverse && conversion != 0u {
+        conversion = 3u - conversion;
+    }
+    switch conversion {
+        case 1u: {
+            return linear_to_srgb(rgb);
+        }
+        case 2u: {
+            return srgb_to_linear(rgb);
+        }
+        default: {
+            return rgb;
+        }
+    }
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let uv = position.xy / vec2<f32>(textureDimensions(source_texture));
+    let source = textureSampleLevel(source_texture, source_sampler, uv, 0.0);
+
+    // The matrix applies to unpremultiplied colors.
+    let color = vec4<f32>(convert(source.rgb / max(source.a, 1e-6), false), source.a);
+    let result = clamp(
+        vec4<f32>(
+            dot(matrix.rows[0], color),
+            dot(matrix.rows[1], color),
+            dot(matrix.rows[2], color),
+            dot(matrix.rows[3], color),
+        ) + matrix.offsets,
+        vec4<f32>(0.0),
+        vec4<f32>(1.0),
+    );
+
+    return vec4<f32>(convert(result.rgb, true) * result.a, result.a);
+}
@@ -0,0 +1,108 @@
+// Shaders for convolving a texture with a kernel.
+
+struct Kernel {
+    size: vec2<u32>,
+    divisor: f32,
+    bias: f32,
+    // 1 if the alpha is left untouched and the kernel applies to unpremultiplied colors.
+    preserve_alpha: u32,
+    // 0 to use the sampled colors as-is, 1 to encode them to sRGB, 2 to decode them to linear.
+    conversion: u32,
+    // The weights in rows from top to bottom, four per element.
+    values: array<vec4<f32>, 64>,
+};
+
+@group(0) @binding(0)
+var<uniform> kernel: Kernel;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var source_texture: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // A triangle covering the whole target.
+    var corners = array<vec2<f32>, 3>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(3.0, -1.0),
+        vec2<f32>(-1.0, 3.0),
+    );
+
+    return vec4<f32>(corners[vertex_index], 0.0, 1.0);
+}
+
+fn linear_to_srgb(value: vec3<f32>) -> vec3<f32> {
+    let low = value * 12.92;
+    let high = 1.055 * pow(value, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, value <= vec3<f32>(0.0031308));
+}
+
+fn srgb_to_linear(value: vec3<f32>) -> vec3<f32> {
+    let low = value / 12.92;
+    let high = pow((value + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, value <= vec3<f32>(0.04045));
+}
+
+// Converts a sampled color to the working space, or back if `inverse` is true.
+fn convert(rgb: vec3<f32>, inverse: bool) -> vec3<f32> {
+    var conversion = kernel.conversion;
+    if inverse && conversion != 0u {
+        conversion = 3u - conversion;
+    }
+    switch conversion {
+        case 1u: {
+            return linear_to_srgb(rgb);
+        }
+        case 2u: {
+            return srgb_to_linear(rgb);
+        }
+        default: {
+            return rgb;
+        }
+    }
+}
+
+fn weight(index: u32) -> f32 {
+    return kernel.values[index / 4u][index % 4u];
+}
+
+// Samples the source in the working space, premultiplied unless the alpha is preserved.
+fn tap(uv: vec2<f32>) -> vec4<f32> {
+    let source = textureSampleLevel(source_texture, source_sampler, uv, 0.0);
+    let rgb = convert(source.rgb / max(source.a, 1e-6), false);
+    if kernel.preserve_alpha == 1u {
+        return vec4<f32>(rgb, source.a);
+    }
+    return vec4<f32>(rgb * source.a, source.a);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let texture_size = vec2<f32>(textureDimensions(source_texture));
+    let center = vec2<i32>(kernel.size / 2u);
+    let count = kernel.size.x * kernel.size.y;
+
+    var sum = vec4<f32>(0.0);
+    for (var y = 0u; y < kernel.size.y; y++) {
+        for (var x = 0u; x < kernel.size.x; x++) {
+            let offset = vec2<f32>(vec2<i32>(i32(x), i32(y)) - center);
+            // The kernel is rotated by 180 degrees, like in SVG.
+            let index = count - 1u - (y * kernel.size.x + x);
+            sum += tap((position.xy + offset) / texture_size) * weight(index);
+        }
+    }
+
+    var result: vec4<f32>;
+    if kernel.preserve_alpha == 1u {
+        let alpha = textureSampleLevel(source_texture, source_sampler, position.xy / texture_size, 0.0).a;
+        let rgb = clamp(sum.rgb / kernel.divisor + kernel.bias, vec3<f32>(0.0), vec3<f32>(1.0));
+        result = vec4<f32>(rgb, alpha);
+    } else {
+        // Like in SVG, the bias of premultiplied colors is multiplied by the alpha.
+        let alpha = clamp(sum.a / kernel.divisor + kernel.bias, 0.0, 1.0);
+        let rgb = clamp(sum.rgb / kernel.divisor + kernel.bias * alpha, vec3<f32>(0.0), vec3<f32>(alpha));
+        result = vec4<f32>(rgb / max(alpha, 1e-6), alpha);
+    }
+
+    return vec4<f32>(convert(result.rgb, true) * result.a, result.a);
+}
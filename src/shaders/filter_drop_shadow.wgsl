@@ -0,0 +1,43 @@
+// Shaders for drawing a blurred shadow beneath a texture.
+
+struct DropShadow {
+    // Premultiplied.
+    color: vec4<f32>,
+    // In texture coordinates.
+    offset: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> shadow: DropShadow;
+@group(0) @binding(1)
+var source_sampler: sampler;
+@group(0) @binding(2)
+var source_texture: texture_2d<f32>;
+@group(0) @binding(3)
+var blurred_texture: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // A triangle covering the whole target.
+    var corners = array<vec2<f32>, 3>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(3.0, -1.0),
+        vec2<f32>(-1.0, 3.0),
+    );
+
+    return vec4<f32>(corners[vertex_index], 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let source = textureLoad(source_texture, vec2<i32>(position.xy), 0);
+
+    let uv = position.xy / vec2<f32>(textureDimensions(source_texture)) - shadow.offset;
+    var coverage = textureSampleLevel(blurred_texture, source_sampler, uv, 0.0).a;
+    if any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0)) {
+        coverage = 0.0;
+    }
+
+    // The source is drawn over its shadow.
+    return source + shadow.color * coverage * (1.0 - source.a);
+}
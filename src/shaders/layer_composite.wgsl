@@ -0,0 +1,35 @@
+// Shaders for compositing a texture onto the target, pixel for pixel.
+
+struct Composite {
+    // Where the top-left corner of the layer is on the target, in pixels.
+    origin: vec2<i32>,
+};
+
+@group(0) @binding(0)
+var<uniform> composite: Composite;
+@group(0) @binding(1)
+var layer_texture: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // A triangle covering the whole target.
+    var corners = array<vec2<f32>, 3>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(3.0, -1.0),
+        vec2<f32>(-1.0, 3.0),
+    );
+
+    return vec4<f32>(corners[vertex_index], 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let texel = vec2<i32>(position.xy) - composite.origin;
+    let size = vec2<i32>(textureDimensions(layer_texture));
+    if any(texel < vec2<i32>(0)) || any(texel >= size) {
+        return vec4<f32>(0.0);
+    }
+
+    // The layer is premultiplied.
+    return textureLoad(layer_texture, texel, 0) * clip_coverage(position.xy);
+}
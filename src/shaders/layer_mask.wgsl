@@ -0,0 +1,64 @@
+// Shaders for compositing a layer through a mask.
+
+struct Mask {
+    // Maps pixels of the target to pixels of the mask texture.
+    to_mask: mat3x3<f32>,
+    size: vec2<f32>,
+    // 0 for alpha masks, 1 for luminance masks.
+    mode: u32,
+    // 1 if the mask texture has an sRGB format.
+    srgb: u32,
+};
+
+@group(0) @binding(0)
+var<uniform> mask: Mask;
+@group(0) @binding(1)
+var layer_texture: texture_2d<f32>;
+@group(0) @binding(2)
+var mask_texture: texture_2d<f32>;
+@group(0) @binding(3)
+var mask_sampler: sampler;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // A triangle covering the whole target.
+    var corners = array<vec2<f32>, 3>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(3.0, -1.0),
+        vec2<f32>(-1.0, 3.0),
+    );
+
+    return vec4<f32>(corners[vertex_index], 0.0, 1.0);
+}
+
+fn linear_to_srgb(value: vec3<f32>) -> vec3<f32> {
+    let low = value * 12.92;
+    let high = 1.055 * pow(value, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, value <= vec3<f32>(0.0031308));
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let layer = textureLoad(layer_texture, vec2<i32>(position.xy), 0);
+
+    let uv = (mask.to_mask * vec3<f32>(position.xy, 1.0)).xy / mask.size;
+    let sample = textureSampleLevel(mask_texture, mask_sampler, uv, 0.0);
+
+    var coverage = sample.a;
+    if mask.mode == 1u {
+        // The luminance is computed from unpremultiplied sRGB values, like SVG does.
+        var rgb = sample.rgb / max(sample.a, 1e-6);
+        if mask.srgb == 1u {
+            rgb = linear_to_srgb(rgb);
+        }
+        coverage = dot(rgb, vec3<f32>(0.2125, 0.7154, 0.0721)) * sample.a;
+    }
+
+    // Nothing outside the mask texture is visible.
+    if any(uv < vec2<f32>(0.0)) || any(uv > vec2<f32>(1.0)) {
+        coverage = 0.0;
+    }
+
+    // The layer is premultiplied, since it was drawn to from transparent black.
+    return layer * coverage * clip_coverage(position.xy);
+}
@@ -0,0 +1,111 @@
+// Shaders for downsampling a mip level of a texture into the next.
+
+struct Mipmap {
+    // 0 for a box filter, 1 for a Kaiser-windowed sinc filter.
+    kind: u32,
+};
+
+@group(0) @binding(0)
+var<uniform> mipmap: Mipmap;
+@group(0) @binding(1)
+var source_texture: texture_2d<f32>;
+
+const PI: f32 = 3.14159265;
+
+// The radius of the Kaiser filter, in pixels of the target level.
+const KAISER_RADIUS: f32 = 2.0;
+
+// The shape parameter of the Kaiser window.
+const KAISER_BETA: f32 = 4.0;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // A triangle covering the whole target.
+    var corners = array<vec2<f32>, 3>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(3.0, -1.0),
+        vec2<f32>(-1.0, 3.0),
+    );
+
+    return vec4<f32>(corners[vertex_index], 0.0, 1.0);
+}
+
+// The zeroth-order modified Bessel function of the first kind.
+fn bessel_i0(x: f32) -> f32 {
+    let quarter_x_squared = x * x / 4.0;
+    var term = 1.0;
+    var sum = 1.0;
+    for (var k = 1; k < 16; k++) {
+        term *= quarter_x_squared / f32(k * k);
+        sum += term;
+    }
+    return sum;
+}
+
+// Returns the weight of the source pixel starting at *start* along one axis, for the target pixel
+// centered at *center*, in source pixels.  *scale* is the number of source pixels per target
+// pixel.
+fn weight(start: f32, center: f32, scale: f32) -> f32 {
+    if mipmap.kind == 0u {
+        // The part of the source pixel covered by the target pixel.
+        let half = scale * 0.5;
+        return max(min(start + 1.0, center + half) - max(start, center - half), 0.0);
+    }
+
+    let t = (start + 0.5 - center) / scale;
+    if abs(t) >= KAISER_RADIUS {
+        return 0.0;
+    }
+
+    var sinc = 1.0;
+    if abs(t) > 1e-4 {
+        sinc = sin(PI * t) / (PI * t);
+    }
+    let window = t / KAISER_RADIUS;
+    return sinc * bessel_i0(KAISER_BETA * sqrt(1.0 - window * window)) / bessel_i0(KAISER_BETA);
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let source_size = vec2<i32>(textureDimensions(source_texture));
+    let target_size = max(source_size / 2, vec2<i32>(1));
+    let scale = vec2<f32>(source_size) / vec2<f32>(target_size);
+    let center = position.xy * scale;
+
+    var radius = scale * 0.5;
+    if mipmap.kind == 1u {
+        radius = scale * KAISER_RADIUS;
+    }
+    let first = vec2<i32>(floor(center - radius));
+    let last = vec2<i32>(ceil(center + radius)) - 1;
+
+    // Weight the colors by their alpha, so transparent pixels don't darken their neighbours.
+    var color_sum = vec3<f32>(0.0);
+    var alpha_sum = 0.0;
+    var weight_sum = 0.0;
+    for (var y = first.y; y <= last.y; y++) {
+        let weight_y = weight(f32(y), center.y, scale.y);
+        if weight_y == 0.0 {
+            continue;
+        }
+
+        for (var x = first.x; x <= last.x; x++) {
+            let w = weight(f32(x), center.x, scale.x) * weight_y;
+            let texel = textureLoad(
+                source_texture,
+                clamp(vec2<i32>(x, y), vec2<i32>(0), source_size - 1),
+                0,
+            );
+            color_sum += texel.rgb * texel.a * w;
+            alpha_sum += texel.a * w;
+            weight_sum += w;
+        }
+    }
+
+    let alpha = clamp(alpha_sum / weight_sum, 0.0, 1.0);
+    var color = vec3<f32>(0.0);
+    if abs(alpha_sum) > 1e-6 {
+        color = max(color_sum / alpha_sum, vec3<f32>(0.0));
+    }
+    return vec4<f32>(color, alpha);
+}
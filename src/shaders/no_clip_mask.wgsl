@@ -0,0 +1,8 @@
+// Code shared by the shaders drawing to a canvas without clip paths.
+
+// Returns the coverage of the clip paths at the pixel containing *position*: everything is
+// covered.
+fn clip_coverage(position: vec2<f32>) -> f32 {
+    return 1.0;
+}
+
@@ -0,0 +1,100 @@
+// Shared code for the shaders filling shapes with a `Paint`.
+//
+// Shaders including this file must declare a `paint` uniform of type `Paint`.
+
+struct Paint {
+    // 0 for vertex colors, 1 for a solid color, 2 for a linear gradient, 3 for a radial gradient,
+    // 4 for a sweep gradient.
+    kind: u32,
+    stop_count: u32,
+    color: vec4<f32>,
+    // [start, end] for linear gradients, [center, radius, 0] for radial ones, [center, start_angle,
+    // end_angle] for sweep ones.
+    geometry: vec4<f32>,
+    offsets: array<vec4<f32>, 4>,
+    colors: array<vec4<f32>, 16>,
+};
+
+fn premultiply(color: vec4<f32>) -> vec4<f32> {
+    return vec4<f32>(color.rgb * color.a, color.a);
+}
+
+// The color of the gradient at `t`, premultiplied.
+fn gradient_color(t: f32) -> vec4<f32> {
+    if paint.stop_count == 0u {
+        return vec4<f32>(0.0);
+    }
+
+    var previous_offset = paint.offsets[0].x;
+    var previous_color = premultiply(paint.colors[0]);
+    if t <= previous_offset {
+        return previous_color;
+    }
+
+    for (var i = 1u; i < paint.stop_count; i++) {
+        let offset = paint.offsets[i / 4u][i % 4u];
+        let color = premultiply(paint.colors[i]);
+        if t <= offset {
+            let span = offset - previous_offset;
+            let factor = select(1.0, (t - previous_offset) / span, span > 0.0);
+            return mix(previous_color, color, factor);
+        }
+
+        previous_offset = offset;
+        previous_color = color;
+    }
+
+    return previous_color;
+}
+
+// The color of the paint at `position` in pixels, with straight alpha.
+fn paint_color(position: vec2<f32>, vertex_color: vec4<f32>) -> vec4<f32> {
+    switch paint.kind {
+        case 1u: {
+            return paint.color;
+        }
+        case 2u: {
+            let start = paint.geometry.xy;
+            let direction = paint.geometry.zw - start;
+            let length_squared = dot(direction, direction);
+            let t = select(0.0, dot(position - start, direction) / length_squared, length_squared > 0.0);
+            return unpremultiply(gradient_color(t));
+        }
+        case 3u: {
+            let radius = paint.geometry.z;
+            let t = select(0.0, distance(position, paint.geometry.xy) / radius, radius > 0.0);
+            return unpremultiply(gradient_color(t));
+        }
+        case 4u: {
+            let offset = position - paint.geometry.xy;
+            let angle = atan2(offset.y, offset.x) - paint.geometry.z;
+            let span = paint.geometry.w - paint.geometry.z;
+
+            // Measure the angle in the direction of the sweep, within a single turn.
+            let turn = 6.28318530718;
+            var t = 0.0;
+            if span > 0.0 {
+                t = positive_mod(angle, turn) / span;
+            } else if span < 0.0 {
+                t = positive_mod(-angle, turn) / -span;
+            }
+            return unpremultiply(gradient_color(t));
+        }
+        default: {
+            return vertex_color;
+        }
+    }
+}
+
+// The remainder of `x / y`, with the sign of `y`.
+fn positive_mod(x: f32, y: f32) -> f32 {
+    return x - y * floor(x / y);
+}
+
+fn unpremultiply(color: vec4<f32>) -> vec4<f32> {
+    if color.a <= 0.0 {
+        return vec4<f32>(0.0);
+    }
+    return vec4<f32>(color.rgb / color.a, color.a);
+}
+
@@ -1,4 +1,7 @@
-// Shaders for a basic color fill paint.
+// Shaders for filling vertices with a paint.  Prefixed with `paint.wgsl`.
+
+@group(0) @binding(0)
+var<uniform> paint: Paint;
 
 struct VertexInput {
     @location(0) position: vec2<f32>,
@@ -25,5 +28,7 @@ fn vs_main(
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
-    return in.color;
-}
\ No newline at end of file
+    // The position of fragments is in pixels.
+    let color = paint_color(in.clip_position.xy, in.color);
+    return vec4<f32>(color.rgb, color.a * clip_coverage(in.clip_position.xy));
+}
@@ -0,0 +1,152 @@
+// Shaders for resampling a region of a texture along one axis.
+
+struct Resample {
+    // The first and last pixels of the source region, inclusive.
+    source_min: vec2<i32>,
+    source_max: vec2<i32>,
+    // The top-left corner of the target region, in pixels.
+    target_origin: vec2<f32>,
+    // The number of source pixels per target pixel along the axis.
+    scale: f32,
+    // 0 to resample horizontally, 1 vertically.
+    axis: u32,
+    // 0 for nearest, 1 for bilinear, 2 for Catmull-Rom, 3 for Mitchell, 4 for Lanczos.
+    kind: u32,
+    // Bit 0 premultiplies the source colors, bit 1 unpremultiplies the resampled colors.
+    flags: u32,
+};
+
+@group(0) @binding(0)
+var<uniform> resample: Resample;
+@group(0) @binding(1)
+var source_texture: texture_2d<f32>;
+
+const PI: f32 = 3.14159265;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // A triangle covering the whole target.
+    var corners = array<vec2<f32>, 3>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(3.0, -1.0),
+        vec2<f32>(-1.0, 3.0),
+    );
+
+    return vec4<f32>(corners[vertex_index], 0.0, 1.0);
+}
+
+// A cubic filter with the B and C parameters of Mitchell and Netravali.
+fn cubic(distance: f32, b: f32, c: f32) -> f32 {
+    let x = abs(distance);
+    if x < 1.0 {
+        return ((12.0 - 9.0 * b - 6.0 * c) * x * x * x + (-18.0 + 12.0 * b + 6.0 * c) * x * x
+            + (6.0 - 2.0 * b)) / 6.0;
+    }
+    if x < 2.0 {
+        return ((-b - 6.0 * c) * x * x * x + (6.0 * b + 30.0 * c) * x * x
+            + (-12.0 * b - 48.0 * c) * x + (8.0 * b + 24.0 * c)) / 6.0;
+    }
+    return 0.0;
+}
+
+fn sinc(x: f32) -> f32 {
+    if abs(x) < 1e-4 {
+        return 1.0;
+    }
+    return sin(PI * x) / (PI * x);
+}
+
+// Returns the radius of the filter, in target pixels.
+fn radius() -> f32 {
+    switch resample.kind {
+        case 1u: {
+            return 1.0;
+        }
+        case 2u, 3u: {
+            return 2.0;
+        }
+        case 4u: {
+            return 3.0;
+        }
+        default: {
+            return 0.5;
+        }
+    }
+}
+
+// Returns the weight of a source pixel from its distance to the sampled point, in target pixels.
+fn kernel(x: f32) -> f32 {
+    switch resample.kind {
+        case 1u: {
+            return max(1.0 - abs(x), 0.0);
+        }
+        case 2u: {
+            return cubic(x, 0.0, 0.5);
+        }
+        case 3u: {
+            return cubic(x, 1.0 / 3.0, 1.0 / 3.0);
+        }
+        case 4u: {
+            if abs(x) >= 3.0 {
+                return 0.0;
+            }
+            return sinc(x) * sinc(x / 3.0);
+        }
+        default: {
+            return 1.0;
+        }
+    }
+}
+
+fn load(along: i32, across: i32) -> vec4<f32> {
+    var texel: vec4<f32>;
+    if resample.axis == 0u {
+        texel = textureLoad(source_texture, vec2<i32>(along, across), 0);
+    } else {
+        texel = textureLoad(source_texture, vec2<i32>(across, along), 0);
+    }
+
+    if (resample.flags & 1u) != 0u {
+        texel = vec4<f32>(texel.rgb * texel.a, texel.a);
+    }
+    return texel;
+}
+
+@fragment
+fn fs_main(@builtin(position) position: vec4<f32>) -> @location(0) vec4<f32> {
+    let local = position.xy - resample.target_origin;
+    let axis = resample.axis;
+    let across = i32(floor(local[1u - axis])) + resample.source_min[1u - axis];
+    let first = resample.source_min[axis];
+    let last = resample.source_max[axis];
+
+    // The sampled point, in source pixels.
+    let center = f32(first) + local[axis] * resample.scale;
+
+    var color = vec4<f32>(0.0);
+    if resample.kind == 0u {
+        color = load(clamp(i32(floor(center)), first, last), across);
+    } else {
+        // Widen the filter when downsampling, so every source pixel contributes.
+        let filter_scale = max(resample.scale, 1.0);
+        let radius = radius() * filter_scale;
+
+        var weight_sum = 0.0;
+        for (var i = i32(floor(center - radius)); i < i32(ceil(center + radius)); i++) {
+            let weight = kernel((f32(i) + 0.5 - center) / filter_scale);
+            color += load(clamp(i, first, last), across) * weight;
+            weight_sum += weight;
+        }
+        color /= weight_sum;
+    }
+
+    color.a = clamp(color.a, 0.0, 1.0);
+    if (resample.flags & 2u) != 0u {
+        if color.a > 1e-6 {
+            color = vec4<f32>(color.rgb / color.a, color.a);
+        } else {
+            color = vec4<f32>(0.0);
+        }
+    }
+    return vec4<f32>(max(color.rgb, vec3<f32>(0.0)), color.a);
+}
@@ -0,0 +1,106 @@
+// Shaders for shapes rendered with signed distance fields.
+
+struct Viewport {
+    size: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> viewport: Viewport;
+
+struct InstanceInput {
+    @location(0) bounds: vec4<f32>,
+    @location(1) geometry: vec4<f32>,
+    @location(2) radii: vec4<f32>,
+    @location(3) fill: vec4<f32>,
+    @location(4) border_color: vec4<f32>,
+    @location(5) border_width: f32,
+    @location(6) kind: u32,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) position: vec2<f32>,
+    @location(1) geometry: vec4<f32>,
+    @location(2) radii: vec4<f32>,
+    @location(3) fill: vec4<f32>,
+    @location(4) border_color: vec4<f32>,
+    @location(5) border_width: f32,
+    @location(6) @interpolate(flat) kind: u32,
+};
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    instance: InstanceInput,
+) -> VertexOutput {
+    // Two triangles covering the bounds of the shape.
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let position = mix(instance.bounds.xy, instance.bounds.zw, corners[vertex_index]);
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(
+        position.x / viewport.size.x * 2.0 - 1.0,
+        1.0 - position.y / viewport.size.y * 2.0,
+        0.0,
+        1.0,
+    );
+    out.position = position;
+    out.geometry = instance.geometry;
+    out.radii = instance.radii;
+    out.fill = instance.fill;
+    out.border_color = instance.border_color;
+    out.border_width = instance.border_width;
+    out.kind = instance.kind;
+    return out;
+}
+
+// Fragment shader
+
+// The signed distance from `p` to a box centered on the origin with the provided half size and
+// corner radii (top-left, top-right, bottom-right, bottom-left).
+fn sd_rounded_box(p: vec2<f32>, half_size: vec2<f32>, radii: vec4<f32>) -> f32 {
+    var radius: f32;
+    if p.x < 0.0 {
+        radius = select(radii.w, radii.x, p.y < 0.0);
+    } else {
+        radius = select(radii.z, radii.y, p.y < 0.0);
+    }
+
+    let q = abs(p) - half_size + radius;
+    return min(max(q.x, q.y), 0.0) + length(max(q, vec2<f32>(0.0))) - radius;
+}
+
+// The signed distance from `p` to the segment from `a` to `b` thickened by `radius`.
+fn sd_capsule(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>, radius: f32) -> f32 {
+    let pa = p - a;
+    let ba = b - a;
+    let h = clamp(dot(pa, ba) / max(dot(ba, ba), 1e-6), 0.0, 1.0);
+    return length(pa - ba * h) - radius;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    var distance: f32;
+    if in.kind == 0u {
+        distance = sd_rounded_box(in.position - in.geometry.xy, in.geometry.zw, in.radii);
+    } else {
+        distance = sd_capsule(in.position, in.geometry.xy, in.geometry.zw, in.radii.x);
+    }
+
+    // The width of a pixel in distance units, so edges are always one pixel wide.
+    let aa_width = max(fwidth(distance), 1e-4);
+    let outer = clamp(0.5 - distance / aa_width, 0.0, 1.0);
+    let inner = clamp(0.5 - (distance + in.border_width) / aa_width, 0.0, 1.0);
+
+    // Premultiplied output so the border and fill blend correctly at their shared edge.
+    let fill = vec4<f32>(in.fill.rgb * in.fill.a, in.fill.a);
+    let border = vec4<f32>(in.border_color.rgb * in.border_color.a, in.border_color.a);
+    return (fill * inner + border * (outer - inner)) * clip_coverage(in.clip_position.xy);
+}
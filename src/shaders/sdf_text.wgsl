@@ -0,0 +1,111 @@
+// Shaders for drawing glyphs from their signed distance fields in a glyph atlas.  Prefixed with
+// `paint.wgsl`.
+
+struct SdfText {
+    // Maps the pixels of the text layout to the pixels of the canvas.
+    transform: mat3x3<f32>,
+    viewport_size: vec2<f32>,
+
+    // The pixels of the text layout per texel of the atlas.
+    scale: f32,
+
+    // How far the distance fields reach, in texels.
+    spread: f32,
+    outline_color: vec4<f32>,
+    glow_color: vec4<f32>,
+
+    // In the pixels of the text layout.
+    outline_width: f32,
+    glow_radius: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> paint: Paint;
+@group(0) @binding(1)
+var<uniform> sdf_text: SdfText;
+@group(0) @binding(2)
+var atlas: texture_2d<f32>;
+@group(0) @binding(3)
+var atlas_sampler: sampler;
+
+struct InstanceInput {
+    @location(0) origin: vec2<f32>,
+    @location(1) x_axis: vec2<f32>,
+    @location(2) y_axis: vec2<f32>,
+    @location(3) atlas_bounds: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) atlas_position: vec2<f32>,
+};
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    instance: InstanceInput,
+) -> VertexOutput {
+    // Two triangles covering the glyph.
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+    let layout_position =
+        instance.origin + instance.x_axis * corner.x + instance.y_axis * corner.y;
+    let position = (sdf_text.transform * vec3<f32>(layout_position, 1.0)).xy;
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(
+        position.x / sdf_text.viewport_size.x * 2.0 - 1.0,
+        1.0 - position.y / sdf_text.viewport_size.y * 2.0,
+        0.0,
+        1.0,
+    );
+    out.atlas_position = mix(instance.atlas_bounds.xy, instance.atlas_bounds.zw, corner);
+    return out;
+}
+
+// Fragment shader
+
+// Returns how much of a pixel is within *edge* texels of the glyph, given the distance of its
+// center to the glyph and how many texels the pixel spans.
+fn edge_coverage(distance: f32, edge: f32, pixel_size: f32) -> f32 {
+    return clamp(0.5 - (distance - edge) / pixel_size, 0.0, 1.0);
+}
+
+// Composites the premultiplied color *top* over *bottom*.
+fn over(top: vec4<f32>, bottom: vec4<f32>) -> vec4<f32> {
+    return top + bottom * (1.0 - top.a);
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let atlas_size = vec2<f32>(textureDimensions(atlas));
+    let value = textureSample(atlas, atlas_sampler, in.atlas_position / atlas_size).r;
+
+    // The distance from the outline of the glyph, in texels, positive outside of it.
+    let distance = (0.5 - value) * 2.0 * sdf_text.spread;
+    let pixel_size = max(length(vec2<f32>(dpdx(distance), dpdy(distance))), 0.0001);
+
+    let outline_width = max(sdf_text.outline_width, 0.0) / sdf_text.scale;
+    let glow_radius = max(sdf_text.glow_radius, 0.0) / sdf_text.scale;
+
+    var color = vec4<f32>(0.0);
+    if glow_radius > 0.0 {
+        let glow = 1.0 - smoothstep(outline_width, outline_width + glow_radius, distance);
+        color = premultiply(sdf_text.glow_color) * glow;
+    }
+    if outline_width > 0.0 {
+        let outline = edge_coverage(distance, outline_width, pixel_size);
+        color = over(premultiply(sdf_text.outline_color) * outline, color);
+    }
+
+    let fill = edge_coverage(distance, 0.0, pixel_size);
+    let fill_color = paint_color(in.clip_position.xy, vec4<f32>(1.0));
+    return over(premultiply(fill_color) * fill, color) * clip_coverage(in.clip_position.xy);
+}
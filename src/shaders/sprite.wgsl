@@ -0,0 +1,65 @@
+// Shaders for drawing rectangles of a texture, see `Canvas::draw_texture`.
+
+struct Viewport {
+    size: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> viewport: Viewport;
+@group(0) @binding(1)
+var source: texture_2d<f32>;
+@group(0) @binding(2)
+var source_sampler: sampler;
+
+struct InstanceInput {
+    @location(0) origin: vec2<f32>,
+    @location(1) x_axis: vec2<f32>,
+    @location(2) y_axis: vec2<f32>,
+    @location(3) uv_bounds: vec4<f32>,
+    @location(4) color: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    instance: InstanceInput,
+) -> VertexOutput {
+    // Two triangles covering the sprite.
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+    let position = instance.origin + instance.x_axis * corner.x + instance.y_axis * corner.y;
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(
+        position.x / viewport.size.x * 2.0 - 1.0,
+        1.0 - position.y / viewport.size.y * 2.0,
+        0.0,
+        1.0,
+    );
+    out.uv = mix(instance.uv_bounds.xy, instance.uv_bounds.zw, corner);
+    out.color = instance.color;
+    return out;
+}
+
+// Fragment shader
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Textures hold straight alpha; the tint is premultiplied.
+    let texel = textureSample(source, source_sampler, in.uv);
+    let color = vec4<f32>(texel.rgb * texel.a, texel.a) * in.color;
+    return color * clip_coverage(in.clip_position.xy);
+}
@@ -0,0 +1,62 @@
+// Shaders for drawing glyphs from a glyph atlas.  Prefixed with `paint.wgsl`.
+
+struct Viewport {
+    size: vec2<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> paint: Paint;
+@group(0) @binding(1)
+var<uniform> viewport: Viewport;
+@group(0) @binding(2)
+var atlas: texture_2d<f32>;
+
+struct InstanceInput {
+    @location(0) origin: vec2<f32>,
+    @location(1) x_axis: vec2<f32>,
+    @location(2) y_axis: vec2<f32>,
+    @location(3) atlas_bounds: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) atlas_position: vec2<f32>,
+};
+
+@vertex
+fn vs_main(
+    @builtin(vertex_index) vertex_index: u32,
+    instance: InstanceInput,
+) -> VertexOutput {
+    // Two triangles covering the glyph.
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+    let position = instance.origin + instance.x_axis * corner.x + instance.y_axis * corner.y;
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(
+        position.x / viewport.size.x * 2.0 - 1.0,
+        1.0 - position.y / viewport.size.y * 2.0,
+        0.0,
+        1.0,
+    );
+    out.atlas_position = mix(instance.atlas_bounds.xy, instance.atlas_bounds.zw, corner);
+    return out;
+}
+
+// Fragment shader
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Glyphs are drawn at whole pixels, so every pixel covers exactly one texel of the atlas.
+    let coverage = textureLoad(atlas, vec2<i32>(floor(in.atlas_position)), 0).r;
+    let color = paint_color(in.clip_position.xy, vec4<f32>(1.0));
+    return premultiply(color) * coverage * clip_coverage(in.clip_position.xy);
+}
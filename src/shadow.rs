@@ -0,0 +1,156 @@
+use crate::{Color, CornerRadii, Rect, Vec2};
+
+/// The attributes for `BoxShadowInstance::wgpu_desc`.
+const WGPU_ATTRIBS: [wgpu::VertexAttribute; 8] = wgpu::vertex_attr_array![
+    0 => Float32x4, // bounds
+    1 => Float32x4, // box_rect
+    2 => Float32x4, // box_radii
+    3 => Float32x4, // shadow_rect
+    4 => Float32x4, // shadow_radii
+    5 => Float32x4, // color
+    6 => Float32,   // sigma
+    7 => Uint32,    // inset
+];
+
+/// A shadow cast by a rounded rectangle, like the CSS `box-shadow` property.
+///
+/// See [Canvas::draw_box_shadow](crate::Canvas::draw_box_shadow).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BoxShadow {
+    /// How far the shadow is moved, in pixels.
+    pub offset: Vec2,
+
+    /// The blur radius, in pixels.  Like in CSS, the shadow is blurred with a standard deviation
+    /// of half this radius.
+    pub blur: f32,
+
+    /// How far the shadow is expanded (or contracted, if negative) before it is blurred, in
+    /// pixels.
+    pub spread: f32,
+    pub color: Color,
+
+    /// If `true`, the shadow is cast inside of the rectangle instead of outside.
+    pub inset: bool,
+}
+
+impl BoxShadow {
+    /// Creates a [BoxShadow] cast outside of the rectangle, without spread.
+    #[inline]
+    pub const fn new(offset: Vec2, blur: f32, color: Color) -> Self {
+        Self {
+            offset,
+            blur,
+            spread: 0.0,
+            color,
+            inset: false,
+        }
+    }
+
+    /// Sets how far the shadow is expanded before it is blurred.
+    #[inline]
+    pub const fn with_spread(mut self, spread: f32) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    /// Sets whether the shadow is cast inside of the rectangle.
+    #[inline]
+    pub const fn with_inset(mut self, inset: bool) -> Self {
+        self.inset = inset;
+        self
+    }
+}
+
+/// The per-instance data of the box shadow shader.
+#[derive(bytemuck::Zeroable, bytemuck::Pod, Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub(crate) struct BoxShadowInstance {
+    /// The quad covering the shadow in pixels, as `[min_x, min_y, max_x, max_y]`.
+    bounds: [f32; 4],
+
+    /// The rectangle casting the shadow, as `[min_x, min_y, max_x, max_y]`.
+    box_rect: [f32; 4],
+    box_radii: [f32; 4],
+
+    /// The shape of the shadow before blurring: the moved and spread rectangle for outer
+    /// shadows, the hole in the shadow for inset ones.
+    shadow_rect: [f32; 4],
+    shadow_radii: [f32; 4],
+    color: Color,
+    sigma: f32,
+    inset: u32,
+    _padding: [u32; 2],
+}
+
+impl BoxShadowInstance {
+    /// Returns the [`wgpu::VertexBufferLayout`] of [BoxShadowInstance] buffers.
+    pub const fn wgpu_desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &WGPU_ATTRIBS,
+        }
+    }
+
+    /// Returns the quad covering the shadow, in pixels.
+    #[inline]
+    pub fn bounds(&self) -> Rect {
+        Rect::from_min_max(
+            Vec2::new(self.bounds[0], self.bounds[1]),
+            Vec2::new(self.bounds[2], self.bounds[3]),
+        )
+    }
+
+    /// Creates the instance data for drawing the shadow of a rounded rectangle.
+    pub fn new(rect: Rect, radii: CornerRadii, shadow: &BoxShadow) -> Self {
+        let (min, max) = (rect.min(), rect.max());
+        let radii = radii.clamp(rect.width.abs().min(rect.height.abs()) * 0.5);
+        let sigma = shadow.blur.max(0.0) * 0.5;
+
+        // Spreading grows the shadow outwards, and the hole of inset shadows inwards.
+        let spread = if shadow.inset {
+            -shadow.spread
+        } else {
+            shadow.spread
+        };
+        let shadow_min = min + shadow.offset - Vec2::new(spread, spread);
+        let shadow_max = max + shadow.offset + Vec2::new(spread, spread);
+        let shadow_radii = [
+            radii.top_left,
+            radii.top_right,
+            radii.bottom_right,
+            radii.bottom_left,
+        ]
+        .map(|radius| (radius + spread).max(0.0));
+
+        // Three standard deviations hold over 99.7% of the weight of the blur.
+        let bounds = if shadow.inset {
+            [min.x, min.y, max.x, max.y]
+        } else {
+            let margin = sigma * 3.0 + 1.0;
+            [
+                shadow_min.x - margin,
+                shadow_min.y - margin,
+                shadow_max.x + margin,
+                shadow_max.y + margin,
+            ]
+        };
+
+        Self {
+            bounds,
+            box_rect: [min.x, min.y, max.x, max.y],
+            box_radii: [
+                radii.top_left,
+                radii.top_right,
+                radii.bottom_right,
+                radii.bottom_left,
+            ],
+            shadow_rect: [shadow_min.x, shadow_min.y, shadow_max.x, shadow_max.y],
+            shadow_radii,
+            color: shadow.color,
+            sigma,
+            inset: shadow.inset as u32,
+            _padding: [0; 2],
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{Color, Rect, Vec2};
+
+/// The attributes for `ShapeInstance::wgpu_desc`.
+const WGPU_ATTRIBS: [wgpu::VertexAttribute; 7] = wgpu::vertex_attr_array![
+    0 => Float32x4, // bounds
+    1 => Float32x4, // geometry
+    2 => Float32x4, // radii
+    3 => Float32x4, // fill
+    4 => Float32x4, // border_color
+    5 => Float32,   // border_width
+    6 => Uint32,    // kind
+];
+
+/// The radius of each corner of a rounded rectangle, in pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
+pub struct CornerRadii {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl CornerRadii {
+    /// Sharp corners.
+    pub const ZERO: Self = Self::uniform(0.0);
+
+    /// Creates a new [CornerRadii] from the radius of each corner, in clockwise order starting at
+    /// the top-left.
+    #[inline]
+    pub const fn new(top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) -> Self {
+        Self {
+            top_left,
+            top_right,
+            bottom_right,
+            bottom_left,
+        }
+    }
+
+    /// Creates a [CornerRadii] with the same radius for every corner.
+    #[inline]
+    pub const fn uniform(radius: f32) -> Self {
+        Self::new(radius, radius, radius, radius)
+    }
+
+    /// Clamps every radius to the range `0.0..=max`.
+    #[inline]
+    pub fn clamp(&self, max: f32) -> Self {
+        Self::new(
+            self.top_left.clamp(0.0, max),
+            self.top_right.clamp(0.0, max),
+            self.bottom_right.clamp(0.0, max),
+            self.bottom_left.clamp(0.0, max),
+        )
+    }
+}
+
+/// A shape that can be drawn with a signed-distance-field shader.
+///
+/// All coordinates are in pixels, see [Rect].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Shape {
+    /// A rectangle with rounded corners.  Radii larger than half of the rectangle's shortest side
+    /// are clamped.
+    RoundedRect { rect: Rect, radii: CornerRadii },
+
+    /// A circle.
+    Circle { center: Vec2, radius: f32 },
+
+    /// A line segment from *start* to *end* with round caps.
+    Capsule { start: Vec2, end: Vec2, radius: f32 },
+}
+
+/// How a [Shape] is filled and bordered.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ShapeStyle {
+    /// The color of the inside of the shape.
+    pub fill: Color,
+
+    /// The width of the border, in pixels.  The border is drawn on the inside of the shape's edge.
+    pub border_width: f32,
+
+    /// The color of the border.
+    pub border_color: Color,
+}
+
+impl ShapeStyle {
+    /// Creates a [ShapeStyle] which fills the shape with the provided color and has no border.
+    #[inline]
+    pub const fn fill(color: Color) -> Self {
+        Self {
+            fill: color,
+            border_width: 0.0,
+            border_color: Color::new(0.0, 0.0, 0.0, 0.0),
+        }
+    }
+
+    /// Creates a [ShapeStyle] which only draws a border, leaving the inside of the shape empty.
+    ///
+    /// Useful for rings.
+    #[inline]
+    pub const fn border(width: f32, color: Color) -> Self {
+        Self {
+            fill: Color::new(0.0, 0.0, 0.0, 0.0),
+            border_width: width,
+            border_color: color,
+        }
+    }
+}
+
+/// The per-instance data of the SDF shape shader.
+#[derive(bytemuck::Zeroable, bytemuck::Pod, Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub(crate) struct ShapeInstance {
+    /// The quad covering the shape in pixels, as `[min_x, min_y, max_x, max_y]`.
+    bounds: [f32; 4],
+
+    /// `[center_x, center_y, half_width, half_height]` for rounded rectangles, `[start_x,
+    /// start_y, end_x, end_y]` for capsules.
+    geometry: [f32; 4],
+
+    /// The corner radii of rounded rectangles, the radius of capsules.
+    radii: [f32; 4],
+    fill: Color,
+    border_color: Color,
+    border_width: f32,
+
+    /// `0` for rounded rectangles, `1` for capsules.
+    kind: u32,
+    _padding: [u32; 2],
+}
+
+impl ShapeInstance {
+    /// The number of pixels the quad of a shape is expanded by to leave room for anti-aliasing.
+    const AA_MARGIN: f32 = 1.0;
+
+    /// Returns the [`wgpu::VertexBufferLayout`] of [ShapeInstance] buffers.
+    pub const fn wgpu_desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &WGPU_ATTRIBS,
+        }
+    }
+
+    /// Returns the quad covering the shape, in pixels.
+    #[inline]
+    pub fn bounds(&self) -> Rect {
+        Rect::from_min_max(
+            Vec2::new(self.bounds[0], self.bounds[1]),
+            Vec2::new(self.bounds[2], self.bounds[3]),
+        )
+    }
+
+    /// Creates the instance data for drawing the provided [Shape].
+    pub fn new(shape: &Shape, style: &ShapeStyle) -> Self {
+        let (min, max, geometry, radii, kind) = match *shape {
+            Shape::RoundedRect { rect, radii } => {
+                let center = rect.center();
+                let half = Vec2::new(rect.width.abs() * 0.5, rect.height.abs() * 0.5);
+                let radii = radii.clamp(half.x.min(half.y));
+
+                (
+                    Vec2::new(center.x - half.x, center.y - half.y),
+                    Vec2::new(center.x + half.x, center.y + half.y),
+                    [center.x, center.y, half.x, half.y],
+                    [
+                        radii.top_left,
+                        radii.top_right,
+                        radii.bottom_right,
+                        radii.bottom_left,
+                    ],
+                    0,
+                )
+            }
+            Shape::Circle { center, radius } => {
+                let radius = radius.abs();
+
+                (
+                    Vec2::new(center.x - radius, center.y - radius),
+                    Vec2::new(center.x + radius, center.y + radius),
+                    [center.x, center.y, radius, radius],
+                    [radius; 4],
+                    0,
+                )
+            }
+            Shape::Capsule { start, end, radius } => {
+                let radius = radius.abs();
+
+                (
+                    Vec2::new(start.x.min(end.x) - radius, start.y.min(end.y) - radius),
+                    Vec2::new(start.x.max(end.x) + radius, start.y.max(end.y) + radius),
+                    [start.x, start.y, end.x, end.y],
+                    [radius, 0.0, 0.0, 0.0],
+                    1,
+                )
+            }
+        };
+
+        Self {
+            bounds: [
+                min.x - Self::AA_MARGIN,
+                min.y - Self::AA_MARGIN,
+                max.x + Self::AA_MARGIN,
+                max.y + Self::AA_MARGIN,
+            ],
+            geometry,
+            radii,
+            fill: style.fill,
+            border_color: style.border_color,
+            border_width: style.border_width.max(0.0),
+            kind,
+            _padding: [0; 2],
+        }
+    }
+}
@@ -0,0 +1,450 @@
+use std::cmp::Ordering;
+
+use crate::{BlendMode, Color, Dimension, Handle, Rect, Texture, Transform, Vec2};
+
+/// The attributes for `SpriteInstance::wgpu_desc`.
+const WGPU_ATTRIBS: [wgpu::VertexAttribute; 5] = wgpu::vertex_attr_array![
+    0 => Float32x2, // origin
+    1 => Float32x2, // x_axis
+    2 => Float32x2, // y_axis
+    3 => Float32x4, // uv_bounds
+    4 => Float32x4, // color
+];
+
+/// How the pixels of a texture are sampled when it is drawn scaled, rotated or between pixels.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum TextureSampling {
+    /// Blends the four nearest pixels of the first mip level, for smooth results.
+    #[default]
+    Linear,
+
+    /// Takes the nearest pixel of the first mip level, keeping pixel art sharp.
+    Nearest,
+
+    /// Blends the four nearest pixels of the two mip levels closest to the drawn scale, so
+    /// textures with mipmaps don't shimmer when drawn smaller.
+    Trilinear,
+
+    /// Like [TextureSampling::Trilinear], but also takes up to 16 samples along the direction a
+    /// texture is squashed in, so it stays sharp when drawn at an angle or stretched.  Falls back
+    /// to [TextureSampling::Trilinear] on devices without anisotropic filtering.
+    Anisotropic,
+}
+
+impl TextureSampling {
+    /// Every [TextureSampling], in the order of their discriminants.
+    pub(crate) const ALL: [Self; 4] = [
+        Self::Linear,
+        Self::Nearest,
+        Self::Trilinear,
+        Self::Anisotropic,
+    ];
+
+    /// Returns the [`wgpu::SamplerDescriptor`] of this [TextureSampling].
+    pub(crate) fn to_wgpu_sampler_descriptor(self) -> wgpu::SamplerDescriptor<'static> {
+        let (filter, mipmap_filter, anisotropy_clamp) = match self {
+            Self::Linear => (wgpu::FilterMode::Linear, wgpu::FilterMode::Nearest, 1),
+            Self::Nearest => (wgpu::FilterMode::Nearest, wgpu::FilterMode::Nearest, 1),
+            Self::Trilinear => (wgpu::FilterMode::Linear, wgpu::FilterMode::Linear, 1),
+            Self::Anisotropic => (wgpu::FilterMode::Linear, wgpu::FilterMode::Linear, 16),
+        };
+        wgpu::SamplerDescriptor {
+            mag_filter: filter,
+            min_filter: filter,
+            mipmap_filter,
+            anisotropy_clamp,
+            ..Default::default()
+        }
+    }
+
+    /// Creates the view of a texture sampled with this [TextureSampling], which only holds the
+    /// first mip level unless it samples between levels.
+    pub(crate) fn create_wgpu_source_view(self, wgpu_texture: &wgpu::Texture) -> wgpu::TextureView {
+        let mip_level_count = match self {
+            Self::Linear | Self::Nearest => Some(1),
+            Self::Trilinear | Self::Anisotropic => None,
+        };
+        wgpu_texture.create_view(&wgpu::TextureViewDescriptor {
+            mip_level_count,
+            ..Default::default()
+        })
+    }
+}
+
+/// How [Canvas::draw_texture](crate::Canvas::draw_texture) draws a texture.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DrawTextureOptions {
+    /// Multiplies the colors of the texture.  [Color::WHITE] leaves them unchanged.
+    pub tint: Color,
+
+    /// Multiplies the alpha of the texture, from `0.0` to `1.0`.
+    pub opacity: f32,
+
+    /// Mirrors the texture horizontally within its destination.
+    pub flip_x: bool,
+
+    /// Mirrors the texture vertically within its destination.
+    pub flip_y: bool,
+
+    /// The angle the texture is rotated by around [DrawTextureOptions::pivot], in radians.  As
+    /// *y* points down, positive angles rotate clockwise.
+    pub rotation: f32,
+
+    /// The point the texture rotates around, in pixels relative to the top-left corner of its
+    /// destination.
+    pub pivot: Vec2,
+
+    /// How the pixels of the texture are sampled.  Only [TextureSampling::Trilinear] and
+    /// [TextureSampling::Anisotropic] read the mip levels after the first, which must have been
+    /// filled beforehand, e.g. with [Handle::generate_mipmaps].
+    pub sampling: TextureSampling,
+}
+
+impl DrawTextureOptions {
+    /// Creates [DrawTextureOptions] which draw a texture as it is: untinted, opaque, unflipped
+    /// and unrotated, with linear sampling.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            tint: Color::WHITE,
+            opacity: 1.0,
+            flip_x: false,
+            flip_y: false,
+            rotation: 0.0,
+            pivot: Vec2::new(0.0, 0.0),
+            sampling: TextureSampling::Linear,
+        }
+    }
+
+    /// Sets the tint.
+    #[inline]
+    pub const fn with_tint(mut self, tint: Color) -> Self {
+        self.tint = tint;
+        self
+    }
+
+    /// Sets the opacity.
+    #[inline]
+    pub const fn with_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    /// Sets whether the texture is mirrored horizontally and vertically.
+    #[inline]
+    pub const fn with_flip(mut self, flip_x: bool, flip_y: bool) -> Self {
+        self.flip_x = flip_x;
+        self.flip_y = flip_y;
+        self
+    }
+
+    /// Sets the rotation, in radians, and the point it rotates around.
+    #[inline]
+    pub const fn with_rotation(mut self, rotation: f32, pivot: Vec2) -> Self {
+        self.rotation = rotation;
+        self.pivot = pivot;
+        self
+    }
+
+    /// Sets the [TextureSampling].
+    #[inline]
+    pub const fn with_sampling(mut self, sampling: TextureSampling) -> Self {
+        self.sampling = sampling;
+        self
+    }
+}
+
+impl Default for DrawTextureOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The per-instance data of the sprite shader: one rectangle of a texture drawn onto a
+/// parallelogram.
+#[derive(bytemuck::Zeroable, bytemuck::Pod, Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub(crate) struct SpriteInstance {
+    /// Where the top-left corner of the texture rectangle is drawn, in pixels.
+    origin: [f32; 2],
+
+    /// Where the top and left edges of the texture rectangle are drawn, relative to *origin*.
+    x_axis: [f32; 2],
+    y_axis: [f32; 2],
+
+    /// The texture rectangle in normalized coordinates, as `[left, top, right, bottom]`, swapped
+    /// when flipped.
+    uv_bounds: [f32; 4],
+
+    /// The premultiplied tint.
+    color: Color,
+}
+
+impl SpriteInstance {
+    /// Returns the [`wgpu::VertexBufferLayout`] of [SpriteInstance] buffers.
+    pub const fn wgpu_desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &WGPU_ATTRIBS,
+        }
+    }
+
+    /// Creates the instance drawing the *src* rectangle of a texture of the provided size onto
+    /// the *dst* rectangle, in pixels.
+    pub fn new(
+        texture_size: Dimension,
+        src: Rect,
+        dst: Rect,
+        options: &DrawTextureOptions,
+    ) -> Self {
+        let size = Vec2::new(texture_size.width as f32, texture_size.height as f32);
+        let (mut min, mut max) = (src.min(), src.max());
+        if options.flip_x {
+            std::mem::swap(&mut min.x, &mut max.x);
+        }
+        if options.flip_y {
+            std::mem::swap(&mut min.y, &mut max.y);
+        }
+
+        let pivot = dst.min() + options.pivot;
+        let transform = Transform::translate(pivot)
+            * Transform::rotate(options.rotation)
+            * Transform::translate(-pivot);
+        let origin = transform.transform_point(dst.min());
+        let x_axis = transform.transform_vector(Vec2::new(dst.width, 0.0));
+        let y_axis = transform.transform_vector(Vec2::new(0.0, dst.height));
+
+        let alpha = options.tint.alpha * options.opacity;
+        Self {
+            origin: [origin.x, origin.y],
+            x_axis: [x_axis.x, x_axis.y],
+            y_axis: [y_axis.x, y_axis.y],
+            uv_bounds: [
+                min.x / size.x,
+                min.y / size.y,
+                max.x / size.x,
+                max.y / size.y,
+            ],
+            color: options.tint.with_alpha(alpha).premultiplied(),
+        }
+    }
+
+    /// Returns the bounds of the parallelogram covered by the sprite, in pixels.
+    pub fn bounds(&self) -> Rect {
+        let origin = Vec2::new(self.origin[0], self.origin[1]);
+        let (x_axis, y_axis) = (
+            Vec2::new(self.x_axis[0], self.x_axis[1]),
+            Vec2::new(self.y_axis[0], self.y_axis[1]),
+        );
+        let corners = [origin + x_axis, origin + y_axis, origin + x_axis + y_axis];
+        let (min, max) = corners.iter().fold((origin, origin), |(min, max), corner| {
+            (
+                Vec2::new(min.x.min(corner.x), min.y.min(corner.y)),
+                Vec2::new(max.x.max(corner.x), max.y.max(corner.y)),
+            )
+        });
+        Rect::from_min_max(min, max)
+    }
+}
+
+/// The order a [SpriteBatch] draws its sprites in.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum SpriteSorting {
+    /// In the order they were added.  Only consecutive sprites with the same texture, sampling and
+    /// blend mode share a draw call.
+    #[default]
+    None,
+
+    /// From the lowest depth to the highest, so sprites with a higher depth are drawn on top.
+    /// Sprites of the same depth are grouped by texture, sampling and blend mode, so a batch
+    /// where every sprite has the same depth takes one draw call per texture.
+    Depth,
+}
+
+/// A sprite added to a [SpriteBatch].
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct BatchedSprite<'t> {
+    pub wgpu_texture: &'t wgpu::Texture,
+    pub sampling: TextureSampling,
+    pub blend_mode: BlendMode,
+    pub depth: f32,
+    pub instance: SpriteInstance,
+}
+
+impl<'t> BatchedSprite<'t> {
+    /// Returns `true` if this sprite can be drawn in the same draw call as *other*.
+    #[inline]
+    pub fn shares_draw_call(&self, other: &Self) -> bool {
+        std::ptr::eq(self.wgpu_texture, other.wgpu_texture)
+            && self.sampling == other.sampling
+            && self.blend_mode == other.blend_mode
+    }
+
+    /// Orders sprites by depth, then by the state their draw call needs.
+    fn cmp_depth_and_state(&self, other: &Self) -> Ordering {
+        let state = |sprite: &Self| {
+            (
+                sprite.wgpu_texture as *const wgpu::Texture as usize,
+                sprite.sampling as u8,
+                sprite.blend_mode as u8,
+            )
+        };
+        self.depth
+            .total_cmp(&other.depth)
+            .then_with(|| state(self).cmp(&state(other)))
+    }
+}
+
+/// Collects many sprites, drawn by [Canvas::draw_sprite_batch](crate::Canvas::draw_sprite_batch)
+/// with as few draw calls as possible.
+///
+/// Sprites are rectangles of textures drawn like by
+/// [Canvas::draw_texture](crate::Canvas::draw_texture).  Consecutive sprites sharing a texture,
+/// sampling and [BlendMode] are drawn by a single draw call, and every draw call of a batch goes
+/// through the same render pass.  The [SpriteSorting] of the batch can reorder sprites to group
+/// them further.
+///
+/// A batch keeps its sprites after being drawn, and the GPU buffer holding them grows as needed
+/// and is reused: call [SpriteBatch::clear] and add the sprites of the next frame to the same
+/// batch.
+#[derive(Debug)]
+pub struct SpriteBatch<'t> {
+    sprites: Vec<BatchedSprite<'t>>,
+    sorting: SpriteSorting,
+
+    /// The blend mode and depth of the sprites added next.
+    blend_mode: BlendMode,
+    depth: f32,
+
+    /// The instance buffer, and the number of [SpriteInstances](SpriteInstance) it can hold.
+    wgpu_buffer: Option<(wgpu::Buffer, usize)>,
+}
+
+impl Default for SpriteBatch<'_> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            sprites: Vec::new(),
+            sorting: SpriteSorting::default(),
+            blend_mode: BlendMode::SourceOver,
+            depth: 0.0,
+            wgpu_buffer: None,
+        }
+    }
+}
+
+impl<'t> SpriteBatch<'t> {
+    /// Creates an empty [SpriteBatch], drawing sprites in the order they are added.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty [SpriteBatch] with the provided [SpriteSorting].
+    #[inline]
+    pub fn with_sorting(sorting: SpriteSorting) -> Self {
+        Self {
+            sorting,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the [SpriteSorting] of this batch.
+    #[inline]
+    pub fn sorting(&self) -> SpriteSorting {
+        self.sorting
+    }
+
+    /// Sets the [BlendMode] the sprites added next are drawn with.  Defaults to
+    /// [BlendMode::SourceOver].
+    ///
+    /// Like [Canvas::draw_texture](crate::Canvas::draw_texture), sprites are drawn over the
+    /// destination with [BlendMode::Replace], so their transparent pixels don't punch holes in it.
+    #[inline]
+    pub fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    /// Sets the depth of the sprites added next, used by [SpriteSorting::Depth].  Defaults to
+    /// `0.0`.
+    #[inline]
+    pub fn set_depth(&mut self, depth: f32) {
+        self.depth = depth;
+    }
+
+    /// Adds a sprite drawing the *src* rectangle of a [Texture] onto the *dst* rectangle, both in
+    /// pixels, like [Canvas::draw_texture](crate::Canvas::draw_texture).
+    pub fn add<H: Handle>(
+        &mut self,
+        texture: &'t impl Texture<H>,
+        src: Rect,
+        dst: Rect,
+        options: &DrawTextureOptions,
+    ) {
+        self.sprites.push(BatchedSprite {
+            wgpu_texture: texture.wgpu_texture(),
+            sampling: options.sampling,
+            blend_mode: self.blend_mode.blending(),
+            depth: self.depth,
+            instance: SpriteInstance::new(texture.size(), src, dst, options),
+        });
+    }
+
+    /// Removes every sprite from this batch, keeping its buffers.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.sprites.clear();
+    }
+
+    /// Returns the number of sprites in this batch.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.sprites.len()
+    }
+
+    /// Returns `true` if this batch has no sprites.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sprites.is_empty()
+    }
+
+    /// Returns the sprites of this batch in the order they are drawn, sorting them if needed.
+    pub(crate) fn sorted_sprites(&mut self) -> &[BatchedSprite<'t>] {
+        if self.sorting == SpriteSorting::Depth {
+            // Stable, so sprites of the same depth and state keep their order.
+            self.sprites.sort_by(BatchedSprite::cmp_depth_and_state);
+        }
+        &self.sprites
+    }
+
+    /// Returns an instance buffer of this batch holding at least *len*
+    /// [SpriteInstances](SpriteInstance), growing it if needed.
+    pub(crate) fn wgpu_instance_buffer(
+        &mut self,
+        wgpu_device: &wgpu::Device,
+        len: usize,
+    ) -> &wgpu::Buffer {
+        if self
+            .wgpu_buffer
+            .as_ref()
+            .map_or(true, |(_, capacity)| *capacity < len)
+        {
+            let capacity = len.next_power_of_two().max(64);
+            let wgpu_buffer = wgpu_device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("Sprite Batch Buffer"),
+                size: (capacity * std::mem::size_of::<SpriteInstance>()) as wgpu::BufferAddress,
+                usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            });
+            self.wgpu_buffer = Some((wgpu_buffer, capacity));
+        }
+
+        &self
+            .wgpu_buffer
+            .as_ref()
+            .expect("the buffer was just created")
+            .0
+    }
+}
@@ -0,0 +1,595 @@
+use std::{collections::HashMap, fmt, sync::Mutex};
+
+use ab_glyph_rasterizer::{point, Point, Rasterizer};
+use etagere::{AllocId, AtlasAllocator};
+
+use super::{
+    color::{bitmap_glyph, colr_glyph},
+    sdf::sdf_glyph,
+};
+use crate::{Color, Error, Font, Handle};
+
+/// The number of horizontal positions within a pixel glyphs are rasterized at.
+pub(crate) const SUBPIXEL_STEPS: u8 = 4;
+
+/// How a glyph is stored in a [GlyphAtlas].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub(crate) enum GlyphRaster {
+    /// The coverage of the glyph.
+    Coverage {
+        /// The bits of the font size, in pixels.
+        size_bits: u32,
+
+        /// Which of the [SUBPIXEL_STEPS] the glyph is offset by.
+        subpixel: u8,
+    },
+
+    /// The signed distance field of the glyph at [SDF_SIZE](super::sdf::SDF_SIZE), see
+    /// `sdf_glyph`.
+    Sdf,
+
+    /// The colored layers of the glyph from the `COLR` table, see `colr_glyph`.
+    Color {
+        /// The bits of the font size, in pixels.
+        size_bits: u32,
+
+        /// The color of the layers using the color of the text, as RGBA bytes.
+        foreground: [u8; 4],
+    },
+
+    /// The embedded bitmap of the glyph from the strike with the provided pixels per em, see
+    /// `bitmap_glyph`.
+    Bitmap { pixels_per_em: u16 },
+}
+
+impl GlyphRaster {
+    /// Returns `true` if glyphs rasterized this way are stored in the color page of a
+    /// [GlyphAtlas].
+    #[inline]
+    pub const fn is_color(&self) -> bool {
+        matches!(self, Self::Color { .. } | Self::Bitmap { .. })
+    }
+
+    /// Returns how to rasterize a glyph in color at the provided font size, or [None] if the
+    /// glyph has no colors and is drawn with the color of the text.
+    ///
+    /// *foreground* is the color of the text, used by the layers of `COLR` glyphs which don't
+    /// have their own color.
+    pub fn for_color_glyph(
+        face: &ttf_parser::Face<'_>,
+        glyph_id: u16,
+        size: f32,
+        foreground: Color,
+    ) -> Option<Self> {
+        let id = ttf_parser::GlyphId(glyph_id);
+        if face.is_color_glyph(id) {
+            return Some(Self::Color {
+                size_bits: size.to_bits(),
+                foreground: foreground.to_rgba_bytes(),
+            });
+        }
+
+        let image = face.glyph_raster_image(id, size.ceil().clamp(1.0, u16::MAX as f32) as u16)?;
+        matches!(
+            image.format,
+            ttf_parser::RasterImageFormat::PNG | ttf_parser::RasterImageFormat::BitmapPremulBgra32
+        )
+        .then_some(Self::Bitmap {
+            pixels_per_em: image.pixels_per_em,
+        })
+    }
+
+    /// Returns how many pixels of text at the provided font size each pixel of a glyph
+    /// rasterized this way covers.
+    #[inline]
+    pub fn scale(&self, size: f32) -> f32 {
+        match self {
+            Self::Coverage { .. } => 1.0,
+            Self::Sdf => size / super::sdf::SDF_SIZE,
+            Self::Color { size_bits, .. } => size / f32::from_bits(*size_bits),
+            Self::Bitmap { pixels_per_em } => size / *pixels_per_em as f32,
+        }
+    }
+}
+
+/// Identifies a rasterized glyph in a [GlyphAtlas].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+struct GlyphKey {
+    font_id: u64,
+    glyph_id: u16,
+    raster: GlyphRaster,
+}
+
+/// Where a rasterized glyph is in a [GlyphAtlas].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct AtlasGlyph {
+    /// The position of the top-left corner of the glyph's bitmap relative to its origin on the
+    /// baseline, in pixels.
+    pub offset: [i32; 2],
+
+    /// The size of the glyph's bitmap, zero for glyphs without an outline like spaces.
+    pub size: [u32; 2],
+
+    /// The position of the top-left corner of the glyph's bitmap in the atlas.
+    pub origin: [u32; 2],
+
+    /// `true` if the glyph is in the color page of the atlas rather than the coverage one.
+    pub color: bool,
+}
+
+/// The views of the pages of a [GlyphAtlas] returned by `GlyphAtlas::prepare`.
+#[derive(Debug)]
+pub(crate) struct AtlasViews {
+    /// The `R8Unorm` page holding coverage and signed distance fields.
+    pub wgpu_mask_view: wgpu::TextureView,
+
+    /// The `Rgba8Unorm` page holding color glyphs, with premultiplied alpha.  [None] until a
+    /// color glyph is first drawn.
+    pub wgpu_color_view: Option<wgpu::TextureView>,
+}
+
+/// The pixels of a rasterized glyph, before they are copied into a [GlyphAtlas].
+pub(super) enum GlyphPixels {
+    /// Rows of pixels in the format of the page the glyph goes in.
+    Data(Vec<u8>),
+
+    /// A texture holding the glyph, in the format of the color page.
+    Texture(wgpu::Texture),
+}
+
+/// A glyph rasterized by `rasterize_glyph` or one of its colored counterparts: the position of
+/// its bitmap relative to its origin, the size of the bitmap and its pixels.
+pub(super) type GlyphBitmap = ([i32; 2], [u32; 2], GlyphPixels);
+
+/// A glyph stored in a [GlyphAtlas].
+#[derive(Debug)]
+struct CachedGlyph {
+    glyph: AtlasGlyph,
+
+    /// The space the glyph takes up in the atlas, [None] for glyphs without a bitmap.
+    alloc_id: Option<AllocId>,
+
+    /// The last draw call which used this glyph.
+    last_used: u64,
+}
+
+/// One of the textures of a [GlyphAtlas], and the space used in it.
+struct AtlasPage {
+    /// Created when first needed.
+    wgpu_texture: Option<wgpu::Texture>,
+    allocator: AtlasAllocator,
+    format: wgpu::TextureFormat,
+}
+
+struct GlyphAtlasInner {
+    /// Holds the coverage or signed distance fields of the glyphs.
+    mask_page: AtlasPage,
+
+    /// Holds the colored glyphs.
+    color_page: AtlasPage,
+    glyphs: HashMap<GlyphKey, CachedGlyph>,
+
+    /// Counts the draw calls which used this atlas.
+    draw_count: u64,
+}
+
+/// A texture caching rasterized glyphs across draw calls and frames.
+///
+/// Glyphs are packed into the atlas as they are drawn.  When it is full, the glyphs which were
+/// used the longest time ago are evicted to make room for new ones.
+///
+/// Color glyphs, like emoji, are kept in a second texture of the same size, created the first
+/// time one is drawn.
+pub struct GlyphAtlas {
+    size: u32,
+    inner: Mutex<GlyphAtlasInner>,
+}
+
+impl GlyphAtlas {
+    /// The width and height of the atlas of a [Handle] by default, in pixels.
+    pub const DEFAULT_SIZE: u32 = 1024;
+
+    /// Creates an empty [GlyphAtlas] with the provided width and height, in pixels.
+    pub fn new(size: u32) -> Self {
+        Self {
+            size,
+            inner: Mutex::new(GlyphAtlasInner {
+                mask_page: AtlasPage::new(size, wgpu::TextureFormat::R8Unorm),
+                color_page: AtlasPage::new(size, wgpu::TextureFormat::Rgba8Unorm),
+                glyphs: HashMap::new(),
+                draw_count: 0,
+            }),
+        }
+    }
+
+    /// Returns the width and height of the textures of this [GlyphAtlas], in pixels.
+    #[inline]
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Returns the number of glyphs cached in this [GlyphAtlas].
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().glyphs.len()
+    }
+
+    /// Returns `true` if no glyphs are cached in this [GlyphAtlas].
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Evicts every glyph from this [GlyphAtlas].
+    pub fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.mask_page.allocator.clear();
+        inner.color_page.allocator.clear();
+        inner.glyphs.clear();
+    }
+
+    /// Makes sure the provided glyphs are in the atlas, rasterizing the missing ones.
+    ///
+    /// Each glyph is given as its font, id and how it is rasterized.  Returns where the glyphs are, in
+    /// the same order, along with views of the atlas textures to draw them from.
+    ///
+    /// # Fails
+    /// - Fails if the glyphs don't all fit in the atlas at once.
+    /// - Fails if drawing a `COLR` glyph fails.
+    pub(crate) fn prepare<H: Handle>(
+        &self,
+        handle: &H,
+        glyphs: &[(&Font, u16, GlyphRaster)],
+    ) -> Result<(Vec<AtlasGlyph>, AtlasViews), Error> {
+        let mut inner = self.inner.lock().unwrap();
+        inner.draw_count += 1;
+        let draw_count = inner.draw_count;
+
+        let mut placed = Vec::with_capacity(glyphs.len());
+        for &(font, glyph_id, raster) in glyphs {
+            let key = GlyphKey {
+                font_id: font.id(),
+                glyph_id,
+                raster,
+            };
+
+            if let Some(cached) = inner.glyphs.get_mut(&key) {
+                cached.last_used = draw_count;
+                placed.push(cached.glyph);
+                continue;
+            }
+
+            let face = font.face();
+            let bitmap = match raster {
+                GlyphRaster::Coverage {
+                    size_bits,
+                    subpixel,
+                } => rasterize_glyph(&face, glyph_id, f32::from_bits(size_bits), subpixel)
+                    .map(|(offset, size, coverage)| (offset, size, GlyphPixels::Data(coverage))),
+                GlyphRaster::Sdf => sdf_glyph(&face, glyph_id)
+                    .map(|(offset, size, sdf)| (offset, size, GlyphPixels::Data(sdf))),
+                GlyphRaster::Color {
+                    size_bits,
+                    foreground,
+                } => colr_glyph(
+                    handle,
+                    &face,
+                    glyph_id,
+                    f32::from_bits(size_bits),
+                    Color::from_rgba8(foreground[0], foreground[1], foreground[2], foreground[3]),
+                )?,
+                GlyphRaster::Bitmap { pixels_per_em } => {
+                    bitmap_glyph(&face, glyph_id, pixels_per_em)
+                }
+            };
+            let cached = match bitmap {
+                Some((offset, [width, height], pixels)) => {
+                    let color = raster.is_color();
+                    let page = if color {
+                        &mut inner.color_page
+                    } else {
+                        &mut inner.mask_page
+                    };
+                    let bytes_per_pixel = page.bytes_per_pixel();
+
+                    // Leave a transparent pixel around every glyph so they never bleed into each
+                    // other.
+                    let padded_size = [width + 2, height + 2];
+                    let padded_row = (padded_size[0] * bytes_per_pixel) as usize;
+                    let mut padded = vec![0; padded_row * padded_size[1] as usize];
+                    if let GlyphPixels::Data(data) = &pixels {
+                        let row_len = (width * bytes_per_pixel) as usize;
+                        for (row, src) in data.chunks_exact(row_len).enumerate() {
+                            let start = (row + 1) * padded_row + bytes_per_pixel as usize;
+                            padded[start..start + row_len].copy_from_slice(src);
+                        }
+                    }
+
+                    let allocation = inner.allocate(color, padded_size, draw_count)?;
+                    let corner = allocation.rectangle.min;
+                    let (x, y) = (corner.x as u32, corner.y as u32);
+
+                    let page = if color {
+                        &mut inner.color_page
+                    } else {
+                        &mut inner.mask_page
+                    };
+                    let wgpu_texture = page.wgpu_texture(handle, self.size);
+                    handle.wgpu_queue().write_texture(
+                        wgpu::ImageCopyTexture {
+                            texture: wgpu_texture,
+                            mip_level: 0,
+                            origin: wgpu::Origin3d { x, y, z: 0 },
+                            aspect: wgpu::TextureAspect::All,
+                        },
+                        &padded,
+                        wgpu::ImageDataLayout {
+                            offset: 0,
+                            bytes_per_row: Some(padded_row as u32),
+                            rows_per_image: None,
+                        },
+                        wgpu::Extent3d {
+                            width: padded_size[0],
+                            height: padded_size[1],
+                            depth_or_array_layers: 1,
+                        },
+                    );
+
+                    // Glyphs drawn on the GPU are copied inside of the transparent border.
+                    if let GlyphPixels::Texture(wgpu_glyph_texture) = &pixels {
+                        let mut encoder = handle.wgpu_device().create_command_encoder(
+                            &wgpu::CommandEncoderDescriptor { label: None },
+                        );
+                        encoder.copy_texture_to_texture(
+                            wgpu_glyph_texture.as_image_copy(),
+                            wgpu::ImageCopyTexture {
+                                texture: wgpu_texture,
+                                mip_level: 0,
+                                origin: wgpu::Origin3d {
+                                    x: x + 1,
+                                    y: y + 1,
+                                    z: 0,
+                                },
+                                aspect: wgpu::TextureAspect::All,
+                            },
+                            wgpu::Extent3d {
+                                width,
+                                height,
+                                depth_or_array_layers: 1,
+                            },
+                        );
+                        handle
+                            .wgpu_queue()
+                            .submit(std::iter::once(encoder.finish()));
+                    }
+
+                    CachedGlyph {
+                        glyph: AtlasGlyph {
+                            offset,
+                            size: [width, height],
+                            origin: [x + 1, y + 1],
+                            color,
+                        },
+                        alloc_id: Some(allocation.id),
+                        last_used: draw_count,
+                    }
+                }
+                None => CachedGlyph {
+                    glyph: AtlasGlyph::default(),
+                    alloc_id: None,
+                    last_used: draw_count,
+                },
+            };
+
+            placed.push(cached.glyph);
+            inner.glyphs.insert(key, cached);
+        }
+
+        let views = AtlasViews {
+            wgpu_mask_view: inner
+                .mask_page
+                .wgpu_texture(handle, self.size)
+                .create_view(&wgpu::TextureViewDescriptor::default()),
+            wgpu_color_view: inner.color_page.wgpu_texture.as_ref().map(|wgpu_texture| {
+                wgpu_texture.create_view(&wgpu::TextureViewDescriptor::default())
+            }),
+        };
+        Ok((placed, views))
+    }
+}
+
+impl fmt::Debug for GlyphAtlas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlyphAtlas")
+            .field("size", &self.size)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl Default for GlyphAtlas {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SIZE)
+    }
+}
+
+impl AtlasPage {
+    /// Creates an empty [AtlasPage] with the provided width and height, in pixels.
+    fn new(size: u32, format: wgpu::TextureFormat) -> Self {
+        Self {
+            wgpu_texture: None,
+            allocator: AtlasAllocator::new(etagere::size2(size as i32, size as i32)),
+            format,
+        }
+    }
+
+    /// Returns the number of bytes of each pixel of this page.
+    #[inline]
+    fn bytes_per_pixel(&self) -> u32 {
+        self.format.block_size(None).unwrap_or(1)
+    }
+
+    /// Returns the texture of this page, creating it if needed.
+    fn wgpu_texture<H: Handle>(&mut self, handle: &H, size: u32) -> &wgpu::Texture {
+        let format = self.format;
+        self.wgpu_texture.get_or_insert_with(|| {
+            handle
+                .wgpu_device()
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some("Glyph Atlas"),
+                    size: wgpu::Extent3d {
+                        width: size,
+                        height: size,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                    view_formats: &[],
+                })
+        })
+    }
+}
+
+impl GlyphAtlasInner {
+    /// Allocates space in the color or coverage page, evicting the least recently used glyphs of
+    /// that page until it fits.
+    ///
+    /// Glyphs used by the current draw call are never evicted.
+    fn allocate(
+        &mut self,
+        color: bool,
+        [width, height]: [u32; 2],
+        draw_count: u64,
+    ) -> Result<etagere::Allocation, Error> {
+        let size = etagere::size2(width as i32, height as i32);
+        let page = if color {
+            &mut self.color_page
+        } else {
+            &mut self.mask_page
+        };
+        if let Some(allocation) = page.allocator.allocate(size) {
+            return Ok(allocation);
+        }
+
+        let mut evictable: Vec<_> = self
+            .glyphs
+            .iter()
+            .filter(|(key, cached)| {
+                key.raster.is_color() == color
+                    && cached.last_used < draw_count
+                    && cached.alloc_id.is_some()
+            })
+            .map(|(key, cached)| (cached.last_used, *key))
+            .collect();
+        evictable.sort_unstable_by_key(|(last_used, _)| *last_used);
+
+        for (_, key) in evictable {
+            if let Some(alloc_id) = self.glyphs.remove(&key).and_then(|cached| cached.alloc_id) {
+                page.allocator.deallocate(alloc_id);
+            }
+
+            if let Some(allocation) = page.allocator.allocate(size) {
+                return Ok(allocation);
+            }
+        }
+
+        Err(Error::GlyphAtlasFull)
+    }
+}
+
+/// Rasterizes the coverage of a glyph at the provided font size, offset horizontally by
+/// `subpixel / SUBPIXEL_STEPS` pixels.
+///
+/// Returns the position of the bitmap relative to the glyph's origin, its size and its rows of
+/// coverage, or [None] if the glyph has nothing to draw.
+pub(super) fn rasterize_glyph(
+    face: &ttf_parser::Face<'_>,
+    glyph_id: u16,
+    size: f32,
+    subpixel: u8,
+) -> Option<([i32; 2], [u32; 2], Vec<u8>)> {
+    let glyph_id = ttf_parser::GlyphId(glyph_id);
+    let scale = Font::scale(face, size);
+    let shift = subpixel as f32 / SUBPIXEL_STEPS as f32;
+
+    // Font units point upwards, pixels downwards.
+    let bounds = face.glyph_bounding_box(glyph_id)?;
+    let left = (bounds.x_min as f32 * scale + shift).floor();
+    let top = (-bounds.y_max as f32 * scale).floor();
+    let right = (bounds.x_max as f32 * scale + shift).ceil();
+    let bottom = (-bounds.y_min as f32 * scale).ceil();
+    let (width, height) = ((right - left) as u32, (bottom - top) as u32);
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    let mut builder = RasterizerBuilder {
+        rasterizer: Rasterizer::new(width as usize, height as usize),
+        transform: [scale, shift - left, -top],
+        start: point(0.0, 0.0),
+        last: point(0.0, 0.0),
+    };
+    face.outline_glyph(glyph_id, &mut builder)?;
+
+    let mut coverage = vec![0; (width * height) as usize];
+    builder.rasterizer.for_each_pixel(|index, alpha| {
+        coverage[index] = (alpha.min(1.0) * 255.0).round() as u8;
+    });
+
+    Some(([left as i32, top as i32], [width, height], coverage))
+}
+
+/// Draws the outline of a glyph with a [Rasterizer].
+struct RasterizerBuilder {
+    rasterizer: Rasterizer,
+
+    /// `[scale, x_offset, y_offset]`, mapping font units to the pixels of the rasterizer.
+    transform: [f32; 3],
+    start: Point,
+    last: Point,
+}
+
+impl RasterizerBuilder {
+    #[inline]
+    fn point(&self, x: f32, y: f32) -> Point {
+        let [scale, x_offset, y_offset] = self.transform;
+        point(x * scale + x_offset, -y * scale + y_offset)
+    }
+}
+
+impl ttf_parser::OutlineBuilder for RasterizerBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.start = self.point(x, y);
+        self.last = self.start;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let to = self.point(x, y);
+        self.rasterizer.draw_line(self.last, to);
+        self.last = to;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (control, to) = (self.point(x1, y1), self.point(x, y));
+        self.rasterizer.draw_quad(self.last, control, to);
+        self.last = to;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (control1, control2, to) = (self.point(x1, y1), self.point(x2, y2), self.point(x, y));
+        self.rasterizer
+            .draw_cubic(self.last, control1, control2, to);
+        self.last = to;
+    }
+
+    fn close(&mut self) {
+        if self.last != self.start {
+            self.rasterizer.draw_line(self.last, self.start);
+        }
+        self.last = self.start;
+    }
+}
@@ -0,0 +1,581 @@
+use std::{collections::HashMap, sync::Arc};
+
+use super::GlyphInstance;
+use crate::{Error, Handle, OwnedTexture, Vec2};
+
+/// A glyph of a [BitmapFont], in the pixels of its page.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct BitmapGlyph {
+    /// The top-left corner of the glyph in its page.
+    x: u16,
+    y: u16,
+    width: u16,
+    height: u16,
+
+    /// Where the top-left corner of the glyph is drawn, relative to the pen at the top of the
+    /// line.
+    x_offset: i16,
+    y_offset: i16,
+
+    /// How far the pen moves after drawing the glyph.
+    x_advance: i16,
+    page: u8,
+}
+
+/// The contents of an AngelCode BMFont descriptor (a `.fnt` file), in the text or binary format.
+///
+/// A descriptor names the images its glyphs are packed into, which are loaded separately (see
+/// [BitmapFontDescriptor::pages]) to make a [BitmapFont].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BitmapFontDescriptor {
+    face: String,
+    size: f32,
+    line_height: f32,
+    base: f32,
+    pages: Vec<String>,
+    glyphs: HashMap<char, BitmapGlyph>,
+    kernings: HashMap<(char, char), i16>,
+}
+
+impl BitmapFontDescriptor {
+    /// Parses a BMFont descriptor, in the text format or the binary format (version 3).
+    ///
+    /// # Fails
+    /// Fails if the data isn't a valid descriptor.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        let descriptor = if data.starts_with(b"BMF") {
+            Self::from_binary(data)?
+        } else {
+            let text = std::str::from_utf8(data).map_err(|err| invalid(err.to_string()))?;
+            Self::from_text(text)?
+        };
+
+        if descriptor.pages.is_empty() {
+            return Err(invalid("the descriptor has no pages"));
+        }
+        if let Some(glyph) = descriptor
+            .glyphs
+            .values()
+            .find(|glyph| glyph.page as usize >= descriptor.pages.len())
+        {
+            return Err(invalid(format!(
+                "a glyph is on missing page {}",
+                glyph.page
+            )));
+        }
+        Ok(descriptor)
+    }
+
+    /// Parses a descriptor in the text format.
+    fn from_text(text: &str) -> Result<Self, Error> {
+        let mut descriptor = Self::default();
+        let mut pages = Vec::new();
+
+        for line in text.lines() {
+            let (tag, attributes) = parse_text_line(line);
+            let get = |key: &str| {
+                attributes
+                    .iter()
+                    .find(|(name, _)| *name == key)
+                    .map(|(_, value)| *value)
+                    .unwrap_or_default()
+            };
+            let number = |key: &str| {
+                get(key)
+                    .parse::<i64>()
+                    .map_err(|_| invalid(format!("`{tag}` has an invalid `{key}`")))
+            };
+
+            match tag {
+                "info" => {
+                    descriptor.face = get("face").to_owned();
+                    descriptor.size = number("size")?.unsigned_abs() as f32;
+                }
+                "common" => {
+                    descriptor.line_height = number("lineHeight")? as f32;
+                    descriptor.base = number("base")? as f32;
+                }
+                "page" => pages.push((number("id")?, get("file").to_owned())),
+                "char" => {
+                    // Some tools write an `id` of -1 for their replacement glyph.
+                    let Some(character) = to_char(number("id")?) else {
+                        continue;
+                    };
+                    let glyph = BitmapGlyph {
+                        x: number("x")? as u16,
+                        y: number("y")? as u16,
+                        width: number("width")? as u16,
+                        height: number("height")? as u16,
+                        x_offset: number("xoffset")? as i16,
+                        y_offset: number("yoffset")? as i16,
+                        x_advance: number("xadvance")? as i16,
+                        page: number("page")? as u8,
+                    };
+                    descriptor.glyphs.insert(character, glyph);
+                }
+                "kerning" => {
+                    let characters = (number("first")?, number("second")?);
+                    if let (Some(first), Some(second)) =
+                        (to_char(characters.0), to_char(characters.1))
+                    {
+                        descriptor
+                            .kernings
+                            .insert((first, second), number("amount")? as i16);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        pages.sort_by_key(|(id, _)| *id);
+        descriptor.pages = pages.into_iter().map(|(_, file)| file).collect();
+        Ok(descriptor)
+    }
+
+    /// Parses a descriptor in the binary format.
+    fn from_binary(data: &[u8]) -> Result<Self, Error> {
+        if data.get(3) != Some(&3) {
+            return Err(invalid("only version 3 of the binary format is supported"));
+        }
+
+        let mut descriptor = Self::default();
+        let mut rest = &data[4..];
+        while !rest.is_empty() {
+            let kind = rest[0];
+            let block_len = read_u32(rest, 1).ok_or_else(|| invalid("truncated block header"))?;
+            let block = rest
+                .get(5..5 + block_len as usize)
+                .ok_or_else(|| invalid("truncated block"))?;
+            rest = &rest[5 + block_len as usize..];
+
+            match kind {
+                // Info: the font size, then fixed fields, then the name of the face.
+                1 => {
+                    let size = read_u16(block, 0).ok_or_else(|| invalid("truncated info"))?;
+                    descriptor.size = (size as i16).unsigned_abs() as f32;
+                    let name = block.get(14..).unwrap_or_default();
+                    let end = name
+                        .iter()
+                        .position(|&byte| byte == 0)
+                        .unwrap_or(name.len());
+                    descriptor.face = String::from_utf8_lossy(&name[..end]).into_owned();
+                }
+                // Common: the line height and base, then fields G2d doesn't use.
+                2 => {
+                    let (Some(line_height), Some(base)) = (read_u16(block, 0), read_u16(block, 2))
+                    else {
+                        return Err(invalid("truncated common"));
+                    };
+                    descriptor.line_height = line_height as f32;
+                    descriptor.base = base as f32;
+                }
+                // Pages: null-terminated file names.
+                3 => {
+                    descriptor.pages = block
+                        .split(|&byte| byte == 0)
+                        .filter(|name| !name.is_empty())
+                        .map(|name| String::from_utf8_lossy(name).into_owned())
+                        .collect();
+                }
+                // Chars: 20 bytes each.
+                4 => {
+                    for record in block.chunks_exact(20) {
+                        let field = |offset| read_u16(record, offset).unwrap_or_default();
+                        let Some(character) = read_u32(record, 0).and_then(char::from_u32) else {
+                            continue;
+                        };
+                        let glyph = BitmapGlyph {
+                            x: field(4),
+                            y: field(6),
+                            width: field(8),
+                            height: field(10),
+                            x_offset: field(12) as i16,
+                            y_offset: field(14) as i16,
+                            x_advance: field(16) as i16,
+                            page: record[18],
+                        };
+                        descriptor.glyphs.insert(character, glyph);
+                    }
+                }
+                // Kerning pairs: 10 bytes each.
+                5 => {
+                    for record in block.chunks_exact(10) {
+                        let first = read_u32(record, 0).and_then(char::from_u32);
+                        let second = read_u32(record, 4).and_then(char::from_u32);
+                        if let (Some(first), Some(second)) = (first, second) {
+                            let amount = read_u16(record, 8).unwrap_or_default() as i16;
+                            descriptor.kernings.insert((first, second), amount);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(descriptor)
+    }
+
+    /// Returns the name of the face the font was generated from, like `"Arial"`.
+    #[inline]
+    pub fn face(&self) -> &str {
+        &self.face
+    }
+
+    /// Returns the font size the glyphs were rendered at, in pixels.
+    #[inline]
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    /// Returns the distance between the baselines of two lines, in pixels at [Self::size].
+    #[inline]
+    pub fn line_height(&self) -> f32 {
+        self.line_height
+    }
+
+    /// Returns the distance from the top of a line to its baseline, in pixels at [Self::size].
+    #[inline]
+    pub fn base(&self) -> f32 {
+        self.base
+    }
+
+    /// Returns the file names of the page images, relative to the descriptor, in page order.
+    #[inline]
+    pub fn pages(&self) -> &[String] {
+        &self.pages
+    }
+}
+
+/// A font of pre-rendered glyphs, loaded from an AngelCode BMFont descriptor and its page
+/// textures, drawn by [Canvas::draw_text](crate::Canvas::draw_text) like a [Font](crate::Font).
+///
+/// Glyphs are copied from the pages with nearest-neighbour sampling, so they keep sharp pixels
+/// when drawn at a whole multiple of [BitmapFontDescriptor::size].  The [Paint](crate::Paint) of
+/// the text multiplies the colors of the pages, so white glyphs take its color.  Channel-packed
+/// fonts aren't supported: every glyph is drawn with all four channels of its page.
+///
+/// Cloning a [BitmapFont] is cheap: clones share their descriptor and pages.
+#[derive(Clone, Debug)]
+pub struct BitmapFont {
+    descriptor: Arc<BitmapFontDescriptor>,
+    pages: Arc<[wgpu::Texture]>,
+}
+
+impl BitmapFont {
+    /// Creates a [BitmapFont] from its descriptor and one texture for each of its pages, in the
+    /// order of [BitmapFontDescriptor::pages], e.g. loaded with
+    /// [Handle::make_texture](crate::Handle::make_texture).
+    ///
+    /// # Fails
+    /// Fails if the number of textures doesn't match the number of pages.
+    pub fn new<H: Handle>(
+        descriptor: BitmapFontDescriptor,
+        pages: Vec<OwnedTexture<'_, H>>,
+    ) -> Result<Self, Error> {
+        if pages.len() != descriptor.pages.len() {
+            return Err(invalid(format!(
+                "expected {} pages, got {}",
+                descriptor.pages.len(),
+                pages.len()
+            )));
+        }
+
+        Ok(Self {
+            descriptor: Arc::new(descriptor),
+            pages: pages
+                .into_iter()
+                .map(OwnedTexture::into_wgpu_texture)
+                .collect(),
+        })
+    }
+
+    /// Returns the descriptor of this [BitmapFont].
+    #[inline]
+    pub fn descriptor(&self) -> &BitmapFontDescriptor {
+        &self.descriptor
+    }
+
+    /// Returns the textures of the pages of this [BitmapFont].
+    #[inline]
+    pub(crate) fn wgpu_pages(&self) -> &[wgpu::Texture] {
+        &self.pages
+    }
+
+    /// Lays out *text* at the provided font size, with the baseline of its first line starting
+    /// at *position*, returning the page and quad of each visible glyph.
+    ///
+    /// Each `'\n'` starts a new line.  Characters the font has no glyph for are skipped.
+    pub(crate) fn glyph_instances(
+        &self,
+        size: f32,
+        position: Vec2,
+        text: &str,
+    ) -> Vec<(usize, GlyphInstance)> {
+        let descriptor = &self.descriptor;
+        let scale = if descriptor.size > 0.0 {
+            size / descriptor.size
+        } else {
+            1.0
+        };
+
+        // Snap the text to whole pixels, so glyphs drawn at their size keep sharp pixels.
+        let start_x = position.x.round();
+        let mut pen = Vec2::new(start_x, (position.y - descriptor.base * scale).round());
+        let mut previous = None;
+        let mut instances = Vec::new();
+        for character in text.chars() {
+            if character == '\n' {
+                pen = Vec2::new(start_x, pen.y + descriptor.line_height * scale);
+                previous = None;
+                continue;
+            }
+            let Some(glyph) = descriptor.glyphs.get(&character) else {
+                previous = None;
+                continue;
+            };
+
+            if let Some(amount) =
+                previous.and_then(|previous| descriptor.kernings.get(&(previous, character)))
+            {
+                pen.x += *amount as f32 * scale;
+            }
+
+            if glyph.width > 0 && glyph.height > 0 {
+                let min = pen + Vec2::new(glyph.x_offset as f32, glyph.y_offset as f32) * scale;
+                let (width, height) = (glyph.width as f32, glyph.height as f32);
+                instances.push((
+                    glyph.page as usize,
+                    GlyphInstance {
+                        origin: [min.x, min.y],
+                        x_axis: [width * scale, 0.0],
+                        y_axis: [0.0, height * scale],
+                        atlas_bounds: [
+                            glyph.x as f32,
+                            glyph.y as f32,
+                            glyph.x as f32 + width,
+                            glyph.y as f32 + height,
+                        ],
+                    },
+                ));
+            }
+
+            pen.x += glyph.x_advance as f32 * scale;
+            previous = Some(character);
+        }
+
+        instances
+    }
+}
+
+/// Returns an [Error::InvalidBitmapFont] with the provided message.
+#[inline]
+fn invalid(message: impl Into<String>) -> Error {
+    Error::InvalidBitmapFont(message.into())
+}
+
+/// Converts a character id of a descriptor to a [char].
+#[inline]
+fn to_char(id: i64) -> Option<char> {
+    u32::try_from(id).ok().and_then(char::from_u32)
+}
+
+/// Splits a line of a text descriptor into its tag and its `key=value` attributes, unquoting
+/// the values.
+fn parse_text_line(line: &str) -> (&str, Vec<(&str, &str)>) {
+    let line = line.trim();
+    let (tag, mut rest) = line.split_once(' ').unwrap_or((line, ""));
+
+    let mut attributes = Vec::new();
+    loop {
+        rest = rest.trim_start();
+        let Some((key, value)) = rest.split_once('=') else {
+            break;
+        };
+
+        let (value, remaining) = match value.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"').unwrap_or(quoted.len());
+                (&quoted[..end], quoted.get(end + 1..).unwrap_or_default())
+            }
+            None => value.split_once(' ').unwrap_or((value, "")),
+        };
+        attributes.push((key.trim(), value));
+        rest = remaining;
+    }
+
+    (tag, attributes)
+}
+
+/// Reads a little-endian [u16] at *offset*.
+#[inline]
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+/// Reads a little-endian [u32] at *offset*.
+#[inline]
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small descriptor in the text format, with a quoted face name holding a space, a
+    /// replacement glyph of id -1 and a kerning pair.
+    const TEXT_FONT: &str = r#"info face="Pixel Sans" size=-16 bold=0 italic=0
+common lineHeight=18 base=14 scaleW=64 scaleH=64 pages=2
+page id=1 file="font_1.png"
+page id=0 file="font_0.png"
+chars count=3
+char id=65 x=0 y=0 width=8 height=10 xoffset=1 yoffset=4 xadvance=9 page=0 chnl=15
+char id=86 x=10 y=0 width=9 height=10 xoffset=0 yoffset=4 xadvance=9 page=1 chnl=15
+char id=-1 x=20 y=0 width=8 height=10 xoffset=0 yoffset=4 xadvance=8 page=0 chnl=15
+kernings count=1
+kerning first=65 second=86 amount=-2
+"#;
+
+    /// Returns a block of the binary format.
+    fn block(kind: u8, contents: &[u8]) -> Vec<u8> {
+        let mut block = vec![kind];
+        block.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+        block.extend_from_slice(contents);
+        block
+    }
+
+    /// Returns the same font as [TEXT_FONT] in the binary format, without the replacement glyph.
+    fn binary_font() -> Vec<u8> {
+        let mut info = Vec::new();
+        info.extend_from_slice(&(-16i16).to_le_bytes());
+        info.extend_from_slice(&[0; 12]);
+        info.extend_from_slice(b"Pixel Sans\0");
+
+        let mut common = Vec::new();
+        for value in [18u16, 14, 64, 64, 2] {
+            common.extend_from_slice(&value.to_le_bytes());
+        }
+        common.extend_from_slice(&[0; 5]);
+
+        let mut chars = Vec::new();
+        for (id, x, width, page) in [(65u32, 0u16, 8u16, 0u8), (86, 10, 9, 1)] {
+            chars.extend_from_slice(&id.to_le_bytes());
+            for value in [x, 0, width, 10, (id == 65) as u16, 4, 9] {
+                chars.extend_from_slice(&value.to_le_bytes());
+            }
+            chars.extend_from_slice(&[page, 15]);
+        }
+
+        let mut kernings = Vec::new();
+        kernings.extend_from_slice(&65u32.to_le_bytes());
+        kernings.extend_from_slice(&86u32.to_le_bytes());
+        kernings.extend_from_slice(&(-2i16).to_le_bytes());
+
+        let mut data = b"BMF\x03".to_vec();
+        data.extend(block(1, &info));
+        data.extend(block(2, &common));
+        data.extend(block(3, b"font_0.png\0font_1.png\0"));
+        data.extend(block(4, &chars));
+        data.extend(block(5, &kernings));
+        data
+    }
+
+    /// Checks the parts of [TEXT_FONT] and [binary_font] the two formats share.
+    fn assert_parsed(descriptor: &BitmapFontDescriptor) {
+        assert_eq!(descriptor.face(), "Pixel Sans");
+        assert_eq!(descriptor.size(), 16.0);
+        assert_eq!(descriptor.line_height(), 18.0);
+        assert_eq!(descriptor.base(), 14.0);
+        assert_eq!(descriptor.pages(), ["font_0.png", "font_1.png"]);
+        assert_eq!(
+            descriptor.glyphs[&'A'],
+            BitmapGlyph {
+                x: 0,
+                y: 0,
+                width: 8,
+                height: 10,
+                x_offset: 1,
+                y_offset: 4,
+                x_advance: 9,
+                page: 0,
+            }
+        );
+        assert_eq!(descriptor.glyphs[&'V'].x, 10);
+        assert_eq!(descriptor.glyphs[&'V'].page, 1);
+        assert_eq!(descriptor.kernings[&('A', 'V')], -2);
+    }
+
+    #[test]
+    fn parses_text_font() {
+        let descriptor = BitmapFontDescriptor::from_bytes(TEXT_FONT.as_bytes()).unwrap();
+
+        assert_parsed(&descriptor);
+        assert_eq!(descriptor.glyphs.len(), 2);
+    }
+
+    #[test]
+    fn parses_binary_font() {
+        let descriptor = BitmapFontDescriptor::from_bytes(&binary_font()).unwrap();
+
+        assert_parsed(&descriptor);
+        assert_eq!(descriptor.glyphs.len(), 2);
+    }
+
+    #[test]
+    fn rejects_truncated_binary_blocks() {
+        let data = binary_font();
+        for len in [5, 8, 12, data.len() - 1] {
+            assert!(
+                matches!(
+                    BitmapFontDescriptor::from_bytes(&data[..len]),
+                    Err(Error::InvalidBitmapFont(_))
+                ),
+                "a font truncated to {len} bytes was accepted"
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_other_binary_versions() {
+        let mut data = binary_font();
+        data[3] = 2;
+
+        assert!(matches!(
+            BitmapFontDescriptor::from_bytes(&data),
+            Err(Error::InvalidBitmapFont(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_missing_pages() {
+        // The second glyph is on page 1, which is missing.
+        let missing_page = TEXT_FONT.replace("page id=1 file=\"font_1.png\"\n", "");
+        // No page at all.
+        let no_pages = TEXT_FONT
+            .lines()
+            .filter(|line| !line.starts_with("page ") && !line.starts_with("char "))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        for text in [missing_page, no_pages] {
+            assert!(matches!(
+                BitmapFontDescriptor::from_bytes(text.as_bytes()),
+                Err(Error::InvalidBitmapFont(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_numbers() {
+        let text = TEXT_FONT.replace("xadvance=9 page=0", "xadvance=nine page=0");
+
+        assert!(matches!(
+            BitmapFontDescriptor::from_bytes(text.as_bytes()),
+            Err(Error::InvalidBitmapFont(_))
+        ));
+    }
+}
@@ -0,0 +1,408 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+};
+
+use crate::{Error, Font, FontStretch, FontStyle, FontWeight};
+
+/// A family of fonts to look for in a [FontCollection], like in the CSS `font-family` property.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub enum FontFamily {
+    /// The family with this name, like `"DejaVu Sans"`.  Names are compared ignoring ASCII case.
+    Name(String),
+
+    /// The families [FontCollection::set_generic_family] sets for serif fonts.
+    Serif,
+
+    /// The families [FontCollection::set_generic_family] sets for sans-serif fonts.
+    SansSerif,
+
+    /// The families [FontCollection::set_generic_family] sets for monospace fonts.
+    Monospace,
+}
+
+impl FontFamily {
+    /// Returns the index of this generic family in `FontCollection::generic_families`.
+    #[inline]
+    const fn generic_index(&self) -> Option<usize> {
+        match self {
+            Self::Name(_) => None,
+            Self::Serif => Some(0),
+            Self::SansSerif => Some(1),
+            Self::Monospace => Some(2),
+        }
+    }
+}
+
+/// The font to look for in a [FontCollection], like the CSS font properties.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct FontQuery {
+    /// The families to look for, in order of preference.
+    pub families: Vec<FontFamily>,
+    pub weight: FontWeight,
+    pub style: FontStyle,
+    pub stretch: FontStretch,
+}
+
+impl FontQuery {
+    /// Creates a [FontQuery] for the normal font of a family.
+    #[inline]
+    pub fn new(family: FontFamily) -> Self {
+        Self {
+            families: vec![family],
+            weight: FontWeight::NORMAL,
+            style: FontStyle::Normal,
+            stretch: FontStretch::Normal,
+        }
+    }
+
+    /// Adds a family to look for if the previous ones aren't in the collection.
+    #[inline]
+    pub fn with_family(mut self, family: FontFamily) -> Self {
+        self.families.push(family);
+        self
+    }
+
+    /// Sets the weight to look for.
+    #[inline]
+    pub fn with_weight(mut self, weight: FontWeight) -> Self {
+        self.weight = weight;
+        self
+    }
+
+    /// Sets the style to look for.
+    #[inline]
+    pub fn with_style(mut self, style: FontStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Sets the stretch to look for.
+    #[inline]
+    pub fn with_stretch(mut self, stretch: FontStretch) -> Self {
+        self.stretch = stretch;
+        self
+    }
+
+    /// Returns how well a face matches the weight, style and stretch of this query, lower being
+    /// better.
+    ///
+    /// Like in the CSS font matching algorithm, the stretch is compared first, then the style,
+    /// then the weight.
+    fn match_key(&self, info: &FontFaceInfo) -> (u8, u32, u8, u8, u16) {
+        let (wanted, stretch) = (self.stretch.percentage(), info.stretch.percentage());
+        let stretch_key = match (self.stretch <= FontStretch::Normal, stretch <= wanted) {
+            // Narrower faces are preferred when looking for a narrow one, wider faces otherwise.
+            (true, true) => (0, wanted - stretch),
+            (true, false) => (1, stretch - wanted),
+            (false, false) => (0, stretch - wanted),
+            (false, true) => (1, wanted - stretch),
+        };
+
+        let style_key = match (self.style, info.style) {
+            (wanted, style) if wanted == style => 0,
+            (FontStyle::Italic, FontStyle::Oblique)
+            | (FontStyle::Oblique, FontStyle::Italic)
+            | (FontStyle::Normal, FontStyle::Oblique) => 1,
+            _ => 2,
+        };
+
+        let (wanted, weight) = (self.weight.0, info.weight.0);
+        let weight_key = if (400..=500).contains(&wanted) {
+            // Heavier faces up to 500 first, then lighter ones, then heavier ones.
+            if (wanted..=500).contains(&weight) {
+                (0, weight - wanted)
+            } else if weight < wanted {
+                (1, wanted - weight)
+            } else {
+                (2, weight - wanted)
+            }
+        } else if wanted < 400 {
+            // Lighter faces first, then heavier ones.
+            if weight <= wanted {
+                (0, wanted - weight)
+            } else {
+                (1, weight - wanted)
+            }
+        } else if weight >= wanted {
+            // Heavier faces first, then lighter ones.
+            (0, weight - wanted)
+        } else {
+            (1, wanted - weight)
+        };
+
+        (
+            stretch_key.0,
+            (stretch_key.1 * 2.0) as u32,
+            style_key,
+            weight_key.0,
+            weight_key.1,
+        )
+    }
+}
+
+impl Default for FontQuery {
+    #[inline]
+    fn default() -> Self {
+        Self::new(FontFamily::SansSerif)
+    }
+}
+
+/// The properties of a font in a [FontCollection], which it is matched by.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FontFaceInfo {
+    /// The name of the family of the font, empty if it has none.
+    pub family: String,
+    pub weight: FontWeight,
+    pub style: FontStyle,
+    pub stretch: FontStretch,
+}
+
+/// A font of a [FontCollection].
+#[derive(Debug)]
+struct FaceEntry {
+    info: FontFaceInfo,
+
+    /// The file and index the font is loaded from, [None] for fonts added already loaded.
+    source: Option<(PathBuf, u32)>,
+
+    /// The font, loaded when first needed.  [None] if it failed to load.
+    font: OnceLock<Option<Font>>,
+}
+
+impl FaceEntry {
+    /// Returns the [Font] of this entry, loading it from its file the first time.
+    fn font(&self) -> Option<&Font> {
+        self.font
+            .get_or_init(|| {
+                let (path, index) = self.source.as_ref()?;
+                Font::from_bytes_with_index(std::fs::read(path).ok()?, *index).ok()
+            })
+            .as_ref()
+    }
+}
+
+/// A set of [Fonts](Font) to pick from by family, weight, style and stretch, like the fonts
+/// available to a web page.
+///
+/// Fonts can be added from memory, from a directory or from the fonts installed on the system.
+/// Fonts found in files are only read once they are needed.  [TextLayouts](crate::TextLayout)
+/// made with a [FontCollection] fall back to its other fonts for the characters their font lacks,
+/// like CJK or emoji in Latin text.
+#[derive(Debug)]
+pub struct FontCollection {
+    faces: Vec<FaceEntry>,
+
+    /// The families of [FontFamily::Serif], [FontFamily::SansSerif] and [FontFamily::Monospace].
+    generic_families: [Vec<String>; 3],
+}
+
+impl FontCollection {
+    /// Creates an empty [FontCollection].
+    ///
+    /// The generic families are set to common families of Linux, macOS and Windows.
+    pub fn new() -> Self {
+        let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        Self {
+            faces: Vec::new(),
+            generic_families: [
+                names(&[
+                    "DejaVu Serif",
+                    "Noto Serif",
+                    "Liberation Serif",
+                    "Times New Roman",
+                    "Times",
+                ]),
+                names(&[
+                    "DejaVu Sans",
+                    "Noto Sans",
+                    "Liberation Sans",
+                    "Arial",
+                    "Helvetica",
+                ]),
+                names(&[
+                    "DejaVu Sans Mono",
+                    "Noto Sans Mono",
+                    "Liberation Mono",
+                    "Courier New",
+                    "Courier",
+                ]),
+            ],
+        }
+    }
+
+    /// Adds a [Font] to this collection.
+    pub fn add_font(&mut self, font: Font) {
+        self.faces.push(FaceEntry {
+            info: FontFaceInfo {
+                family: font.family().unwrap_or_default(),
+                weight: font.weight(),
+                style: font.style(),
+                stretch: font.stretch(),
+            },
+            source: None,
+            font: OnceLock::from(Some(font)),
+        });
+    }
+
+    /// Adds every font of the contents of a `.ttf`, `.otf`, `.ttc` or `.otc` file to this
+    /// collection, returning how many were added.
+    ///
+    /// # Fails
+    /// Fails if the data isn't a valid font or font collection.
+    pub fn add_font_data(&mut self, data: impl Into<Vec<u8>>) -> Result<usize, Error> {
+        let data = Arc::new(data.into());
+        let count = ttf_parser::fonts_in_collection(&data).unwrap_or(1);
+        for index in 0..count {
+            self.add_font(Font::from_shared_bytes(data.clone(), index)?);
+        }
+        Ok(count as usize)
+    }
+
+    /// Adds the fonts of the files in a directory and its subdirectories to this collection,
+    /// returning how many were added.  Files which aren't fonts are skipped.
+    pub fn load_fonts_dir(&mut self, path: impl AsRef<Path>) -> usize {
+        let mut database = fontdb::Database::new();
+        database.load_fonts_dir(path);
+        self.add_database(&database)
+    }
+
+    /// Adds the fonts installed on the system to this collection, returning how many were added.
+    ///
+    /// On Linux, the directories are read from the fontconfig configuration.  Nothing is
+    /// downloaded.
+    pub fn load_system_fonts(&mut self) -> usize {
+        let mut database = fontdb::Database::new();
+        database.load_system_fonts();
+        self.add_database(&database)
+    }
+
+    /// Adds the fonts found in files by a [`fontdb::Database`].
+    fn add_database(&mut self, database: &fontdb::Database) -> usize {
+        let count = self.faces.len();
+        self.faces.extend(database.faces().filter_map(|face| {
+            let path = match &face.source {
+                fontdb::Source::File(path) | fontdb::Source::SharedFile(path, _) => path,
+                fontdb::Source::Binary(_) => return None,
+            };
+            Some(FaceEntry {
+                info: FontFaceInfo {
+                    family: face
+                        .families
+                        .first()
+                        .map(|(family, _)| family.clone())
+                        .unwrap_or_default(),
+                    weight: FontWeight(face.weight.0),
+                    style: match face.style {
+                        fontdb::Style::Normal => FontStyle::Normal,
+                        fontdb::Style::Italic => FontStyle::Italic,
+                        fontdb::Style::Oblique => FontStyle::Oblique,
+                    },
+                    stretch: FontStretch::from_ttf_width(face.stretch),
+                },
+                source: Some((path.clone(), face.index)),
+                font: OnceLock::new(),
+            })
+        }));
+        self.faces.len() - count
+    }
+
+    /// Sets the families looked for in place of a generic [FontFamily], in order of preference.
+    ///
+    /// Does nothing for [FontFamily::Name].
+    pub fn set_generic_family(&mut self, generic: FontFamily, families: Vec<String>) {
+        if let Some(index) = generic.generic_index() {
+            self.generic_families[index] = families;
+        }
+    }
+
+    /// Returns the number of fonts in this collection.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// Returns `true` if this collection has no fonts.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_empty()
+    }
+
+    /// Returns the properties of the fonts of this collection.
+    #[inline]
+    pub fn faces(&self) -> impl Iterator<Item = &FontFaceInfo> + '_ {
+        self.faces.iter().map(|face| &face.info)
+    }
+
+    /// Returns the [Font] best matching *query*.
+    ///
+    /// The fonts of the first family of *query* found in this collection are matched by
+    /// stretch, style and weight like in CSS.  If no family is found, the sans-serif families
+    /// are looked for, then any family.  Returns [None] if no font of this collection can be
+    /// loaded.
+    pub fn query(&self, query: &FontQuery) -> Option<Font> {
+        self.ranked(query)
+            .into_iter()
+            .find_map(|index| self.faces[index].font())
+            .cloned()
+    }
+
+    /// Returns the [Font] best matching *query* which has a glyph for *character*.
+    #[inline]
+    pub fn font_for_char(&self, query: &FontQuery, character: char) -> Option<Font> {
+        self.fallback_font(query, character, &[])
+    }
+
+    /// Returns the [Font] best matching *query* which has a glyph for *character*, skipping the
+    /// fonts with the provided ids.
+    pub(crate) fn fallback_font(
+        &self,
+        query: &FontQuery,
+        character: char,
+        skipped_ids: &[u64],
+    ) -> Option<Font> {
+        self.ranked(query)
+            .into_iter()
+            .filter_map(|index| self.faces[index].font())
+            .find(|font| !skipped_ids.contains(&font.id()) && font.glyph_id(character).is_some())
+            .cloned()
+    }
+
+    /// Returns the indices of the faces of this collection, from the best to the worst match of
+    /// *query*.
+    fn ranked(&self, query: &FontQuery) -> Vec<usize> {
+        let families: Vec<&str> = query
+            .families
+            .iter()
+            .flat_map(|family| match family {
+                FontFamily::Name(name) => std::slice::from_ref(name),
+                generic => &self.generic_families[generic.generic_index().unwrap()][..],
+            })
+            // Like browsers, fall back to the default sans-serif font.
+            .chain(&self.generic_families[1])
+            .map(String::as_str)
+            .collect();
+
+        let mut ranked: Vec<_> = self
+            .faces
+            .iter()
+            .enumerate()
+            .map(|(index, face)| {
+                let family_rank = families
+                    .iter()
+                    .position(|family| family.eq_ignore_ascii_case(&face.info.family))
+                    .unwrap_or(families.len());
+                ((family_rank, query.match_key(&face.info)), index)
+            })
+            .collect();
+        ranked.sort_by_key(|(key, _)| *key);
+        ranked.into_iter().map(|(_, index)| index).collect()
+    }
+}
+
+impl Default for FontCollection {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,672 @@
+use ttf_parser::colr;
+
+use super::{
+    atlas::{GlyphBitmap, GlyphPixels},
+    font::glyph_path,
+};
+use crate::{
+    BlendMode, Canvas, Color, Dimension, Error, Font, GradientStop, Handle, LinearGradient,
+    MaskMode, Paint, Path, Rect, TextureDescriptor, TextureFormat, Transform, Vec2, Vertex,
+};
+
+/// How far the lines approximating the curves of a `COLR` glyph are allowed to stray from them,
+/// in pixels.
+const FLATTEN_TOLERANCE: f32 = 0.1;
+
+/// The number of rows of samples in each pixel when rasterizing the outlines of a `COLR` glyph.
+const COVERAGE_SAMPLES: u32 = 16;
+
+/// Decodes the embedded bitmap of a glyph from the strike with the provided pixels per em.
+///
+/// Returns the position of the bitmap relative to the glyph's origin, its size and its
+/// premultiplied `Rgba8Unorm` pixels, or [None] if the glyph has no bitmap in a supported format.
+/// Only PNG and premultiplied BGRA bitmaps have colors.
+pub(super) fn bitmap_glyph(
+    face: &ttf_parser::Face<'_>,
+    glyph_id: u16,
+    pixels_per_em: u16,
+) -> Option<GlyphBitmap> {
+    let image = face.glyph_raster_image(ttf_parser::GlyphId(glyph_id), pixels_per_em)?;
+    let (width, height, rgba) = match image.format {
+        ttf_parser::RasterImageFormat::PNG => decode_png(image.data)?,
+        ttf_parser::RasterImageFormat::BitmapPremulBgra32 => {
+            let (width, height) = (image.width as u32, image.height as u32);
+            let mut rgba = image.data.get(..(width * height * 4) as usize)?.to_vec();
+            for pixel in rgba.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+            (width, height, rgba)
+        }
+        _ => return None,
+    };
+
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    // The offsets point upwards, from the origin to the bottom-left corner of the bitmap.
+    Some((
+        [image.x as i32, -(image.y as i32 + height as i32)],
+        [width, height],
+        GlyphPixels::Data(rgba),
+    ))
+}
+
+/// Decodes a PNG image to premultiplied `Rgba8Unorm` pixels, returning its width and height along
+/// with them.
+fn decode_png(data: &[u8]) -> Option<(u32, u32, Vec<u8>)> {
+    let mut decoder = png::Decoder::new(data);
+    decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+    let mut reader = decoder.read_info().ok()?;
+    let mut buffer = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buffer).ok()?;
+    buffer.truncate(info.buffer_size());
+
+    let rgba: Vec<u8> = match info.color_type {
+        png::ColorType::Rgba => buffer,
+        png::ColorType::Rgb => buffer
+            .chunks_exact(3)
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2], 255])
+            .collect(),
+        png::ColorType::GrayscaleAlpha => buffer
+            .chunks_exact(2)
+            .flat_map(|pixel| [pixel[0], pixel[0], pixel[0], pixel[1]])
+            .collect(),
+        png::ColorType::Grayscale => buffer
+            .iter()
+            .flat_map(|&gray| [gray, gray, gray, 255])
+            .collect(),
+        // Palettes are expanded by the decoder.
+        png::ColorType::Indexed => return None,
+    };
+
+    let premultiplied = rgba
+        .chunks_exact(4)
+        .flat_map(|pixel| {
+            let alpha = pixel[3] as u32;
+            let premultiply = |channel: u8| ((channel as u32 * alpha + 127) / 255) as u8;
+            [
+                premultiply(pixel[0]),
+                premultiply(pixel[1]),
+                premultiply(pixel[2]),
+                pixel[3],
+            ]
+        })
+        .collect();
+
+    Some((info.width, info.height, premultiplied))
+}
+
+/// Draws the colored layers of a glyph from the `COLR` table at the provided font size, with
+/// the paths and [Paints](Paint) of a [Canvas].
+///
+/// *foreground* is the color of the layers which use the color of the text.  Returns the
+/// position of the bitmap relative to the glyph's origin, its size and a premultiplied
+/// `Rgba8Unorm` texture holding it, or [None] if the glyph has nothing to draw.
+///
+/// Layers are composited with [BlendMode::SourceOver], or [BlendMode::Additive] for the `plus`
+/// composite mode, as G2d has no other blend modes.  Two-point conical gradients are drawn as
+/// radial gradients around their end circle, and every gradient pads its colors.
+///
+/// # Fails
+/// Fails if the glyph is larger than the textures of the [Handle] can be.
+pub(super) fn colr_glyph<H: Handle>(
+    handle: &H,
+    face: &ttf_parser::Face<'_>,
+    glyph_id: u16,
+    size: f32,
+    foreground: Color,
+) -> Result<Option<GlyphBitmap>, Error> {
+    let glyph_id = ttf_parser::GlyphId(glyph_id);
+    let [red, green, blue, alpha] = foreground.to_rgba_bytes();
+    let foreground = ttf_parser::RgbaColor::new(red, green, blue, alpha);
+
+    // Font units point upwards, pixels downwards.
+    let scale = Font::scale(face, size);
+    let to_pixels = Transform::scale(Vec2::new(scale, -scale));
+
+    // Find the pixels the glyph covers first, to size its texture.
+    let mut bounds_painter = BoundsPainter {
+        face,
+        transforms: vec![to_pixels],
+        outline: None,
+        clips: Vec::new(),
+        bounds: None,
+    };
+    if face
+        .paint_color_glyph(glyph_id, 0, foreground, &mut bounds_painter)
+        .is_none()
+    {
+        return Ok(None);
+    }
+    let Some(bounds) = bounds_painter.bounds else {
+        return Ok(None);
+    };
+
+    let (min, max) = (bounds.min(), bounds.max());
+    let (left, top) = (min.x.floor(), min.y.floor());
+    let (width, height) = ((max.x.ceil() - left) as u32, (max.y.ceil() - top) as u32);
+    if width == 0 || height == 0 {
+        return Ok(None);
+    }
+    if width.max(height) > handle.wgpu_device().limits().max_texture_dimension_2d {
+        return Err(Error::GlyphAtlasFull);
+    }
+
+    let wgpu_texture = handle
+        .wgpu_device()
+        .create_texture(&wgpu::TextureDescriptor {
+            label: Some("Color Glyph"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8Unorm,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+
+    let mut painter = ColrPainter {
+        handle,
+        canvas: Canvas::from_raw_parts(handle, &wgpu_texture),
+        face,
+        size: [width, height],
+        transforms: vec![Transform::translate(Vec2::new(-left, -top)) * to_pixels],
+        outline: None,
+        clips: Vec::new(),
+        layers: Vec::new(),
+        error: None,
+    };
+    painter.canvas.set_blend_mode(BlendMode::SourceOver);
+    face.paint_color_glyph(glyph_id, 0, foreground, &mut painter);
+    let error = painter.error.take();
+    drop(painter);
+    if let Some(err) = error {
+        return Err(err);
+    }
+
+    Ok(Some((
+        [left as i32, top as i32],
+        [width, height],
+        GlyphPixels::Texture(wgpu_texture),
+    )))
+}
+
+/// Returns the [Path] of a clip box, transformed by *transform*.
+fn clip_box_path(clip_box: colr::ClipBox, transform: Transform) -> Path {
+    let mut path = Path::new();
+    path.move_to(transform.transform_point(Vec2::new(clip_box.x_min, clip_box.y_min)))
+        .line_to(transform.transform_point(Vec2::new(clip_box.x_max, clip_box.y_min)))
+        .line_to(transform.transform_point(Vec2::new(clip_box.x_max, clip_box.y_max)))
+        .line_to(transform.transform_point(Vec2::new(clip_box.x_min, clip_box.y_max)))
+        .close();
+    path
+}
+
+/// Converts a `COLR` transform to a [Transform].
+#[inline]
+fn to_transform(transform: ttf_parser::Transform) -> Transform {
+    Transform::new(
+        Vec2::new(transform.a, transform.b),
+        Vec2::new(transform.c, transform.d),
+        Vec2::new(transform.e, transform.f),
+    )
+}
+
+/// Finds the pixels a `COLR` glyph paints, without painting them.
+struct BoundsPainter<'f, 'a> {
+    face: &'f ttf_parser::Face<'a>,
+
+    /// The transforms pushed so far, mapping font units to pixels.  The last one is current.
+    transforms: Vec<Transform>,
+
+    /// The bounds of the outline paints fill, [None] to fill every pixel within the clips.
+    outline: Option<Rect>,
+
+    /// The bounds of the clips pushed so far, each within the previous one.
+    clips: Vec<Rect>,
+
+    /// The bounds of every pixel painted so far.
+    bounds: Option<Rect>,
+}
+
+impl<'f, 'a> BoundsPainter<'f, 'a> {
+    /// Returns the current transform.
+    #[inline]
+    fn transform(&self) -> Transform {
+        *self.transforms.last().unwrap()
+    }
+
+    /// Pushes a clip with the provided bounds.
+    fn push_clip_bounds(&mut self, bounds: Rect) {
+        let clip = match self.clips.last() {
+            Some(previous) => bounds.intersection(previous),
+            None => bounds,
+        };
+        self.clips.push(clip);
+    }
+}
+
+impl<'a> colr::Painter<'a> for BoundsPainter<'_, 'a> {
+    fn outline_glyph(&mut self, glyph_id: ttf_parser::GlyphId) {
+        let path = glyph_path(self.face, glyph_id, self.transform());
+        self.outline = (!path.is_empty()).then(|| path.bounds());
+    }
+
+    fn paint(&mut self, _: colr::Paint<'a>) {
+        let painted = match (self.outline, self.clips.last()) {
+            (Some(outline), Some(clip)) => outline.intersection(clip),
+            (Some(outline), None) => outline,
+            (None, Some(clip)) => *clip,
+            // Unbounded paints can't size a bitmap.
+            (None, None) => return,
+        };
+        if painted.is_empty() {
+            return;
+        }
+
+        self.bounds = Some(match self.bounds {
+            Some(bounds) => {
+                let (min, max) = (bounds.min(), bounds.max());
+                let (painted_min, painted_max) = (painted.min(), painted.max());
+                Rect::from_min_max(
+                    Vec2::new(min.x.min(painted_min.x), min.y.min(painted_min.y)),
+                    Vec2::new(max.x.max(painted_max.x), max.y.max(painted_max.y)),
+                )
+            }
+            None => painted,
+        });
+    }
+
+    fn push_clip(&mut self) {
+        let bounds = self.outline.unwrap_or_default();
+        self.push_clip_bounds(bounds);
+    }
+
+    fn push_clip_box(&mut self, clip_box: colr::ClipBox) {
+        let bounds = clip_box_path(clip_box, self.transform()).bounds();
+        self.push_clip_bounds(bounds);
+    }
+
+    fn pop_clip(&mut self) {
+        self.clips.pop();
+        self.outline = None;
+    }
+
+    fn push_layer(&mut self, _: colr::CompositeMode) {}
+
+    fn pop_layer(&mut self) {}
+
+    fn push_transform(&mut self, transform: ttf_parser::Transform) {
+        self.transforms
+            .push(self.transform() * to_transform(transform));
+    }
+
+    fn pop_transform(&mut self) {
+        self.transforms.pop();
+    }
+}
+
+/// Paints a `COLR` glyph onto a [Canvas].
+///
+/// Outlines and clips are rasterized into coverage masks, through which the [Paints](Paint) are
+/// drawn with [Canvas::push_mask].
+struct ColrPainter<'c, 'f, 'a, H: Handle> {
+    handle: &'c H,
+    canvas: Canvas<'c, H>,
+    face: &'f ttf_parser::Face<'a>,
+
+    /// The width and height of the [Canvas], in pixels.
+    size: [u32; 2],
+
+    /// The transforms pushed so far, mapping font units to the pixels of the [Canvas].  The last
+    /// one is current.
+    transforms: Vec<Transform>,
+
+    /// The outline paints fill, [None] to fill every pixel within the clips.
+    outline: Option<Path>,
+
+    /// The coverage of the clips pushed so far, each within the previous one.
+    clips: Vec<Vec<f32>>,
+
+    /// The blend modes of the layers pushed so far.
+    layers: Vec<BlendMode>,
+
+    /// The first error painting ran into.  Painting stops once it is set.
+    error: Option<Error>,
+}
+
+impl<'c, 'f, 'a, H: Handle> ColrPainter<'c, 'f, 'a, H> {
+    /// Returns the current transform.
+    #[inline]
+    fn transform(&self) -> Transform {
+        *self.transforms.last().unwrap()
+    }
+
+    /// Returns the coverage of a [Path] within the current clip, or of the whole clip if there
+    /// is no path.
+    fn clipped_coverage(&self, path: Option<&Path>) -> Vec<f32> {
+        let [width, height] = self.size;
+        let mut coverage = match path {
+            Some(path) => path_coverage(path, width, height),
+            None => vec![1.0; (width * height) as usize],
+        };
+        if let Some(clip) = self.clips.last() {
+            for (coverage, clip) in coverage.iter_mut().zip(clip) {
+                *coverage *= clip;
+            }
+        }
+        coverage
+    }
+
+    /// Fills the pixels of the [Canvas] with a [Paint], weighted by *coverage*.
+    fn fill(&mut self, paint: Paint, coverage: &[f32]) -> Result<(), Error> {
+        let [width, height] = self.size;
+        let mask_data: Vec<u8> = coverage
+            .iter()
+            .flat_map(|coverage| [255, 255, 255, (coverage * 255.0).round() as u8])
+            .collect();
+        let mask = self.handle.make_texture_with_descriptor(
+            &TextureDescriptor::new(Dimension::new(width, height))
+                .with_format(TextureFormat::Rgba8Unorm),
+            &mask_data,
+        )?;
+
+        // Two triangles covering the whole canvas, counter-clockwise.
+        let corner = |x: f32, y: f32| Vertex::new(Vec2::new(x, y), Vec2::default(), Color::WHITE);
+        let vertices = self.handle.make_vertex_buffer(&[
+            corner(-1.0, -1.0),
+            corner(1.0, -1.0),
+            corner(1.0, 1.0),
+            corner(-1.0, -1.0),
+            corner(1.0, 1.0),
+            corner(-1.0, 1.0),
+        ]);
+
+        self.canvas
+            .push_mask(&mask, MaskMode::Alpha, Transform::IDENTITY)?;
+        self.canvas.draw_vertices(&vertices, paint)?;
+        self.canvas.pop_mask()
+    }
+
+    /// Runs a fallible painting step, keeping its error if it is the first one.
+    fn run(&mut self, step: impl FnOnce(&mut Self) -> Result<(), Error>) {
+        if self.error.is_none() {
+            if let Err(err) = step(self) {
+                self.error = Some(err);
+            }
+        }
+    }
+}
+
+impl<'a, H: Handle> colr::Painter<'a> for ColrPainter<'_, '_, 'a, H> {
+    fn outline_glyph(&mut self, glyph_id: ttf_parser::GlyphId) {
+        self.outline = Some(glyph_path(self.face, glyph_id, self.transform()));
+    }
+
+    fn paint(&mut self, paint: colr::Paint<'a>) {
+        let Some(paint) = convert_paint(&paint, self.transform()) else {
+            return;
+        };
+        let coverage = self.clipped_coverage(self.outline.as_ref());
+        self.run(|painter| painter.fill(paint, &coverage));
+    }
+
+    fn push_clip(&mut self) {
+        let coverage = match &self.outline {
+            Some(outline) => self.clipped_coverage(Some(outline)),
+            None => vec![0.0; (self.size[0] * self.size[1]) as usize],
+        };
+        self.clips.push(coverage);
+    }
+
+    fn push_clip_box(&mut self, clip_box: colr::ClipBox) {
+        let path = clip_box_path(clip_box, self.transform());
+        let coverage = self.clipped_coverage(Some(&path));
+        self.clips.push(coverage);
+    }
+
+    fn pop_clip(&mut self) {
+        self.clips.pop();
+        self.outline = None;
+    }
+
+    fn push_layer(&mut self, mode: colr::CompositeMode) {
+        self.layers.push(match mode {
+            colr::CompositeMode::Plus => BlendMode::Additive,
+            _ => BlendMode::SourceOver,
+        });
+        self.run(|painter| painter.canvas.push_layer(&[]));
+    }
+
+    fn pop_layer(&mut self) {
+        let blend_mode = self.layers.pop().unwrap_or(BlendMode::SourceOver);
+        self.run(|painter| {
+            painter.canvas.save();
+            painter.canvas.set_blend_mode(blend_mode);
+            let result = painter.canvas.pop_layer();
+            painter.canvas.restore();
+            result
+        });
+    }
+
+    fn push_transform(&mut self, transform: ttf_parser::Transform) {
+        self.transforms
+            .push(self.transform() * to_transform(transform));
+    }
+
+    fn pop_transform(&mut self) {
+        self.transforms.pop();
+    }
+}
+
+/// Rasterizes the coverage of a [Path] within a bitmap of the provided size, in rows of pixels,
+/// with the non-zero fill rule.
+///
+/// Unlike the rasterizer of plain glyphs, this counts the winding of each sample, as the layers
+/// of color glyphs often abut contours which wind in opposite directions.
+fn path_coverage(path: &Path, width: u32, height: u32) -> Vec<f32> {
+    // The edges of the path, from top to bottom, with +1 if they go down and -1 otherwise.
+    let mut edges = Vec::new();
+    for contour in path.flatten(FLATTEN_TOLERANCE) {
+        for (index, &start) in contour.iter().enumerate() {
+            let end = contour[(index + 1) % contour.len()];
+            if start.y < end.y {
+                edges.push((start, end, 1));
+            } else if start.y > end.y {
+                edges.push((end, start, -1));
+            }
+        }
+    }
+
+    let mut coverage = vec![0.0; (width * height) as usize];
+    let mut crossings: Vec<(f32, i32)> = Vec::new();
+    for (y, row) in coverage.chunks_exact_mut(width as usize).enumerate() {
+        for sample in 0..COVERAGE_SAMPLES {
+            let sample_y = y as f32 + (sample as f32 + 0.5) / COVERAGE_SAMPLES as f32;
+
+            crossings.clear();
+            crossings.extend(
+                edges
+                    .iter()
+                    .filter(|(top, bottom, _)| top.y <= sample_y && sample_y < bottom.y)
+                    .map(|&(top, bottom, winding)| {
+                        let t = (sample_y - top.y) / (bottom.y - top.y);
+                        (top.x + t * (bottom.x - top.x), winding)
+                    }),
+            );
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            // Fill the spans between crossings where the winding isn't zero.
+            let mut winding = 0;
+            let mut span_start = 0.0;
+            for &(x, crossing_winding) in &crossings {
+                if winding == 0 {
+                    span_start = x;
+                }
+                winding += crossing_winding;
+                if winding == 0 {
+                    add_span(row, span_start, x, 1.0 / COVERAGE_SAMPLES as f32);
+                }
+            }
+        }
+    }
+
+    for coverage in &mut coverage {
+        *coverage = coverage.min(1.0);
+    }
+    coverage
+}
+
+/// Adds *weight* to the pixels of *row* between *start* and *end*, in proportion to how much of
+/// each pixel the span covers.
+fn add_span(row: &mut [f32], start: f32, end: f32, weight: f32) {
+    let (start, end) = (
+        start.clamp(0.0, row.len() as f32),
+        end.clamp(0.0, row.len() as f32),
+    );
+    if start >= end {
+        return;
+    }
+
+    let (first, last) = (start as usize, (end.ceil() as usize).max(1) - 1);
+    if first == last {
+        row[first] += (end - start) * weight;
+        return;
+    }
+
+    row[first] += (first as f32 + 1.0 - start) * weight;
+    for pixel in &mut row[first + 1..last] {
+        *pixel += weight;
+    }
+    row[last] += (end - last as f32) * weight;
+}
+
+/// Converts a `COLR` paint to a [Paint] in the pixels of the [Canvas], given the current
+/// transform.  Returns [None] if it paints nothing.
+fn convert_paint(paint: &colr::Paint<'_>, transform: Transform) -> Option<Paint> {
+    match paint {
+        colr::Paint::Solid(color) => Some(Paint::Solid(to_color(*color))),
+        colr::Paint::LinearGradient(gradient) => {
+            let stops = collect_stops(gradient.stops(0, &[]));
+            let (first, last) = (stops.first()?.0, stops.last()?.0);
+
+            // The colors change along p0 -> p1 and stay constant parallel to p0 -> p2, so the
+            // gradient ends at p1 projected onto the normal of p0 -> p2.
+            let p0 = transform.transform_point(Vec2::new(gradient.x0, gradient.y0));
+            let p1 = transform.transform_point(Vec2::new(gradient.x1, gradient.y1));
+            let p2 = transform.transform_point(Vec2::new(gradient.x2, gradient.y2));
+            let (direction, parallel) = (p1 - p0, p2 - p0);
+            let parallel_length_squared = dot(parallel, parallel);
+            let end = if parallel_length_squared > 0.0 {
+                p1 - parallel * (dot(direction, parallel) / parallel_length_squared)
+            } else {
+                p1
+            };
+
+            // Stops may lie outside of [0, 1], so move the ends of the gradient onto them.
+            Some(Paint::linear_gradient(
+                p0.lerp(end, first),
+                p0.lerp(end, last),
+                normalize_stops(stops, first, last),
+            ))
+        }
+        colr::Paint::RadialGradient(gradient) => {
+            // Approximate the two-point conical gradient with a radial one around the end
+            // circle, interpolating the radii of the stops.
+            let radius_scale = transform.determinant().abs().sqrt();
+            let mut stops: Vec<_> = collect_stops(gradient.stops(0, &[]))
+                .into_iter()
+                .map(|(offset, color)| {
+                    let radius = gradient.r0 + offset * (gradient.r1 - gradient.r0);
+                    (radius.max(0.0) * radius_scale, color)
+                })
+                .collect();
+            stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+            let radius = stops.last()?.0;
+            if radius <= 0.0 {
+                return None;
+            }
+
+            Some(Paint::radial_gradient(
+                transform.transform_point(Vec2::new(gradient.x1, gradient.y1)),
+                radius,
+                normalize_stops(stops, 0.0, radius),
+            ))
+        }
+        colr::Paint::SweepGradient(gradient) => {
+            let stops = collect_stops(gradient.stops(0, &[]));
+            let (first, last) = (stops.first()?.0, stops.last()?.0);
+
+            // Angles are in half turns, counter-clockwise as the y axis of fonts points up.
+            let angle = |offset: f32| {
+                (gradient.start_angle + offset * (gradient.end_angle - gradient.start_angle))
+                    * std::f32::consts::PI
+            };
+            let (start, end) = (angle(first), angle(last));
+            let direction = transform.transform_vector(Vec2::new(start.cos(), start.sin()));
+            let start_angle = direction.y.atan2(direction.x);
+
+            // Flipping the y axis, as fonts do, turns counter-clockwise sweeps clockwise.
+            let span = (end - start) * transform.determinant().signum();
+            Some(Paint::sweep_gradient(
+                transform.transform_point(Vec2::new(gradient.center_x, gradient.center_y)),
+                start_angle,
+                start_angle + span,
+                normalize_stops(stops, first, last),
+            ))
+        }
+    }
+}
+
+/// Collects the stops of a `COLR` gradient as offsets and [Colors](Color), sorted by offset.
+fn collect_stops(stops: impl Iterator<Item = colr::ColorStop>) -> Vec<(f32, Color)> {
+    let mut stops: Vec<_> = stops
+        .map(|stop| (stop.stop_offset, to_color(stop.color)))
+        .collect();
+    stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+    stops
+}
+
+/// Maps the offsets of sorted stops from `[start, end]` to `[0, 1]`, keeping at most
+/// [LinearGradient::MAX_STOPS] of them.
+fn normalize_stops(stops: Vec<(f32, Color)>, start: f32, end: f32) -> Vec<GradientStop> {
+    let span = end - start;
+    let count = stops.len().min(LinearGradient::MAX_STOPS);
+    (0..count)
+        .map(|index| {
+            // Spread the kept stops evenly, always keeping the first and last ones.
+            let (offset, color) = if count > 1 {
+                stops[index * (stops.len() - 1) / (count - 1)]
+            } else {
+                stops[0]
+            };
+            GradientStop {
+                offset: if span > 0.0 {
+                    (offset - start) / span
+                } else {
+                    0.0
+                },
+                color,
+            }
+        })
+        .collect()
+}
+
+/// Converts a `COLR` color to a [Color].
+#[inline]
+fn to_color(color: ttf_parser::RgbaColor) -> Color {
+    Color::from_rgba8(color.red, color.green, color.blue, color.alpha)
+}
+
+/// Returns the dot product of two vectors.
+#[inline]
+fn dot(a: Vec2, b: Vec2) -> f32 {
+    a.x * b.x + a.y * b.y
+}
@@ -0,0 +1,382 @@
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use crate::{BitmapFont, Error, Path, Transform, Vec2};
+
+/// The id of the next [Font] to be loaded.
+static NEXT_FONT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// How thick the strokes of a [Font] are, from `1` to `1000`, like the CSS `font-weight`
+/// property.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FontWeight(pub u16);
+
+impl FontWeight {
+    pub const THIN: Self = Self(100);
+    pub const EXTRA_LIGHT: Self = Self(200);
+    pub const LIGHT: Self = Self(300);
+    pub const NORMAL: Self = Self(400);
+    pub const MEDIUM: Self = Self(500);
+    pub const SEMI_BOLD: Self = Self(600);
+    pub const BOLD: Self = Self(700);
+    pub const EXTRA_BOLD: Self = Self(800);
+    pub const BLACK: Self = Self(900);
+}
+
+impl Default for FontWeight {
+    #[inline]
+    fn default() -> Self {
+        Self::NORMAL
+    }
+}
+
+/// Whether a [Font] is slanted, like the CSS `font-style` property.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum FontStyle {
+    #[default]
+    Normal,
+
+    /// Slanted, with cursive letterforms.
+    Italic,
+
+    /// Slanted, with the same letterforms as [FontStyle::Normal].
+    Oblique,
+}
+
+/// How wide the glyphs of a [Font] are, like the CSS `font-stretch` property.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum FontStretch {
+    UltraCondensed,
+    ExtraCondensed,
+    Condensed,
+    SemiCondensed,
+    #[default]
+    Normal,
+    SemiExpanded,
+    Expanded,
+    ExtraExpanded,
+    UltraExpanded,
+}
+
+impl FontStretch {
+    /// Returns the width of this [FontStretch] as a percentage of [FontStretch::Normal].
+    #[inline]
+    pub const fn percentage(&self) -> f32 {
+        match self {
+            Self::UltraCondensed => 50.0,
+            Self::ExtraCondensed => 62.5,
+            Self::Condensed => 75.0,
+            Self::SemiCondensed => 87.5,
+            Self::Normal => 100.0,
+            Self::SemiExpanded => 112.5,
+            Self::Expanded => 125.0,
+            Self::ExtraExpanded => 150.0,
+            Self::UltraExpanded => 200.0,
+        }
+    }
+
+    /// Converts the width class of the `OS/2` table of a font to a [FontStretch].
+    pub(crate) const fn from_ttf_width(width: ttf_parser::Width) -> Self {
+        match width {
+            ttf_parser::Width::UltraCondensed => Self::UltraCondensed,
+            ttf_parser::Width::ExtraCondensed => Self::ExtraCondensed,
+            ttf_parser::Width::Condensed => Self::Condensed,
+            ttf_parser::Width::SemiCondensed => Self::SemiCondensed,
+            ttf_parser::Width::Normal => Self::Normal,
+            ttf_parser::Width::SemiExpanded => Self::SemiExpanded,
+            ttf_parser::Width::Expanded => Self::Expanded,
+            ttf_parser::Width::ExtraExpanded => Self::ExtraExpanded,
+            ttf_parser::Width::UltraExpanded => Self::UltraExpanded,
+        }
+    }
+}
+
+/// A TrueType or OpenType font.
+///
+/// Cloning a [Font] is cheap: clones share their data, and the glyphs of one are cached for all of
+/// them.
+#[derive(Clone)]
+pub struct Font {
+    data: Arc<Vec<u8>>,
+    index: u32,
+
+    /// Identifies the glyphs of this font in a [GlyphAtlas](crate::GlyphAtlas).
+    id: u64,
+}
+
+impl Font {
+    /// Loads a [Font] from the contents of a `.ttf` or `.otf` file.
+    ///
+    /// # Fails
+    /// Fails if the data isn't a valid font.
+    #[inline]
+    pub fn from_bytes(data: impl Into<Vec<u8>>) -> Result<Self, Error> {
+        Self::from_bytes_with_index(data, 0)
+    }
+
+    /// Loads the font at *index* of a font collection (`.ttc` or `.otc` file).
+    ///
+    /// # Fails
+    /// Fails if the data isn't a valid font or has no font at *index*.
+    #[inline]
+    pub fn from_bytes_with_index(data: impl Into<Vec<u8>>, index: u32) -> Result<Self, Error> {
+        Self::from_shared_bytes(Arc::new(data.into()), index)
+    }
+
+    /// Loads the font at *index* of data shared with other [Fonts](Font), like the other fonts
+    /// of a font collection.
+    ///
+    /// # Fails
+    /// Fails if the data isn't a valid font or has no font at *index*.
+    pub(crate) fn from_shared_bytes(data: Arc<Vec<u8>>, index: u32) -> Result<Self, Error> {
+        ttf_parser::Face::parse(&data, index).map_err(|err| Error::InvalidFont(err.to_string()))?;
+
+        Ok(Self {
+            data,
+            index,
+            id: NEXT_FONT_ID.fetch_add(1, Ordering::Relaxed),
+        })
+    }
+
+    /// Returns the data this [Font] was loaded from.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Returns the index of this [Font] in its font collection, `0` if it isn't part of one.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Returns the id identifying the glyphs of this [Font] in a cache.
+    #[inline]
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Returns the parsed tables of this [Font].
+    #[inline]
+    pub(crate) fn face(&self) -> ttf_parser::Face<'_> {
+        ttf_parser::Face::parse(&self.data, self.index).expect("the font was validated when loaded")
+    }
+
+    /// Returns the factor converting font units to pixels at the provided font size.
+    #[inline]
+    pub(crate) fn scale(face: &ttf_parser::Face<'_>, size: f32) -> f32 {
+        size / face.units_per_em() as f32
+    }
+
+    /// Returns the name of the family of this [Font], like `"DejaVu Sans"`, if it has one.
+    ///
+    /// The typographic family is preferred, so the bold and condensed faces of a family share its
+    /// name.
+    pub fn family(&self) -> Option<String> {
+        let face = self.face();
+        let names = || face.names().into_iter().filter(|name| name.is_unicode());
+        [
+            ttf_parser::name_id::TYPOGRAPHIC_FAMILY,
+            ttf_parser::name_id::FAMILY,
+        ]
+        .into_iter()
+        .find_map(|name_id| {
+            let mut names = names().filter(|name| name.name_id == name_id);
+            names
+                .clone()
+                .find(|name| name.language() == ttf_parser::Language::English_UnitedStates)
+                .or_else(|| names.next())
+                .and_then(|name| name.to_string())
+        })
+    }
+
+    /// Returns the weight of this [Font].
+    #[inline]
+    pub fn weight(&self) -> FontWeight {
+        FontWeight(self.face().weight().to_number())
+    }
+
+    /// Returns whether this [Font] is slanted.
+    #[inline]
+    pub fn style(&self) -> FontStyle {
+        match self.face().style() {
+            ttf_parser::Style::Normal => FontStyle::Normal,
+            ttf_parser::Style::Italic => FontStyle::Italic,
+            ttf_parser::Style::Oblique => FontStyle::Oblique,
+        }
+    }
+
+    /// Returns how wide the glyphs of this [Font] are.
+    #[inline]
+    pub fn stretch(&self) -> FontStretch {
+        FontStretch::from_ttf_width(self.face().width())
+    }
+
+    /// Returns the number of font units per em square.
+    #[inline]
+    pub fn units_per_em(&self) -> u16 {
+        self.face().units_per_em()
+    }
+
+    /// Returns how far glyphs reach above the baseline at the provided font size, in pixels.
+    #[inline]
+    pub fn ascent(&self, size: f32) -> f32 {
+        let face = self.face();
+        face.ascender() as f32 * Self::scale(&face, size)
+    }
+
+    /// Returns how far glyphs reach below the baseline at the provided font size, in pixels.
+    ///
+    /// The descent is usually negative, as it's measured upwards.
+    #[inline]
+    pub fn descent(&self, size: f32) -> f32 {
+        let face = self.face();
+        face.descender() as f32 * Self::scale(&face, size)
+    }
+
+    /// Returns the distance between the baselines of two lines at the provided font size, in
+    /// pixels.
+    #[inline]
+    pub fn line_height(&self, size: f32) -> f32 {
+        let face = self.face();
+        (face.ascender() - face.descender() + face.line_gap()) as f32 * Self::scale(&face, size)
+    }
+
+    /// Returns the id of the glyph this [Font] draws for a character, if it has one.
+    #[inline]
+    pub fn glyph_id(&self, character: char) -> Option<u16> {
+        self.face().glyph_index(character).map(|id| id.0)
+    }
+
+    /// Returns how far the pen moves after drawing a glyph at the provided font size, in pixels.
+    #[inline]
+    pub fn advance(&self, glyph_id: u16, size: f32) -> f32 {
+        let face = self.face();
+        face.glyph_hor_advance(ttf_parser::GlyphId(glyph_id))
+            .unwrap_or(0) as f32
+            * Self::scale(&face, size)
+    }
+
+    /// Returns the outline of a glyph at the provided font size as a [Path], in pixels relative
+    /// to its origin on the baseline.
+    ///
+    /// The path is empty if the glyph has no outline, like a space or a bitmap glyph.  Layered
+    /// color glyphs return the outline of their fallback glyph.
+    pub fn glyph_outline(&self, glyph_id: u16, size: f32) -> Path {
+        let face = self.face();
+        let scale = Self::scale(&face, size);
+        glyph_path(
+            &face,
+            ttf_parser::GlyphId(glyph_id),
+            Transform::scale(Vec2::new(scale, -scale)),
+        )
+    }
+}
+
+/// Builds a [Path] from the outline of a glyph, transforming its points.
+struct PathBuilder {
+    path: Path,
+
+    /// Maps font units to the points of the path.
+    transform: Transform,
+}
+
+impl PathBuilder {
+    #[inline]
+    fn point(&self, x: f32, y: f32) -> Vec2 {
+        self.transform.transform_point(Vec2::new(x, y))
+    }
+}
+
+impl ttf_parser::OutlineBuilder for PathBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        let point = self.point(x, y);
+        self.path.move_to(point);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let point = self.point(x, y);
+        self.path.line_to(point);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (control, point) = (self.point(x1, y1), self.point(x, y));
+        self.path.quad_to(control, point);
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (control1, control2, point) =
+            (self.point(x1, y1), self.point(x2, y2), self.point(x, y));
+        self.path.cubic_to(control1, control2, point);
+    }
+
+    fn close(&mut self) {
+        self.path.close();
+    }
+}
+
+/// Returns the outline of a glyph as a [Path], transformed by *transform*.
+pub(super) fn glyph_path(
+    face: &ttf_parser::Face<'_>,
+    glyph_id: ttf_parser::GlyphId,
+    transform: Transform,
+) -> Path {
+    let mut path = Path::new();
+    append_glyph_path(face, glyph_id, transform, &mut path);
+    path
+}
+
+/// Appends the outline of a glyph, transformed by *transform*, to *path*.
+pub(super) fn append_glyph_path(
+    face: &ttf_parser::Face<'_>,
+    glyph_id: ttf_parser::GlyphId,
+    transform: Transform,
+    path: &mut Path,
+) {
+    let mut builder = PathBuilder {
+        path: std::mem::take(path),
+        transform,
+    };
+    face.outline_glyph(glyph_id, &mut builder);
+    *path = builder.path;
+}
+
+/// A font [Canvas::draw_text](crate::Canvas::draw_text) can draw with.
+#[derive(Clone, Copy, Debug)]
+pub enum TextFont<'a> {
+    /// A TrueType or OpenType font, shaped and rasterized into the
+    /// [GlyphAtlas](crate::GlyphAtlas).
+    Vector(&'a Font),
+
+    /// A font of pre-rendered glyphs.
+    Bitmap(&'a BitmapFont),
+}
+
+impl<'a> From<&'a Font> for TextFont<'a> {
+    #[inline]
+    fn from(font: &'a Font) -> Self {
+        Self::Vector(font)
+    }
+}
+
+impl<'a> From<&'a BitmapFont> for TextFont<'a> {
+    #[inline]
+    fn from(font: &'a BitmapFont) -> Self {
+        Self::Bitmap(font)
+    }
+}
+
+impl fmt::Debug for Font {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Font")
+            .field("id", &self.id)
+            .field("index", &self.index)
+            .field("data_len", &self.data.len())
+            .finish()
+    }
+}
@@ -0,0 +1,859 @@
+use std::ops::Range;
+
+use unicode_bidi::{BidiInfo, Level};
+use unicode_linebreak::BreakOpportunity;
+use unicode_script::{Script, UnicodeScript};
+
+use super::font::append_glyph_path;
+use crate::{Error, Font, FontCollection, FontQuery, Path, Transform, Vec2};
+
+/// How the lines of a [TextLayout] are aligned horizontally.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum TextAlign {
+    /// Aligned to the side lines start from: the left for left-to-right paragraphs, the right
+    /// for right-to-left ones.
+    #[default]
+    Start,
+
+    /// Aligned to the side lines end at.
+    End,
+
+    /// Centered.
+    Center,
+
+    /// Stretched to fill the whole width by widening the spaces between words.  The last line of
+    /// each paragraph is aligned like [TextAlign::Start].
+    Justify,
+}
+
+/// The direction a paragraph of text flows in.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum TextDirection {
+    LeftToRight,
+    RightToLeft,
+}
+
+/// An OpenType feature to turn on or off when shaping text, e.g. `liga` for ligatures or `smcp`
+/// for small capitals.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct FontFeature {
+    /// The four-letter tag of the feature.
+    pub tag: [u8; 4],
+
+    /// `0` to turn the feature off, `1` to turn it on.  Some features, like `salt`, pick one of
+    /// several alternates with larger values.
+    pub value: u32,
+}
+
+impl FontFeature {
+    /// Creates a [FontFeature] from its tag and value.
+    #[inline]
+    pub const fn new(tag: &[u8; 4], value: u32) -> Self {
+        Self { tag: *tag, value }
+    }
+}
+
+/// How text is laid out by [TextLayout::new].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TextLayoutOptions {
+    /// The width lines are wrapped at, in pixels.  Lines only end at `'\n'` if [None].
+    pub max_width: Option<f32>,
+    pub align: TextAlign,
+
+    /// The direction of every paragraph, or [None] to use the direction of each paragraph's first
+    /// strongly directional character.
+    pub direction: Option<TextDirection>,
+
+    /// The OpenType features applied on top of the font's defaults.
+    pub features: Vec<FontFeature>,
+}
+
+impl TextLayoutOptions {
+    /// Sets the width lines are wrapped at.
+    #[inline]
+    pub fn with_max_width(mut self, max_width: f32) -> Self {
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// Sets how lines are aligned.
+    #[inline]
+    pub fn with_align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Sets the direction of every paragraph.
+    #[inline]
+    pub fn with_direction(mut self, direction: TextDirection) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// Adds an OpenType feature.
+    #[inline]
+    pub fn with_feature(mut self, feature: FontFeature) -> Self {
+        self.features.push(feature);
+        self
+    }
+}
+
+/// The position and size of a line of a [TextLayout], in pixels relative to the top-left corner
+/// of the layout.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LineMetrics {
+    /// The bytes of the text on this line, including trailing whitespace and line breaks.
+    pub range: Range<usize>,
+
+    /// The direction of the paragraph this line is part of.
+    pub direction: TextDirection,
+
+    /// The left edge of the glyphs of this line.
+    pub x: f32,
+
+    /// The width of the glyphs of this line, without trailing whitespace.
+    pub width: f32,
+
+    /// The top edge of this line.
+    pub top: f32,
+    pub baseline: f32,
+
+    /// How far glyphs reach above the baseline.
+    pub ascent: f32,
+
+    /// How far glyphs reach below the baseline, negative as it's measured upwards.
+    pub descent: f32,
+
+    /// The distance from the top of this line to the top of the next one.
+    pub height: f32,
+}
+
+/// Where the caret is drawn before a character of a [TextLayout].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Caret {
+    /// The top of the caret, in pixels relative to the top-left corner of the layout.
+    pub position: Vec2,
+    pub height: f32,
+
+    /// The index of the line the caret is on.
+    pub line: usize,
+}
+
+/// A glyph of a [TextLayout].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct LayoutGlyph {
+    /// The index of the font of the glyph in [TextLayout::fonts].
+    pub font: usize,
+    pub glyph_id: u16,
+
+    /// The origin of the glyph on the baseline, in pixels relative to the top-left corner of the
+    /// layout.
+    pub position: Vec2,
+}
+
+/// A glyph output by the shaper.
+#[derive(Clone, Copy, Debug)]
+struct ShapedGlyph {
+    /// The index of the font of the glyph in `Shaper::fonts`.
+    font: usize,
+    glyph_id: u16,
+
+    /// The byte index of the first character this glyph was shaped from.
+    cluster: usize,
+    advance: f32,
+    offset: Vec2,
+}
+
+/// A range of text with a single direction and script, shaped at once.
+#[derive(Debug)]
+struct ShapedRun {
+    range: Range<usize>,
+    level: Level,
+
+    /// The glyphs of the run, in visual order.
+    glyphs: Vec<ShapedGlyph>,
+}
+
+/// A line, before it is positioned.
+#[derive(Debug)]
+struct PendingLine {
+    range: Range<usize>,
+
+    /// The end of the line without its trailing whitespace.
+    visible_end: usize,
+    paragraph: usize,
+
+    /// `true` if the line ends its paragraph.
+    last_in_paragraph: bool,
+    width: f32,
+}
+
+/// Text shaped, broken into lines and positioned, ready to be drawn by
+/// [Canvas::draw_text_layout](crate::Canvas::draw_text_layout) as many times as needed.
+///
+/// Glyphs are shaped with their OpenType features, so ligatures, kerning and complex scripts are
+/// drawn correctly.  Paragraphs are ordered with the Unicode bidirectional algorithm and wrapped
+/// at the line break opportunities of UAX #14.
+///
+/// Characters are identified by the index of their first byte in the text.
+#[derive(Clone, Debug)]
+pub struct TextLayout {
+    /// The fonts of the glyphs, starting with the font the layout was made with.
+    fonts: Vec<Font>,
+    size: f32,
+    text: String,
+    lines: Vec<LineMetrics>,
+    glyphs: Vec<LayoutGlyph>,
+
+    /// For each line, the x position of the caret before each of its characters, as `(index,
+    /// x)` sorted by index.
+    carets: Vec<Vec<(usize, f32)>>,
+    width: f32,
+}
+
+impl TextLayout {
+    /// Lays out *text* with the provided [Font] and font size, in pixels.
+    pub fn new(font: &Font, size: f32, text: &str, options: &TextLayoutOptions) -> Self {
+        Self::with_shaper(
+            Shaper::new(font.clone(), None, size, options),
+            text,
+            options,
+        )
+    }
+
+    /// Lays out *text* with the [Font] of *collection* best matching *query*, at the provided
+    /// font size in pixels.
+    ///
+    /// The clusters of characters this font has no glyphs for are drawn with the best matching
+    /// font of the collection which has them, so CJK or emoji can be mixed into Latin text.
+    ///
+    /// # Fails
+    /// Fails if no font of *collection* can be loaded.
+    pub fn from_collection(
+        collection: &FontCollection,
+        query: &FontQuery,
+        size: f32,
+        text: &str,
+        options: &TextLayoutOptions,
+    ) -> Result<Self, Error> {
+        let font = collection.query(query).ok_or(Error::NoFontFound)?;
+        let shaper = Shaper::new(font, Some((collection, query)), size, options);
+        Ok(Self::with_shaper(shaper, text, options))
+    }
+
+    /// Lays out *text*, shaping it with *shaper*.
+    fn with_shaper(mut shaper: Shaper<'_>, text: &str, options: &TextLayoutOptions) -> Self {
+        let size = shaper.size;
+        let default_level = options.direction.map(|direction| match direction {
+            TextDirection::LeftToRight => Level::ltr(),
+            TextDirection::RightToLeft => Level::rtl(),
+        });
+        let bidi = BidiInfo::new(text, default_level);
+        let breaks: Vec<_> = unicode_linebreak::linebreaks(text).collect();
+
+        // Shape every paragraph, then break it into lines.
+        let mut runs = Vec::new();
+        let mut pending_lines = Vec::new();
+        for (paragraph_index, paragraph) in bidi.paragraphs.iter().enumerate() {
+            let range = paragraph.range.clone();
+            let first_run = runs.len();
+            for (run_range, level) in itemize(text, range.clone(), &bidi.levels) {
+                runs.push(ShapedRun {
+                    glyphs: shaper.shape(text, run_range.clone(), level, 0, &[]),
+                    range: run_range,
+                    level,
+                });
+            }
+
+            // The advance of the glyphs starting at each byte of the paragraph, summed up.
+            let mut advances = vec![0.0; range.len() + 1];
+            for glyph in runs[first_run..].iter().flat_map(|run| &run.glyphs) {
+                advances[glyph.cluster - range.start + 1] += glyph.advance;
+            }
+            for index in 1..advances.len() {
+                advances[index] += advances[index - 1];
+            }
+            let width_of = |start: usize, end: usize| {
+                advances[end - range.start] - advances[start - range.start]
+            };
+
+            let mut push_line = |start: usize, end: usize, last_in_paragraph: bool| {
+                let visible_end = start + text[start..end].trim_end().len();
+                pending_lines.push(PendingLine {
+                    range: start..end,
+                    visible_end,
+                    paragraph: paragraph_index,
+                    last_in_paragraph,
+                    width: width_of(start, visible_end),
+                });
+            };
+
+            // Greedily fit as many words as possible on each line.
+            let mut line_start = range.start;
+            let mut last_break = None;
+            for &(index, opportunity) in breaks
+                .iter()
+                .filter(|(index, _)| *index > range.start && *index <= range.end)
+            {
+                let visible_end = line_start + text[line_start..index].trim_end().len();
+                let overflows = options
+                    .max_width
+                    .is_some_and(|max_width| width_of(line_start, visible_end) > max_width);
+                if let (true, Some(last_break)) = (overflows, last_break) {
+                    push_line(line_start, last_break, false);
+                    line_start = last_break;
+                }
+
+                last_break = Some(index);
+                if opportunity == BreakOpportunity::Mandatory || index == range.end {
+                    push_line(line_start, index, true);
+                    line_start = index;
+                    last_break = None;
+                }
+            }
+        }
+
+        // The caret can be placed after a final line break, or in empty text.
+        if text.is_empty() || text.ends_with(['\n', '\r', '\u{2029}']) {
+            pending_lines.push(PendingLine {
+                range: text.len()..text.len(),
+                visible_end: text.len(),
+                paragraph: bidi.paragraphs.len(),
+                last_in_paragraph: true,
+                width: 0.0,
+            });
+        }
+
+        let width = options.max_width.unwrap_or_else(|| {
+            pending_lines
+                .iter()
+                .map(|line| line.width)
+                .fold(0.0, f32::max)
+        });
+
+        // Lines are as tall as the tallest of their fonts, and at least as tall as the first font.
+        let metrics: Vec<_> = shaper
+            .fonts
+            .iter()
+            .map(|font| {
+                (
+                    font.ascent(size),
+                    font.descent(size),
+                    font.line_height(size),
+                )
+            })
+            .collect();
+
+        let mut layout = Self {
+            fonts: shaper.fonts,
+            size,
+            text: text.to_owned(),
+            lines: Vec::with_capacity(pending_lines.len()),
+            glyphs: Vec::new(),
+            carets: Vec::with_capacity(pending_lines.len()),
+            width,
+        };
+
+        let mut top = 0.0;
+        for line in &pending_lines {
+            let direction = match bidi.paragraphs.get(line.paragraph) {
+                Some(paragraph) if paragraph.level.is_rtl() => TextDirection::RightToLeft,
+                Some(_) => TextDirection::LeftToRight,
+                None => match options.direction {
+                    Some(direction) => direction,
+                    None => layout
+                        .lines
+                        .last()
+                        .map_or(TextDirection::LeftToRight, |line| line.direction),
+                },
+            };
+
+            // Justified lines widen their spaces, the others are moved as a whole.
+            let free_space = (width - line.width).max(0.0);
+            let spaces = text[line.range.start..line.visible_end]
+                .chars()
+                .filter(|character| *character == ' ')
+                .count();
+            let (x, extra_space) = match (options.align, direction) {
+                (TextAlign::Justify, _) if !line.last_in_paragraph && spaces > 0 => {
+                    (0.0, free_space / spaces as f32)
+                }
+                (TextAlign::Start | TextAlign::Justify, TextDirection::LeftToRight)
+                | (TextAlign::End, TextDirection::RightToLeft) => (0.0, 0.0),
+                (TextAlign::Start | TextAlign::Justify, TextDirection::RightToLeft)
+                | (TextAlign::End, TextDirection::LeftToRight) => (free_space, 0.0),
+                (TextAlign::Center, _) => (free_space * 0.5, 0.0),
+            };
+
+            let (ascent, descent, line_height) = runs
+                .iter()
+                .flat_map(|run| &run.glyphs)
+                .filter(|glyph| (line.range.start..line.visible_end).contains(&glyph.cluster))
+                .fold(metrics[0], |(ascent, descent, height), glyph| {
+                    let metrics = metrics[glyph.font];
+                    (
+                        ascent.max(metrics.0),
+                        descent.min(metrics.1),
+                        height.max(metrics.2),
+                    )
+                });
+            let baseline = top + ascent;
+            let carets = layout.place_line(&bidi, &runs, line, x, baseline, extra_space);
+
+            let line_width = if extra_space > 0.0 { width } else { line.width };
+            layout.carets.push(carets);
+            layout.lines.push(LineMetrics {
+                range: line.range.clone(),
+                direction,
+                x,
+                width: line_width,
+                top,
+                baseline,
+                ascent,
+                descent,
+                height: line_height,
+            });
+            top += line_height;
+        }
+
+        layout
+    }
+
+    /// Positions the glyphs of a line in visual order, returning its caret positions.
+    fn place_line(
+        &mut self,
+        bidi: &BidiInfo<'_>,
+        runs: &[ShapedRun],
+        line: &PendingLine,
+        x: f32,
+        baseline: f32,
+        extra_space: f32,
+    ) -> Vec<(usize, f32)> {
+        let visible = line.range.start..line.visible_end;
+
+        // The glyphs of each cluster: its first byte, left and right edges and direction.
+        let mut clusters: Vec<(usize, f32, f32, bool)> = Vec::new();
+        let mut pen = x;
+        if let Some(paragraph) = bidi.paragraphs.get(line.paragraph) {
+            let (levels, visual_runs) = bidi.visual_runs(paragraph, line.range.clone());
+            for visual_run in visual_runs {
+                let level = levels[visual_run.start];
+                let mut run_parts: Vec<_> = runs
+                    .iter()
+                    .filter(|run| {
+                        run.range.start < visual_run.end.min(visible.end)
+                            && run.range.end > visual_run.start.max(visible.start)
+                    })
+                    .collect();
+                if level.is_rtl() {
+                    run_parts.reverse();
+                }
+
+                for run in run_parts {
+                    for glyph in &run.glyphs {
+                        if !visible.contains(&glyph.cluster) || !visual_run.contains(&glyph.cluster)
+                        {
+                            continue;
+                        }
+
+                        self.glyphs.push(LayoutGlyph {
+                            font: glyph.font,
+                            glyph_id: glyph.glyph_id,
+                            position: Vec2::new(pen + glyph.offset.x, baseline - glyph.offset.y),
+                        });
+
+                        let start = pen;
+                        pen += glyph.advance;
+                        if extra_space > 0.0 && self.text[glyph.cluster..].starts_with(' ') {
+                            pen += extra_space;
+                        }
+
+                        match clusters.last_mut() {
+                            Some(cluster) if cluster.0 == glyph.cluster => cluster.2 = pen,
+                            _ => clusters.push((glyph.cluster, start, pen, run.level.is_rtl())),
+                        }
+                    }
+                }
+            }
+        }
+
+        // Spread the carets of clusters made of several characters, like ligatures, evenly
+        // across them.
+        clusters.sort_unstable_by_key(|cluster| cluster.0);
+        let mut carets = Vec::new();
+        for (index, &(start, left, right, rtl)) in clusters.iter().enumerate() {
+            let end = clusters.get(index + 1).map_or(visible.end, |next| next.0);
+            let count = self.text[start..end].chars().count().max(1) as f32;
+            for (position, (offset, _)) in self.text[start..end].char_indices().enumerate() {
+                let fraction = position as f32 / count;
+                let caret_x = if rtl {
+                    right - (right - left) * fraction
+                } else {
+                    left + (right - left) * fraction
+                };
+                carets.push((start + offset, caret_x));
+            }
+        }
+
+        // The trailing whitespace, and the end of the last line, share the end of the glyphs.
+        let end_x = match clusters.iter().max_by_key(|cluster| cluster.0) {
+            Some(&(_, left, _, true)) => left,
+            Some(&(_, _, right, false)) => right,
+            None => x,
+        };
+        let trailing_end = if line.range.end == self.text.len() {
+            line.range.end + 1
+        } else {
+            line.range.end
+        };
+        carets.extend(
+            (visible.end..trailing_end)
+                .filter(|index| self.text.is_char_boundary(*index) || *index == self.text.len())
+                .map(|index| (index, end_x)),
+        );
+        carets.sort_unstable_by_key(|caret| caret.0);
+        carets.dedup_by_key(|caret| caret.0);
+        carets
+    }
+
+    /// Returns the [Font] this layout was made with.
+    #[inline]
+    pub fn font(&self) -> &Font {
+        &self.fonts[0]
+    }
+
+    /// Returns the fonts the glyphs of this layout are drawn with: the [Font] it was made with,
+    /// followed by the fallback fonts it needed.
+    #[inline]
+    pub fn fonts(&self) -> &[Font] {
+        &self.fonts
+    }
+
+    /// Returns the font size this layout was made with, in pixels.
+    #[inline]
+    pub fn font_size(&self) -> f32 {
+        self.size
+    }
+
+    /// Returns the text of this layout.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the lines of this layout, from top to bottom.
+    #[inline]
+    pub fn lines(&self) -> &[LineMetrics] {
+        &self.lines
+    }
+
+    /// Returns the width of this layout: the maximum width if there is one, the width of the
+    /// longest line otherwise.
+    #[inline]
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    /// Returns the height of all the lines of this layout.
+    #[inline]
+    pub fn height(&self) -> f32 {
+        self.lines.last().map_or(0.0, |line| line.top + line.height)
+    }
+
+    /// Returns the outlines of the glyphs of this layout as a single [Path], in pixels relative to
+    /// its top-left corner.
+    ///
+    /// Use it to clip other drawings to the text with [Canvas::clip_path](crate::Canvas::clip_path),
+    /// or to draw text too large for the [GlyphAtlas](crate::GlyphAtlas).  Glyphs without
+    /// outlines, like bitmap emoji, are left out.
+    pub fn to_path(&self) -> Path {
+        let faces: Vec<_> = self.fonts.iter().map(Font::face).collect();
+        let mut path = Path::new();
+        for glyph in &self.glyphs {
+            let face = &faces[glyph.font];
+            let scale = Font::scale(face, self.size);
+            let transform =
+                Transform::translate(glyph.position) * Transform::scale(Vec2::new(scale, -scale));
+            append_glyph_path(
+                face,
+                ttf_parser::GlyphId(glyph.glyph_id),
+                transform,
+                &mut path,
+            );
+        }
+        path
+    }
+
+    /// Returns the glyphs of this layout.
+    #[inline]
+    pub(crate) fn glyphs(&self) -> &[LayoutGlyph] {
+        &self.glyphs
+    }
+
+    /// Returns the index of the line holding the character at *index*.
+    pub fn line_at(&self, index: usize) -> usize {
+        self.lines
+            .iter()
+            .rposition(|line| {
+                line.range.start <= index && (line.range.end > index || line.range.is_empty())
+            })
+            .unwrap_or(self.lines.len().saturating_sub(1))
+    }
+
+    /// Returns where the caret is drawn before the character at byte *index* of the text, or
+    /// after the last character if *index* is the length of the text.
+    ///
+    /// Indices within a character are moved to its start.
+    pub fn caret(&self, index: usize) -> Caret {
+        let line_index = self.line_at(index);
+        let line = &self.lines[line_index];
+        let carets = &self.carets[line_index];
+
+        let x = match carets.binary_search_by_key(&index, |caret| caret.0) {
+            Ok(found) => carets[found].1,
+            Err(0) => carets.first().map_or(line.x, |caret| caret.1),
+            Err(after) => carets[after - 1].1,
+        };
+
+        Caret {
+            position: Vec2::new(x, line.top),
+            height: line.height,
+            line: line_index,
+        }
+    }
+
+    /// Returns the index of the character boundary nearest to *point*, in pixels relative to the
+    /// top-left corner of the layout.
+    ///
+    /// Points above or below the layout hit its first or last line.
+    pub fn hit_test(&self, point: Vec2) -> usize {
+        let line_index = self
+            .lines
+            .iter()
+            .position(|line| point.y < line.top + line.height)
+            .unwrap_or(self.lines.len() - 1);
+
+        self.carets[line_index]
+            .iter()
+            .min_by(|a, b| (a.1 - point.x).abs().total_cmp(&(b.1 - point.x).abs()))
+            .map_or(self.lines[line_index].range.start, |caret| caret.0)
+    }
+}
+
+/// Splits a paragraph into runs of characters with the same bidi level and script.
+fn itemize(text: &str, paragraph: Range<usize>, levels: &[Level]) -> Vec<(Range<usize>, Level)> {
+    let mut items: Vec<(Range<usize>, Level, Script)> = Vec::new();
+    for (offset, character) in text[paragraph.clone()].char_indices() {
+        let index = paragraph.start + offset;
+        let end = index + character.len_utf8();
+        let level = levels[index];
+
+        // Punctuation and marks take the script of the text around them.
+        let script = match character.script() {
+            Script::Common | Script::Inherited | Script::Unknown => None,
+            script => Some(script),
+        };
+
+        match items.last_mut() {
+            Some((range, last_level, last_script))
+                if *last_level == level
+                    && (script.is_none()
+                        || *last_script == Script::Common
+                        || Some(*last_script) == script) =>
+            {
+                range.end = end;
+                if let (Script::Common, Some(script)) = (*last_script, script) {
+                    *last_script = script;
+                }
+            }
+            _ => items.push((index..end, level, script.unwrap_or(Script::Common))),
+        }
+    }
+
+    items
+        .into_iter()
+        .map(|(range, level, _)| (range, level))
+        .collect()
+}
+
+/// Shapes runs of text, falling back to the fonts of a [FontCollection] for the clusters of
+/// characters the first font has no glyphs for.
+struct Shaper<'a> {
+    /// The fonts used so far, starting with the font of the layout.
+    fonts: Vec<Font>,
+    fallback: Option<(&'a FontCollection, &'a FontQuery)>,
+    features: Vec<rustybuzz::Feature>,
+    size: f32,
+}
+
+impl<'a> Shaper<'a> {
+    /// Creates a [Shaper] for the provided font, font size and features.
+    fn new(
+        font: Font,
+        fallback: Option<(&'a FontCollection, &'a FontQuery)>,
+        size: f32,
+        options: &TextLayoutOptions,
+    ) -> Self {
+        let features = options
+            .features
+            .iter()
+            .map(|feature| {
+                rustybuzz::Feature::new(
+                    rustybuzz::ttf_parser::Tag::from_bytes(&feature.tag),
+                    feature.value,
+                    ..,
+                )
+            })
+            .collect();
+
+        Self {
+            fonts: vec![font],
+            fallback,
+            features,
+            size,
+        }
+    }
+
+    /// Shapes a run of text with a single direction and script with the font at index *font*,
+    /// returning its glyphs in visual order.
+    ///
+    /// The clusters the font has no glyphs for are shaped again with a fallback font, if there
+    /// is one which isn't in *skipped_ids*.
+    fn shape(
+        &mut self,
+        text: &str,
+        range: Range<usize>,
+        level: Level,
+        font: usize,
+        skipped_ids: &[u64],
+    ) -> Vec<ShapedGlyph> {
+        let mut glyphs = shape_run(
+            &self.fonts[font],
+            font,
+            &self.features,
+            text,
+            range.clone(),
+            level,
+            self.size,
+        );
+        let Some((collection, query)) = self.fallback else {
+            return glyphs;
+        };
+
+        // Group the clusters with missing glyphs, glyph 0, into ranges of text.
+        let mut clusters: Vec<_> = glyphs.iter().map(|glyph| glyph.cluster).collect();
+        clusters.sort_unstable();
+        clusters.dedup();
+        let mut missing_clusters: Vec<_> = glyphs
+            .iter()
+            .filter(|glyph| glyph.glyph_id == 0)
+            .map(|glyph| glyph.cluster)
+            .collect();
+        missing_clusters.sort_unstable();
+        missing_clusters.dedup();
+
+        let mut missing_ranges: Vec<Range<usize>> = Vec::new();
+        for cluster in missing_clusters {
+            let next = clusters.partition_point(|&start| start <= cluster);
+            let end = clusters.get(next).copied().unwrap_or(range.end);
+            match missing_ranges.last_mut() {
+                Some(last) if last.end == cluster => last.end = end,
+                _ => missing_ranges.push(cluster..end),
+            }
+        }
+
+        let mut skipped_ids = skipped_ids.to_vec();
+        skipped_ids.push(self.fonts[font].id());
+        for missing_range in missing_ranges {
+            let Some(character) = text[missing_range.clone()]
+                .chars()
+                .find(|character| !character.is_whitespace() && !character.is_control())
+            else {
+                continue;
+            };
+            let Some(fallback) = collection.fallback_font(query, character, &skipped_ids) else {
+                continue;
+            };
+
+            let fallback_index = match self
+                .fonts
+                .iter()
+                .position(|font| font.id() == fallback.id())
+            {
+                Some(index) => index,
+                None => {
+                    self.fonts.push(fallback);
+                    self.fonts.len() - 1
+                }
+            };
+            let mut fallback_glyphs = Some(self.shape(
+                text,
+                missing_range.clone(),
+                level,
+                fallback_index,
+                &skipped_ids,
+            ));
+
+            // The glyphs of the range are next to each other in either direction.
+            let mut replaced = Vec::with_capacity(glyphs.len());
+            for glyph in glyphs {
+                if !missing_range.contains(&glyph.cluster) {
+                    replaced.push(glyph);
+                } else if let Some(fallback_glyphs) = fallback_glyphs.take() {
+                    replaced.extend(fallback_glyphs);
+                }
+            }
+            glyphs = replaced;
+        }
+
+        glyphs
+    }
+}
+
+/// Shapes a run of text with a single direction and script with *font*, the font at index
+/// *font_index*.
+fn shape_run(
+    font: &Font,
+    font_index: usize,
+    features: &[rustybuzz::Feature],
+    text: &str,
+    range: Range<usize>,
+    level: Level,
+    size: f32,
+) -> Vec<ShapedGlyph> {
+    let face = font.face();
+    let scale = Font::scale(&face, size);
+    let shaper = rustybuzz::Face::from_face(face);
+
+    let mut buffer = rustybuzz::UnicodeBuffer::new();
+    for (offset, character) in text[range.clone()].char_indices() {
+        buffer.add(character, (range.start + offset) as u32);
+    }
+
+    // The surrounding text lets scripts like Arabic join letters across runs.
+    buffer.set_pre_context(&text[..range.start]);
+    buffer.set_post_context(&text[range.end..]);
+    buffer.set_direction(if level.is_rtl() {
+        rustybuzz::Direction::RightToLeft
+    } else {
+        rustybuzz::Direction::LeftToRight
+    });
+    buffer.guess_segment_properties();
+
+    let output = rustybuzz::shape(&shaper, features, buffer);
+    output
+        .glyph_infos()
+        .iter()
+        .zip(output.glyph_positions())
+        .map(|(info, position)| ShapedGlyph {
+            font: font_index,
+            glyph_id: info.glyph_id as u16,
+            cluster: info.cluster as usize,
+            advance: position.x_advance as f32 * scale,
+            offset: Vec2::new(
+                position.x_offset as f32 * scale,
+                position.y_offset as f32 * scale,
+            ),
+        })
+        .collect()
+}
@@ -0,0 +1,107 @@
+mod atlas;
+mod bitmap;
+mod collection;
+mod color;
+mod font;
+mod layout;
+mod sdf;
+
+pub use atlas::*;
+pub use bitmap::*;
+pub use collection::*;
+pub use font::*;
+pub use layout::*;
+pub use sdf::*;
+
+use crate::{Rect, Transform, Vec2};
+
+/// The attributes for `GlyphInstance::wgpu_desc`.
+const WGPU_ATTRIBS: [wgpu::VertexAttribute; 4] = wgpu::vertex_attr_array![
+    0 => Float32x2, // origin
+    1 => Float32x2, // x_axis
+    2 => Float32x2, // y_axis
+    3 => Float32x4, // atlas_bounds
+];
+
+/// The per-instance data of the text shaders: one glyph copied from a [GlyphAtlas] onto a
+/// parallelogram.
+#[derive(bytemuck::Zeroable, bytemuck::Pod, Clone, Copy, Debug, Default)]
+#[repr(C)]
+pub(crate) struct GlyphInstance {
+    /// Where the top-left corner of the glyph is drawn, in pixels (before transforming, for
+    /// signed distance field and color text).
+    origin: [f32; 2],
+
+    /// Where the top and left edges of the glyph are drawn, relative to *origin*.
+    x_axis: [f32; 2],
+    y_axis: [f32; 2],
+
+    /// The glyph in the pixels of the atlas, as `[min_x, min_y, max_x, max_y]`.
+    atlas_bounds: [f32; 4],
+}
+
+impl GlyphInstance {
+    /// Returns the [`wgpu::VertexBufferLayout`] of [GlyphInstance] buffers.
+    pub const fn wgpu_desc() -> wgpu::VertexBufferLayout<'static> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<Self>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &WGPU_ATTRIBS,
+        }
+    }
+
+    /// Returns the bounds of the parallelogram covered by the glyph, in pixels.
+    pub fn bounds(&self) -> Rect {
+        let origin = Vec2::new(self.origin[0], self.origin[1]);
+        let (x_axis, y_axis) = (
+            Vec2::new(self.x_axis[0], self.x_axis[1]),
+            Vec2::new(self.y_axis[0], self.y_axis[1]),
+        );
+        let corners = [origin + x_axis, origin + y_axis, origin + x_axis + y_axis];
+        let (min, max) = corners.iter().fold((origin, origin), |(min, max), corner| {
+            (
+                Vec2::new(min.x.min(corner.x), min.y.min(corner.y)),
+                Vec2::new(max.x.max(corner.x), max.y.max(corner.y)),
+            )
+        });
+        Rect::from_min_max(min, max)
+    }
+
+    /// Returns this instance with its parallelogram mapped by the provided [Transform].
+    pub fn transformed(&self, transform: Transform) -> Self {
+        let origin = transform.transform_point(Vec2::new(self.origin[0], self.origin[1]));
+        let x_axis = transform.transform_vector(Vec2::new(self.x_axis[0], self.x_axis[1]));
+        let y_axis = transform.transform_vector(Vec2::new(self.y_axis[0], self.y_axis[1]));
+        Self {
+            origin: [origin.x, origin.y],
+            x_axis: [x_axis.x, x_axis.y],
+            y_axis: [y_axis.x, y_axis.y],
+            atlas_bounds: self.atlas_bounds,
+        }
+    }
+
+    /// Creates the instance drawing an [AtlasGlyph] with its origin at the provided position,
+    /// scaling its bitmap by *scale*.
+    ///
+    /// Returns [None] if the glyph has nothing to draw.
+    pub fn new(glyph: &AtlasGlyph, origin: Vec2, scale: f32) -> Option<Self> {
+        let ([width, height], [atlas_x, atlas_y]) = (glyph.size, glyph.origin);
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let min_x = origin.x + glyph.offset[0] as f32 * scale;
+        let min_y = origin.y + glyph.offset[1] as f32 * scale;
+        Some(Self {
+            origin: [min_x, min_y],
+            x_axis: [width as f32 * scale, 0.0],
+            y_axis: [0.0, height as f32 * scale],
+            atlas_bounds: [
+                atlas_x as f32,
+                atlas_y as f32,
+                (atlas_x + width) as f32,
+                (atlas_y + height) as f32,
+            ],
+        })
+    }
+}
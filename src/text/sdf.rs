@@ -0,0 +1,175 @@
+use crate::Color;
+
+use super::atlas::rasterize_glyph;
+
+/// The font size, in pixels, the signed distance fields of glyphs are generated at.
+pub(crate) const SDF_SIZE: f32 = 48.0;
+
+/// How far from the outline of a glyph its signed distance field reaches, in pixels at
+/// [SDF_SIZE].
+pub(crate) const SDF_SPREAD: f32 = 8.0;
+
+/// Stands for an infinite squared distance in the distance transform.
+const INF: f64 = 1e20;
+
+/// How [Canvas::draw_sdf_text_layout](crate::Canvas::draw_sdf_text_layout) decorates text.
+///
+/// Widths and radii are in the pixels of the [TextLayout](crate::TextLayout), before it is
+/// transformed.  Outlines and glows can reach at most as far from the glyphs as their distance
+/// fields do, which is `font_size / 6` pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SdfTextStyle {
+    /// The width of the outline drawn around the glyphs, `0.0` for no outline.
+    pub outline_width: f32,
+    pub outline_color: Color,
+
+    /// How far the glow around the glyphs (and their outline) fades out over, `0.0` for no glow.
+    pub glow_radius: f32,
+    pub glow_color: Color,
+}
+
+impl SdfTextStyle {
+    /// Creates a [SdfTextStyle] without outline or glow.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            outline_width: 0.0,
+            outline_color: Color::TRANSPARENT,
+            glow_radius: 0.0,
+            glow_color: Color::TRANSPARENT,
+        }
+    }
+
+    /// Sets the width and color of the outline.
+    #[inline]
+    pub const fn with_outline(mut self, width: f32, color: Color) -> Self {
+        self.outline_width = width;
+        self.outline_color = color;
+        self
+    }
+
+    /// Sets the radius and color of the glow.
+    #[inline]
+    pub const fn with_glow(mut self, radius: f32, color: Color) -> Self {
+        self.glow_radius = radius;
+        self.glow_color = color;
+        self
+    }
+}
+
+/// Generates the signed distance field of a glyph at [SDF_SIZE].
+///
+/// Each pixel holds `0.5` on the outline of the glyph, going up to `1.0` at [SDF_SPREAD] pixels
+/// inside of it and down to `0.0` at [SDF_SPREAD] pixels outside of it.  Returns the same values
+/// as `rasterize_glyph`, the bitmap growing by [SDF_SPREAD] pixels on each side.
+pub(super) fn sdf_glyph(
+    face: &ttf_parser::Face<'_>,
+    glyph_id: u16,
+) -> Option<([i32; 2], [u32; 2], Vec<u8>)> {
+    let ([left, top], [width, height], coverage) = rasterize_glyph(face, glyph_id, SDF_SIZE, 0)?;
+
+    let padding = SDF_SPREAD.ceil() as u32;
+    let (sdf_width, sdf_height) = (width + 2 * padding, height + 2 * padding);
+
+    // The squared distances to the nearest pixel outside and inside of the glyph.  Partially
+    // covered pixels are placed on the outline, like in Mapbox's TinySDF.
+    let mut outer = vec![INF; (sdf_width * sdf_height) as usize];
+    let mut inner = vec![0.0; (sdf_width * sdf_height) as usize];
+    for (row, src) in coverage.chunks_exact(width as usize).enumerate() {
+        for (column, &alpha) in src.iter().enumerate() {
+            let index = (row + padding as usize) * sdf_width as usize + column + padding as usize;
+            let alpha = alpha as f64 / 255.0;
+            if alpha >= 1.0 {
+                outer[index] = 0.0;
+                inner[index] = INF;
+            } else if alpha > 0.0 {
+                let distance = 0.5 - alpha;
+                outer[index] = distance.max(0.0).powi(2);
+                inner[index] = distance.min(0.0).powi(2);
+            }
+        }
+    }
+
+    distance_transform(&mut outer, sdf_width as usize, sdf_height as usize);
+    distance_transform(&mut inner, sdf_width as usize, sdf_height as usize);
+
+    let sdf = outer
+        .iter()
+        .zip(&inner)
+        .map(|(outer, inner)| {
+            let distance = (outer.sqrt() - inner.sqrt()) as f32;
+            ((0.5 - distance / (2.0 * SDF_SPREAD)).clamp(0.0, 1.0) * 255.0).round() as u8
+        })
+        .collect();
+
+    Some((
+        [left - padding as i32, top - padding as i32],
+        [sdf_width, sdf_height],
+        sdf,
+    ))
+}
+
+/// Replaces each value of *grid* by the smallest squared euclidean distance to a pixel plus the
+/// value of that pixel, following Felzenszwalb and Huttenlocher's algorithm.
+fn distance_transform(grid: &mut [f64], width: usize, height: usize) {
+    let length = width.max(height);
+    let mut f = vec![0.0; length];
+    let mut v = vec![0; length];
+    let mut z = vec![0.0; length + 1];
+
+    for x in 0..width {
+        distance_transform_1d(grid, x, width, height, &mut f, &mut v, &mut z);
+    }
+    for y in 0..height {
+        distance_transform_1d(grid, y * width, 1, width, &mut f, &mut v, &mut z);
+    }
+}
+
+/// Runs the distance transform along one row or column of *grid*, using *f*, *v* and *z* as
+/// scratch space.
+fn distance_transform_1d(
+    grid: &mut [f64],
+    offset: usize,
+    stride: usize,
+    length: usize,
+    f: &mut [f64],
+    v: &mut [usize],
+    z: &mut [f64],
+) {
+    // Find the lower envelope of the parabolas rooted at each pixel.
+    v[0] = 0;
+    z[0] = -INF;
+    z[1] = INF;
+    f[0] = grid[offset];
+
+    let mut k = 0;
+    for q in 1..length {
+        f[q] = grid[offset + q * stride];
+        let q2 = (q * q) as f64;
+        // `z[0]` is minus infinity, so the first parabola is never removed.
+        let mut s;
+        loop {
+            let r = v[k];
+            s = (f[q] - f[r] + q2 - (r * r) as f64) / (q - r) as f64 / 2.0;
+            if s > z[k] {
+                break;
+            }
+            k -= 1;
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = INF;
+    }
+
+    // Sample the lower envelope.
+    let mut k = 0;
+    for q in 0..length {
+        while z[k + 1] < q as f64 {
+            k += 1;
+        }
+        let r = v[k];
+        let qr = q as f64 - r as f64;
+        grid[offset + q * stride] = f[r] + qr * qr;
+    }
+}
@@ -0,0 +1,636 @@
+use std::collections::HashMap;
+
+use crate::{
+    Dimension, Error, Handle, OwnedTexture, Rect, Texture, TextureDescriptor, TextureFormat,
+};
+
+/// Identifies a region of a [TextureAtlas].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AtlasRegionId(u64);
+
+/// An image packed into a [TextureAtlas].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AtlasRegion {
+    /// Identifies the image, e.g. to remove it with [TextureAtlas::remove].
+    pub id: AtlasRegionId,
+
+    /// The index of the page holding the image, see [TextureAtlas::page].
+    pub page: usize,
+
+    /// The image in the pixels of its page, the *src* rectangle for
+    /// [Canvas::draw_texture](crate::Canvas::draw_texture) and
+    /// [SpriteBatch::add](crate::SpriteBatch::add).
+    pub rect: Rect,
+
+    /// The image in the normalized coordinates of its page, from `0.0` to `1.0`, like
+    /// [Vertex::uv](crate::Vertex::uv).
+    pub uv_rect: Rect,
+}
+
+/// A rectangle of a page, in pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct PackedRect {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl PackedRect {
+    #[inline]
+    fn right(&self) -> u32 {
+        self.x + self.width
+    }
+
+    #[inline]
+    fn bottom(&self) -> u32 {
+        self.y + self.height
+    }
+
+    #[inline]
+    fn contains(&self, other: &Self) -> bool {
+        other.x >= self.x
+            && other.y >= self.y
+            && other.right() <= self.right()
+            && other.bottom() <= self.bottom()
+    }
+
+    #[inline]
+    fn intersects(&self, other: &Self) -> bool {
+        self.x < other.right()
+            && other.x < self.right()
+            && self.y < other.bottom()
+            && other.y < self.bottom()
+    }
+}
+
+/// Packs rectangles into a page with the MaxRects algorithm, placing each one in the free
+/// rectangle it fits most snugly ("best short side fit").
+#[derive(Clone, Debug)]
+struct MaxRectsPacker {
+    /// The maximal free rectangles of the page, which may overlap.
+    free: Vec<PackedRect>,
+}
+
+impl MaxRectsPacker {
+    fn new(size: Dimension) -> Self {
+        Self {
+            free: vec![PackedRect {
+                x: 0,
+                y: 0,
+                width: size.width,
+                height: size.height,
+            }],
+        }
+    }
+
+    /// Finds room for a rectangle of the provided size, or returns [None] if the page is too full.
+    fn allocate(&mut self, width: u32, height: u32) -> Option<PackedRect> {
+        let best = self
+            .free
+            .iter()
+            .filter(|free| free.width >= width && free.height >= height)
+            .min_by_key(|free| {
+                let (leftover_x, leftover_y) = (free.width - width, free.height - height);
+                (leftover_x.min(leftover_y), leftover_x.max(leftover_y))
+            })?;
+        let placed = PackedRect {
+            x: best.x,
+            y: best.y,
+            width,
+            height,
+        };
+
+        // Replace every free rectangle the new one overlaps with the parts of it left free.
+        let mut split = Vec::new();
+        self.free.retain(|free| {
+            if !free.intersects(&placed) {
+                return true;
+            }
+
+            if placed.x > free.x {
+                split.push(PackedRect {
+                    width: placed.x - free.x,
+                    ..*free
+                });
+            }
+            if placed.right() < free.right() {
+                split.push(PackedRect {
+                    x: placed.right(),
+                    width: free.right() - placed.right(),
+                    ..*free
+                });
+            }
+            if placed.y > free.y {
+                split.push(PackedRect {
+                    height: placed.y - free.y,
+                    ..*free
+                });
+            }
+            if placed.bottom() < free.bottom() {
+                split.push(PackedRect {
+                    y: placed.bottom(),
+                    height: free.bottom() - placed.bottom(),
+                    ..*free
+                });
+            }
+            false
+        });
+        self.free.extend(split);
+        self.prune();
+
+        Some(placed)
+    }
+
+    /// Frees a rectangle returned by `allocate`.
+    fn deallocate(&mut self, rect: PackedRect) {
+        self.free.push(rect);
+
+        // Merge free rectangles sharing a whole edge, so freed space can be reused for larger
+        // rectangles.
+        let mut merged = true;
+        while merged {
+            merged = false;
+            'search: for i in 0..self.free.len() {
+                for j in 0..self.free.len() {
+                    let (a, b) = (self.free[i], self.free[j]);
+                    let union = if i == j {
+                        continue;
+                    } else if a.y == b.y && a.height == b.height && a.right() == b.x {
+                        PackedRect {
+                            width: a.width + b.width,
+                            ..a
+                        }
+                    } else if a.x == b.x && a.width == b.width && a.bottom() == b.y {
+                        PackedRect {
+                            height: a.height + b.height,
+                            ..a
+                        }
+                    } else {
+                        continue;
+                    };
+
+                    self.free[i] = union;
+                    self.free.swap_remove(j);
+                    merged = true;
+                    break 'search;
+                }
+            }
+        }
+        self.prune();
+    }
+
+    /// Removes the free rectangles contained in others.
+    fn prune(&mut self) {
+        let mut i = 0;
+        while i < self.free.len() {
+            let rect = self.free[i];
+            let contained = self
+                .free
+                .iter()
+                .enumerate()
+                .any(|(j, other)| j != i && other.contains(&rect) && (other != &rect || j < i));
+            if contained {
+                self.free.swap_remove(i);
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
+
+/// A page of a [TextureAtlas].
+struct AtlasPage<'a, H: Handle> {
+    texture: OwnedTexture<'a, H>,
+
+    /// The format of the page, which may differ from the current format of the atlas.
+    format: TextureFormat,
+    packer: MaxRectsPacker,
+}
+
+/// Packs many small images into a few large [OwnedTexture] pages, so they can be drawn from the
+/// same texture, e.g. by a single draw call of a [SpriteBatch](crate::SpriteBatch).
+///
+/// Images are packed with the MaxRects algorithm, and can be inserted and removed at any time.
+/// A new page is created when an image doesn't fit in the existing ones.
+///
+/// To keep neighbouring images from bleeding into each other when sampled between pixels, images
+/// can be separated by transparent padding, and their edge pixels extruded outwards.
+pub struct TextureAtlas<'a, H: Handle> {
+    handle: &'a H,
+    descriptor: TextureDescriptor,
+    padding: u32,
+    extrusion: u32,
+    pages: Vec<AtlasPage<'a, H>>,
+
+    /// The regions of the atlas, and the rectangles allocated for them (with their padding and
+    /// extrusion).
+    regions: HashMap<AtlasRegionId, (AtlasRegion, PackedRect)>,
+    next_id: u64,
+}
+
+impl<'a, H: Handle> TextureAtlas<'a, H> {
+    /// Creates an empty [TextureAtlas] with pages of the provided size, in the `Rgba8UnormSrgb`
+    /// format, without padding or extrusion.
+    #[inline]
+    pub fn new(handle: &'a H, page_size: Dimension) -> Self {
+        Self {
+            handle,
+            descriptor: TextureDescriptor::new(page_size),
+            padding: 0,
+            extrusion: 0,
+            pages: Vec::new(),
+            regions: HashMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Sets the [TextureFormat] of the pages, which the data of inserted images is expected in.
+    ///
+    /// Only affects the pages created afterwards: images inserted afterwards are only packed into
+    /// pages of the new format.
+    #[inline]
+    pub fn with_format(mut self, format: TextureFormat) -> Self {
+        self.descriptor = self.descriptor.with_format(format);
+        self
+    }
+
+    /// Sets the number of transparent pixels left between images.
+    #[inline]
+    pub fn with_padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Sets the number of times the edge pixels of images are repeated around them.
+    #[inline]
+    pub fn with_extrusion(mut self, extrusion: u32) -> Self {
+        self.extrusion = extrusion;
+        self
+    }
+
+    /// Returns the size of the pages of this atlas.
+    #[inline]
+    pub fn page_size(&self) -> Dimension {
+        self.descriptor.size
+    }
+
+    /// Returns the page at *index*, if it exists.
+    #[inline]
+    pub fn page(&self, index: usize) -> Option<&OwnedTexture<'a, H>> {
+        self.pages.get(index).map(|page| &page.texture)
+    }
+
+    /// Returns the number of pages of this atlas.
+    #[inline]
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Returns the region with the provided id, if it hasn't been removed.
+    #[inline]
+    pub fn region(&self, id: AtlasRegionId) -> Option<AtlasRegion> {
+        self.regions.get(&id).map(|(region, _)| *region)
+    }
+
+    /// Returns the number of images in this atlas.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// Returns `true` if this atlas holds no images.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Packs an image of the provided size into this atlas, returning its region.
+    ///
+    /// The data is expected to be in the [TextureFormat] set with [TextureAtlas::with_format],
+    /// and the image is packed into a page of that format.
+    ///
+    /// # Fails
+    /// - Fails if the image has a width or height of zero.
+    /// - Fails if the data is too big or small for the provided size.
+    /// - Fails if the image, with its padding and extrusion, is larger than a page.
+    pub fn insert(&mut self, size: Dimension, data: &[u8]) -> Result<AtlasRegion, Error> {
+        if size.width == 0 || size.height == 0 {
+            return Err(Error::EmptyAtlasImage(size));
+        }
+
+        let format = self.descriptor.format;
+        let bytes_per_pixel = format.bytes_per_pixel();
+        if data.len() as u32 != size.area() * bytes_per_pixel {
+            return Err(Error::TextureDataSizeMismatch {
+                expected: size.area() * bytes_per_pixel,
+                got: data.len() as u32,
+            });
+        }
+
+        // Allocate room for the extruded image, followed by the padding.
+        let extruded = Dimension::new(
+            size.width + 2 * self.extrusion,
+            size.height + 2 * self.extrusion,
+        );
+        let (width, height) = (
+            extruded.width + self.padding,
+            extruded.height + self.padding,
+        );
+        let page_size = self.descriptor.size;
+        if extruded.width > page_size.width || extruded.height > page_size.height {
+            return Err(Error::TooLargeForAtlas(size));
+        }
+        // Images filling a whole side of a page don't need the padding past the edge.
+        let (width, height) = (width.min(page_size.width), height.min(page_size.height));
+
+        let allocation = self
+            .pages
+            .iter_mut()
+            .enumerate()
+            .filter(|(_, page)| page.format == format)
+            .find_map(|(index, page)| Some((index, page.packer.allocate(width, height)?)));
+        let (page, allocated) = match allocation {
+            Some(allocation) => allocation,
+            None => {
+                let mut packer = MaxRectsPacker::new(page_size);
+                let allocated = packer
+                    .allocate(width, height)
+                    .expect("the image fits in an empty page");
+                self.pages.push(AtlasPage {
+                    texture: self
+                        .handle
+                        .make_blank_texture_with_descriptor(&self.descriptor),
+                    format,
+                    packer,
+                });
+                (self.pages.len() - 1, allocated)
+            }
+        };
+
+        // Write the whole allocated rectangle, so the padding of a rectangle freed by another
+        // image doesn't keep its pixels.
+        let texture = &self.pages[page].texture;
+        let extruded_data = extrude(data, size, self.extrusion, bytes_per_pixel);
+        let (extruded_row_len, allocated_row_len) = (
+            (extruded.width * bytes_per_pixel) as usize,
+            (allocated.width * bytes_per_pixel) as usize,
+        );
+        let mut allocated_data = vec![0; allocated_row_len * allocated.height as usize];
+        for (row, extruded_row) in allocated_data
+            .chunks_exact_mut(allocated_row_len)
+            .zip(extruded_data.chunks_exact(extruded_row_len))
+        {
+            row[..extruded_row_len].copy_from_slice(extruded_row);
+        }
+        self.handle.wgpu_queue().write_texture(
+            wgpu::ImageCopyTexture {
+                texture: texture.wgpu_texture(),
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: allocated.x,
+                    y: allocated.y,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            &allocated_data,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(allocated.width * bytes_per_pixel),
+                rows_per_image: None,
+            },
+            Dimension::new(allocated.width, allocated.height).to_extent_3d(),
+        );
+
+        let id = AtlasRegionId(self.next_id);
+        self.next_id += 1;
+        let rect = Rect::new(
+            (allocated.x + self.extrusion) as f32,
+            (allocated.y + self.extrusion) as f32,
+            size.width as f32,
+            size.height as f32,
+        );
+        let page_size = texture.size();
+        let region = AtlasRegion {
+            id,
+            page,
+            rect,
+            uv_rect: Rect::new(
+                rect.x / page_size.width as f32,
+                rect.y / page_size.height as f32,
+                rect.width / page_size.width as f32,
+                rect.height / page_size.height as f32,
+            ),
+        };
+        self.regions.insert(id, (region, allocated));
+        Ok(region)
+    }
+
+    /// Removes an image from this atlas, freeing its room for other images.  Returns its region,
+    /// or [None] if it was already removed.
+    ///
+    /// The pixels of the image are left in its page until they are overwritten.
+    pub fn remove(&mut self, id: AtlasRegionId) -> Option<AtlasRegion> {
+        let (region, allocated) = self.regions.remove(&id)?;
+        self.pages[region.page].packer.deallocate(allocated);
+        Some(region)
+    }
+
+    /// Removes every image from this atlas, keeping its pages.
+    pub fn clear(&mut self) {
+        self.regions.clear();
+        for page in &mut self.pages {
+            page.packer = MaxRectsPacker::new(page.texture.size());
+        }
+    }
+}
+
+/// Returns the pixels of an image surrounded by *extrusion* copies of its edge pixels.
+///
+/// The image must not be empty.
+fn extrude(data: &[u8], size: Dimension, extrusion: u32, bytes_per_pixel: u32) -> Vec<u8> {
+    if extrusion == 0 {
+        return data.to_vec();
+    }
+
+    let (width, height) = (size.width as usize, size.height as usize);
+    let (extrusion, bytes_per_pixel) = (extrusion as usize, bytes_per_pixel as usize);
+    let extruded_width = width + 2 * extrusion;
+    let mut extruded =
+        Vec::with_capacity(extruded_width * (height + 2 * extrusion) * bytes_per_pixel);
+    for y in 0..height + 2 * extrusion {
+        let row = y.saturating_sub(extrusion).min(height - 1) * width;
+        for x in 0..extruded_width {
+            let index = (row + x.saturating_sub(extrusion).min(width - 1)) * bytes_per_pixel;
+            extruded.extend_from_slice(&data[index..index + bytes_per_pixel]);
+        }
+    }
+    extruded
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::executor::block_on;
+
+    use super::*;
+    use crate::{Color, WindowlessHandle};
+
+    /// A small deterministic pseudo-random generator, so the tests don't need a dependency.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self, bound: u32) -> u32 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            ((self.0 >> 33) % bound as u64) as u32
+        }
+    }
+
+    /// Checks that the allocated rectangles are inside the page and don't overlap each other or
+    /// the free rectangles.
+    fn assert_consistent(packer: &MaxRectsPacker, size: Dimension, allocated: &[PackedRect]) {
+        let page = PackedRect {
+            x: 0,
+            y: 0,
+            width: size.width,
+            height: size.height,
+        };
+        for (i, rect) in allocated.iter().enumerate() {
+            assert!(page.contains(rect), "{rect:?} is outside of the page");
+            for other in &allocated[i + 1..] {
+                assert!(!rect.intersects(other), "{rect:?} overlaps {other:?}");
+            }
+            for free in &packer.free {
+                assert!(!rect.intersects(free), "{rect:?} overlaps free {free:?}");
+            }
+        }
+        for free in &packer.free {
+            assert!(page.contains(free), "free {free:?} is outside of the page");
+        }
+    }
+
+    #[test]
+    fn allocations_never_overlap() {
+        let size = Dimension::new(128, 128);
+        let mut packer = MaxRectsPacker::new(size);
+        let mut allocated = Vec::new();
+        let mut random = Lcg(7);
+
+        for _ in 0..1000 {
+            if !allocated.is_empty() && random.next(3) == 0 {
+                let rect = allocated.swap_remove(random.next(allocated.len() as u32) as usize);
+                packer.deallocate(rect);
+            } else if let Some(rect) = packer.allocate(1 + random.next(24), 1 + random.next(24)) {
+                allocated.push(rect);
+            }
+            assert_consistent(&packer, size, &allocated);
+        }
+    }
+
+    #[test]
+    fn allocations_fill_the_page() {
+        let size = Dimension::new(64, 64);
+        let mut packer = MaxRectsPacker::new(size);
+        let allocated: Vec<_> = (0..16)
+            .map(|_| packer.allocate(16, 16).expect("the page has room left"))
+            .collect();
+
+        assert_consistent(&packer, size, &allocated);
+        assert!(packer.free.is_empty());
+        assert_eq!(packer.allocate(1, 1), None);
+    }
+
+    #[test]
+    fn freed_space_is_reused() {
+        let size = Dimension::new(64, 64);
+        let mut packer = MaxRectsPacker::new(size);
+        let mut allocated: Vec<_> = (0..16).map(|_| packer.allocate(16, 16).unwrap()).collect();
+
+        let freed = allocated.remove(5);
+        packer.deallocate(freed);
+        assert_eq!(packer.allocate(16, 16), Some(freed));
+        assert_eq!(packer.allocate(1, 1), None);
+    }
+
+    #[test]
+    fn freed_neighbours_are_merged() {
+        let size = Dimension::new(64, 64);
+        let mut packer = MaxRectsPacker::new(size);
+        let mut allocated: Vec<_> = (0..16).map(|_| packer.allocate(16, 16).unwrap()).collect();
+
+        // Free everything in a scrambled order: the whole page is free again.
+        let mut random = Lcg(3);
+        while !allocated.is_empty() {
+            let rect = allocated.swap_remove(random.next(allocated.len() as u32) as usize);
+            packer.deallocate(rect);
+        }
+        assert_eq!(
+            packer.allocate(64, 64),
+            Some(PackedRect {
+                x: 0,
+                y: 0,
+                width: 64,
+                height: 64,
+            })
+        );
+    }
+
+    #[test]
+    fn reused_rectangles_clear_their_padding() {
+        let handle = block_on(WindowlessHandle::new()).unwrap();
+        let mut atlas = TextureAtlas::new(&handle, Dimension::new(16, 16)).with_padding(4);
+
+        let large = atlas
+            .insert(Dimension::new(8, 8), &[255; 8 * 8 * 4])
+            .unwrap();
+        atlas.remove(large.id);
+        let small = atlas
+            .insert(Dimension::new(4, 4), &[255; 4 * 4 * 4])
+            .unwrap();
+        assert_eq!(small.rect, Rect::new(0.0, 0.0, 4.0, 4.0));
+
+        // The padding of the small image covers pixels of the large one.
+        let pixels = block_on(atlas.page(0).unwrap().canvas().pixels()).unwrap();
+        assert_eq!(pixels.color_at(3, 3), Some(Color::WHITE));
+        for (x, y) in [(4, 0), (7, 7), (0, 7)] {
+            assert_eq!(pixels.color_at(x, y), Some(Color::TRANSPARENT));
+        }
+    }
+
+    #[test]
+    fn pages_keep_their_format() {
+        let handle = block_on(WindowlessHandle::new()).unwrap();
+        let mut atlas = TextureAtlas::new(&handle, Dimension::new(16, 16));
+        let rgba = atlas
+            .insert(Dimension::new(2, 2), &[255; 2 * 2 * 4])
+            .unwrap();
+
+        // Images in the new format go in a new page, and the old page still takes RGBA images.
+        let mut atlas = atlas.with_format(TextureFormat::R8Unorm);
+        let red = atlas.insert(Dimension::new(2, 2), &[255; 2 * 2]).unwrap();
+        assert_eq!((rgba.page, red.page), (0, 1));
+        assert!(matches!(
+            atlas.insert(Dimension::new(2, 2), &[255; 2 * 2 * 4]),
+            Err(Error::TextureDataSizeMismatch { .. })
+        ));
+
+        let mut atlas = atlas.with_format(TextureFormat::Rgba8UnormSrgb);
+        let rgba = atlas
+            .insert(Dimension::new(2, 2), &[255; 2 * 2 * 4])
+            .unwrap();
+        assert_eq!(rgba.page, 0);
+        assert_eq!(atlas.page_count(), 2);
+    }
+
+    #[test]
+    fn extrude_repeats_edge_pixels() {
+        // A 2x1 image with one byte per pixel.
+        let extruded = extrude(&[1, 2], Dimension::new(2, 1), 1, 1);
+
+        assert_eq!(extruded, [1, 1, 2, 2, 1, 1, 2, 2, 1, 1, 2, 2]);
+        assert_eq!(extrude(&[1, 2], Dimension::new(2, 1), 0, 1), [1, 2]);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::Dimension;
+
+/// The format of the pixels of a [Texture](crate::Texture).
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum TextureFormat {
+    /// Red, green, blue and alpha channels with 8 bits each, in the sRGB color space.
+    #[default]
+    Rgba8UnormSrgb,
+
+    /// Red, green, blue and alpha channels with 8 bits each, storing linear data.
+    Rgba8Unorm,
+
+    /// Blue, green, red and alpha channels with 8 bits each, in the sRGB color space.
+    Bgra8UnormSrgb,
+
+    /// Blue, green, red and alpha channels with 8 bits each, storing linear data.
+    Bgra8Unorm,
+
+    /// Red, green, blue and alpha channels with a 16-bit float each.  Useful for HDR intermediate
+    /// layers.
+    Rgba16Float,
+
+    /// A single 8-bit channel.  Useful for masks and glyph atlases.
+    R8Unorm,
+}
+
+impl TextureFormat {
+    /// Returns the number of bytes a single pixel takes up in this [TextureFormat].
+    #[inline]
+    pub const fn bytes_per_pixel(&self) -> u32 {
+        match self {
+            Self::Rgba8UnormSrgb | Self::Rgba8Unorm | Self::Bgra8UnormSrgb | Self::Bgra8Unorm => 4,
+            Self::Rgba16Float => 8,
+            Self::R8Unorm => 1,
+        }
+    }
+
+    /// Converts this [TextureFormat] to a [`wgpu::TextureFormat`].
+    #[inline]
+    pub const fn to_wgpu_format(&self) -> wgpu::TextureFormat {
+        match self {
+            Self::Rgba8UnormSrgb => wgpu::TextureFormat::Rgba8UnormSrgb,
+            Self::Rgba8Unorm => wgpu::TextureFormat::Rgba8Unorm,
+            Self::Bgra8UnormSrgb => wgpu::TextureFormat::Bgra8UnormSrgb,
+            Self::Bgra8Unorm => wgpu::TextureFormat::Bgra8Unorm,
+            Self::Rgba16Float => wgpu::TextureFormat::Rgba16Float,
+            Self::R8Unorm => wgpu::TextureFormat::R8Unorm,
+        }
+    }
+
+    /// Converts a [`wgpu::TextureFormat`] to a [TextureFormat], returning [None] if G2d doesn't
+    /// support it.
+    #[inline]
+    pub const fn from_wgpu_format(wgpu_format: wgpu::TextureFormat) -> Option<Self> {
+        match wgpu_format {
+            wgpu::TextureFormat::Rgba8UnormSrgb => Some(Self::Rgba8UnormSrgb),
+            wgpu::TextureFormat::Rgba8Unorm => Some(Self::Rgba8Unorm),
+            wgpu::TextureFormat::Bgra8UnormSrgb => Some(Self::Bgra8UnormSrgb),
+            wgpu::TextureFormat::Bgra8Unorm => Some(Self::Bgra8Unorm),
+            wgpu::TextureFormat::Rgba16Float => Some(Self::Rgba16Float),
+            wgpu::TextureFormat::R8Unorm => Some(Self::R8Unorm),
+            _ => None,
+        }
+    }
+}
+
+/// Describes a [Texture](crate::Texture) to create.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct TextureDescriptor {
+    /// The size of the texture, in pixels.
+    pub size: Dimension,
+
+    /// The format of the texture's pixels.
+    pub format: TextureFormat,
+
+    /// Whether the texture has a full chain of mip levels, each half the size of the one before,
+    /// down to 1x1.
+    pub mipmaps: bool,
+}
+
+impl TextureDescriptor {
+    /// Creates a [TextureDescriptor] with the provided size and the default [TextureFormat].
+    #[inline]
+    pub const fn new(size: Dimension) -> Self {
+        Self {
+            size,
+            format: TextureFormat::Rgba8UnormSrgb,
+            mipmaps: false,
+        }
+    }
+
+    /// Sets the [TextureFormat] of this [TextureDescriptor].
+    #[inline]
+    pub const fn with_format(mut self, format: TextureFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets whether the texture has a full chain of mip levels.
+    ///
+    /// Draws only go to the first level: the others are filled in by
+    /// [Handle::generate_mipmaps](crate::Handle::generate_mipmaps).
+    #[inline]
+    pub const fn with_mipmaps(mut self, mipmaps: bool) -> Self {
+        self.mipmaps = mipmaps;
+        self
+    }
+
+    /// Returns the number of mip levels of a texture created from this [TextureDescriptor].
+    #[inline]
+    pub const fn mip_level_count(&self) -> u32 {
+        if self.mipmaps {
+            self.size.max_mip_level_count()
+        } else {
+            1
+        }
+    }
+
+    /// Returns the number of bytes the data of a texture created from this [TextureDescriptor]
+    /// takes up.
+    #[inline]
+    pub const fn data_len(&self) -> u32 {
+        self.size.area() * self.format.bytes_per_pixel()
+    }
+}
@@ -0,0 +1,109 @@
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+
+use crate::{BlendMode, Handle, PipelineKey, PipelineShader, StencilMode};
+
+/// The filter [Handle::generate_mipmaps] downsamples textures with.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum MipmapFilter {
+    /// Averages the pixels covered by each pixel of the smaller level.  Fast, but slightly blurry.
+    #[default]
+    Box,
+
+    /// A Kaiser-windowed sinc filter, keeping the smaller levels sharper at the cost of a wider
+    /// kernel.
+    Kaiser,
+}
+
+/// The uniform of the [PipelineShader::Mipmap] shader.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct MipmapUniform {
+    /// `0` for [MipmapFilter::Box], `1` for [MipmapFilter::Kaiser].
+    filter: u32,
+    _padding: [u32; 3],
+}
+
+/// Fills the mip levels of a [`wgpu::Texture`] after the first, each downsampled from the one
+/// before.
+pub(crate) fn generate_wgpu_mipmaps<H: Handle>(
+    handle: &H,
+    wgpu_texture: &wgpu::Texture,
+    filter: MipmapFilter,
+) {
+    if wgpu_texture.mip_level_count() <= 1 {
+        return;
+    }
+
+    let wgpu_device = handle.wgpu_device();
+    let wgpu_pipeline = handle.pipeline_cache().get(
+        wgpu_device,
+        PipelineKey {
+            shader: PipelineShader::Mipmap,
+            blend_mode: BlendMode::Replace,
+            format: wgpu_texture.format(),
+            sample_count: 1,
+            stencil: StencilMode::None,
+            clipped: false,
+        },
+    );
+    let wgpu_uniform_buffer = wgpu_device.create_buffer_init(&BufferInitDescriptor {
+        label: None,
+        contents: bytemuck::bytes_of(&MipmapUniform {
+            filter: filter as u32,
+            _padding: [0; 3],
+        }),
+        usage: wgpu::BufferUsages::UNIFORM,
+    });
+    let mip_level_view = |level| {
+        wgpu_texture.create_view(&wgpu::TextureViewDescriptor {
+            base_mip_level: level,
+            mip_level_count: Some(1),
+            ..Default::default()
+        })
+    };
+
+    let mut encoder =
+        wgpu_device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    for level in 1..wgpu_texture.mip_level_count() {
+        let wgpu_source_view = mip_level_view(level - 1);
+        let wgpu_target_view = mip_level_view(level);
+        let wgpu_bind_group = wgpu_device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &wgpu_pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu_uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&wgpu_source_view),
+                },
+            ],
+        });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Mipmap Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &wgpu_target_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&wgpu_pipeline);
+        render_pass.set_bind_group(0, &wgpu_bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+
+    // Submit to be drawn.
+    handle
+        .wgpu_queue()
+        .submit(std::iter::once(encoder.finish()));
+}
@@ -1,6 +1,14 @@
+mod atlas;
+mod format;
+mod mipmap;
 mod owned;
+mod resample;
 
+pub use atlas::*;
+pub use format::*;
+pub use mipmap::*;
 pub use owned::*;
+pub use resample::*;
 
 use crate::{Canvas, Dimension, Handle};
 
@@ -21,12 +29,38 @@ pub trait Texture<H: Handle> {
         self.wgpu_texture().usage()
     }
 
+    /// Returns the [`wgpu::TextureFormat`] of this [Texture].
+    #[inline]
+    fn wgpu_texture_format(&self) -> wgpu::TextureFormat {
+        self.wgpu_texture().format()
+    }
+
+    /// Returns the [TextureFormat] of this [Texture], or [None] if G2d doesn't support its
+    /// [`wgpu::TextureFormat`].
+    #[inline]
+    fn format(&self) -> Option<TextureFormat> {
+        TextureFormat::from_wgpu_format(self.wgpu_texture_format())
+    }
+
+    /// Returns the number of bytes a single pixel of this [Texture] takes up, or [None] if G2d
+    /// doesn't support its format.
+    #[inline]
+    fn bytes_per_pixel(&self) -> Option<u32> {
+        self.format().map(|format| format.bytes_per_pixel())
+    }
+
     /// Returns the size of this [Texture], in pixels.
     #[inline]
     fn size(&self) -> Dimension {
         Dimension::new(self.wgpu_texture().width(), self.wgpu_texture().height())
     }
 
+    /// Returns the number of mip levels of this [Texture].
+    #[inline]
+    fn mip_level_count(&self) -> u32 {
+        self.wgpu_texture().mip_level_count()
+    }
+
     /// Creates a [Canvas] for drawing to this [Texture].
     #[inline]
     fn canvas(&self) -> Canvas<'_, H> {
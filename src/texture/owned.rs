@@ -17,6 +17,12 @@ impl<'a, H: Handle> OwnedTexture<'a, H> {
             wgpu_texture,
         }
     }
+
+    /// Returns the [`wgpu::Texture`] of this [OwnedTexture], consuming it.
+    #[inline]
+    pub fn into_wgpu_texture(self) -> wgpu::Texture {
+        self.wgpu_texture
+    }
 }
 
 impl<'a, H: Handle> Texture<H> for OwnedTexture<'a, H> {
@@ -0,0 +1,271 @@
+use wgpu::util::{BufferInitDescriptor, DeviceExt};
+
+use crate::{BlendMode, Dimension, Handle, PipelineKey, PipelineShader, PixelRect, StencilMode};
+
+/// The filter [Handle::blit](crate::Handle::blit) scales textures with.
+///
+/// When downscaling, every filter but [ResampleFilter::Nearest] is widened to cover all the
+/// source pixels under each destination pixel, so the result doesn't alias.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub enum ResampleFilter {
+    /// Picks the closest source pixel.  Fastest, and keeps hard pixel edges when upscaling.
+    Nearest,
+
+    /// Interpolates linearly between source pixels when upscaling, and averages them when
+    /// downscaling.
+    Bilinear,
+
+    /// The Catmull-Rom cubic filter.  Sharp, with slight halos around hard edges.
+    #[default]
+    CatmullRom,
+
+    /// The Mitchell-Netravali cubic filter.  Softer than [ResampleFilter::CatmullRom], with
+    /// fewer halos.
+    Mitchell,
+
+    /// The Lanczos filter with three lobes.  The sharpest, at the cost of the widest kernel and
+    /// the strongest halos.
+    Lanczos3,
+}
+
+/// The uniform of the [PipelineShader::Resample] shader.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ResampleUniform {
+    /// The first and last pixels of the source region, inclusive.
+    source_min: [i32; 2],
+    source_max: [i32; 2],
+    /// The top-left corner of the target region.
+    target_origin: [f32; 2],
+    /// The number of source pixels per target pixel along the axis.
+    scale: f32,
+    /// `0` to resample horizontally, `1` vertically.
+    axis: u32,
+    /// The [ResampleFilter], in declaration order.
+    kind: u32,
+    /// Bit `0` premultiplies the source colors, bit `1` unpremultiplies the resampled colors.
+    flags: u32,
+}
+
+/// Copies a region of a [`wgpu::Texture`] into another at the provided point, without
+/// filtering.  The region may overlap itself if both textures are the same.
+pub(crate) fn copy_wgpu_texture<H: Handle>(
+    handle: &H,
+    src: &wgpu::Texture,
+    src_rect: PixelRect,
+    dst: &wgpu::Texture,
+    [x, y]: [u32; 2],
+) {
+    if src_rect.is_empty() {
+        return;
+    }
+
+    let copy = |encoder: &mut wgpu::CommandEncoder,
+                src: &wgpu::Texture,
+                origin: wgpu::Origin3d,
+                dst: &wgpu::Texture,
+                dst_origin: wgpu::Origin3d| {
+        encoder.copy_texture_to_texture(
+            wgpu::ImageCopyTexture {
+                texture: src,
+                mip_level: 0,
+                origin,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyTexture {
+                texture: dst,
+                mip_level: 0,
+                origin: dst_origin,
+                aspect: wgpu::TextureAspect::All,
+            },
+            src_rect.size().to_extent_3d(),
+        );
+    };
+    let dst_origin = wgpu::Origin3d { x, y, z: 0 };
+
+    let mut encoder = handle
+        .wgpu_device()
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    if std::ptr::eq(src, dst) {
+        // A texture can't be copied onto itself, so go through a temporary texture.
+        let wgpu_temporary_texture =
+            handle
+                .wgpu_device()
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: None,
+                    size: src_rect.size().to_extent_3d(),
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: src.format(),
+                    usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST,
+                    view_formats: &[],
+                });
+        copy(
+            &mut encoder,
+            src,
+            src_rect.to_origin_3d(),
+            &wgpu_temporary_texture,
+            wgpu::Origin3d::ZERO,
+        );
+        copy(
+            &mut encoder,
+            &wgpu_temporary_texture,
+            wgpu::Origin3d::ZERO,
+            dst,
+            dst_origin,
+        );
+    } else {
+        copy(&mut encoder, src, src_rect.to_origin_3d(), dst, dst_origin);
+    }
+
+    // Submit to be copied.
+    handle
+        .wgpu_queue()
+        .submit(std::iter::once(encoder.finish()));
+}
+
+/// Scales a region of a [`wgpu::Texture`] onto a region of another with the provided
+/// [ResampleFilter], replacing the pixels of the destination region.
+///
+/// The source is resampled horizontally into an intermediate `Rgba16Float` texture, then
+/// vertically into the destination, with colors premultiplied in between.
+pub(crate) fn blit_wgpu_texture<H: Handle>(
+    handle: &H,
+    src: &wgpu::Texture,
+    src_rect: PixelRect,
+    dst: &wgpu::Texture,
+    dst_rect: PixelRect,
+    filter: ResampleFilter,
+) {
+    if src_rect.is_empty() || dst_rect.is_empty() {
+        return;
+    }
+
+    let wgpu_device = handle.wgpu_device();
+    let intermediate_size = Dimension::new(dst_rect.width, src_rect.height);
+    let wgpu_intermediate_texture = wgpu_device.create_texture(&wgpu::TextureDescriptor {
+        label: None,
+        size: intermediate_size.to_extent_3d(),
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: wgpu::TextureFormat::Rgba16Float,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    let wgpu_intermediate_view =
+        wgpu_intermediate_texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let first_level_view = |wgpu_texture: &wgpu::Texture| {
+        wgpu_texture.create_view(&wgpu::TextureViewDescriptor {
+            base_mip_level: 0,
+            mip_level_count: Some(1),
+            ..Default::default()
+        })
+    };
+
+    let horizontal = ResampleUniform {
+        source_min: [src_rect.x as i32, src_rect.y as i32],
+        source_max: [
+            (src_rect.x + src_rect.width) as i32 - 1,
+            (src_rect.y + src_rect.height) as i32 - 1,
+        ],
+        target_origin: [0.0, 0.0],
+        scale: src_rect.width as f32 / dst_rect.width as f32,
+        axis: 0,
+        kind: filter as u32,
+        flags: 1,
+    };
+    let vertical = ResampleUniform {
+        source_min: [0, 0],
+        source_max: [
+            intermediate_size.width as i32 - 1,
+            intermediate_size.height as i32 - 1,
+        ],
+        target_origin: [dst_rect.x as f32, dst_rect.y as f32],
+        scale: src_rect.height as f32 / dst_rect.height as f32,
+        axis: 1,
+        kind: filter as u32,
+        flags: 2,
+    };
+
+    let mut encoder =
+        wgpu_device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    for (uniform, wgpu_source_view, wgpu_target_view, format, target_rect) in [
+        (
+            horizontal,
+            first_level_view(src),
+            &wgpu_intermediate_view,
+            wgpu::TextureFormat::Rgba16Float,
+            PixelRect::from_size(intermediate_size),
+        ),
+        (
+            vertical,
+            wgpu_intermediate_texture.create_view(&wgpu::TextureViewDescriptor::default()),
+            &first_level_view(dst),
+            dst.format(),
+            dst_rect,
+        ),
+    ] {
+        let wgpu_pipeline = handle.pipeline_cache().get(
+            wgpu_device,
+            PipelineKey {
+                shader: PipelineShader::Resample,
+                blend_mode: BlendMode::Replace,
+                format,
+                sample_count: 1,
+                stencil: StencilMode::None,
+                clipped: false,
+            },
+        );
+        let wgpu_uniform_buffer = wgpu_device.create_buffer_init(&BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::bytes_of(&uniform),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let wgpu_bind_group = wgpu_device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &wgpu_pipeline.get_bind_group_layout(0),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu_uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&wgpu_source_view),
+                },
+            ],
+        });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Resample Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: wgpu_target_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_pipeline(&wgpu_pipeline);
+        render_pass.set_bind_group(0, &wgpu_bind_group, &[]);
+        render_pass.set_scissor_rect(
+            target_rect.x,
+            target_rect.y,
+            target_rect.width,
+            target_rect.height,
+        );
+        render_pass.draw(0..3, 0..1);
+    }
+
+    // Submit to be drawn.
+    handle
+        .wgpu_queue()
+        .submit(std::iter::once(encoder.finish()));
+}
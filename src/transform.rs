@@ -0,0 +1,136 @@
+use std::ops::Mul;
+
+use crate::Vec2;
+
+/// A 2D affine transformation: a linear map followed by a translation.
+///
+/// A point `p` is transformed to `x_axis * p.x + y_axis * p.y + translation`.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Transform {
+    pub x_axis: Vec2,
+    pub y_axis: Vec2,
+    pub translation: Vec2,
+}
+
+impl Transform {
+    /// The transformation which leaves every point unchanged.
+    pub const IDENTITY: Self = Self::new(
+        Vec2::new(1.0, 0.0),
+        Vec2::new(0.0, 1.0),
+        Vec2::new(0.0, 0.0),
+    );
+
+    /// Creates a new [Transform] from the images of the axes and the translation.
+    #[inline]
+    pub const fn new(x_axis: Vec2, y_axis: Vec2, translation: Vec2) -> Self {
+        Self {
+            x_axis,
+            y_axis,
+            translation,
+        }
+    }
+
+    /// Creates a [Transform] which moves points by *offset*.
+    #[inline]
+    pub const fn translate(offset: Vec2) -> Self {
+        Self::new(Vec2::new(1.0, 0.0), Vec2::new(0.0, 1.0), offset)
+    }
+
+    /// Creates a [Transform] which scales points away from the origin.
+    #[inline]
+    pub const fn scale(scale: Vec2) -> Self {
+        Self::new(
+            Vec2::new(scale.x, 0.0),
+            Vec2::new(0.0, scale.y),
+            Vec2::new(0.0, 0.0),
+        )
+    }
+
+    /// Creates a [Transform] which rotates points around the origin by *angle*, in radians.
+    ///
+    /// As *y* points down, positive angles rotate clockwise.
+    #[inline]
+    pub fn rotate(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(
+            Vec2::new(cos, sin),
+            Vec2::new(-sin, cos),
+            Vec2::new(0.0, 0.0),
+        )
+    }
+
+    /// Returns a [Transform] which applies this transformation, then *other*.
+    #[inline]
+    pub fn then(&self, other: &Transform) -> Self {
+        *other * *self
+    }
+
+    /// Transforms a point.
+    #[inline]
+    pub fn transform_point(&self, point: Vec2) -> Vec2 {
+        self.transform_vector(point) + self.translation
+    }
+
+    /// Transforms a vector, ignoring the translation.
+    #[inline]
+    pub fn transform_vector(&self, vector: Vec2) -> Vec2 {
+        self.x_axis * vector.x + self.y_axis * vector.y
+    }
+
+    /// Returns the determinant of the linear part of this [Transform].
+    #[inline]
+    pub fn determinant(&self) -> f32 {
+        self.x_axis.x * self.y_axis.y - self.y_axis.x * self.x_axis.y
+    }
+
+    /// Returns the [Transform] undoing this one, or [None] if it collapses the plane to a line or
+    /// a point.
+    pub fn inverse(&self) -> Option<Self> {
+        let determinant = self.determinant();
+        if determinant == 0.0 || !determinant.is_finite() {
+            return None;
+        }
+
+        let inverse_determinant = 1.0 / determinant;
+        let x_axis = Vec2::new(self.y_axis.y, -self.x_axis.y) * inverse_determinant;
+        let y_axis = Vec2::new(-self.y_axis.x, self.x_axis.x) * inverse_determinant;
+        let linear = Self::new(x_axis, y_axis, Vec2::default());
+
+        Some(Self::new(
+            x_axis,
+            y_axis,
+            -linear.transform_vector(self.translation),
+        ))
+    }
+
+    /// Returns the columns of the 3x3 matrix of this [Transform], padded for a WGSL `mat3x3<f32>`
+    /// uniform.
+    pub(crate) fn to_wgpu_mat3(self) -> [[f32; 4]; 3] {
+        [
+            [self.x_axis.x, self.x_axis.y, 0.0, 0.0],
+            [self.y_axis.x, self.y_axis.y, 0.0, 0.0],
+            [self.translation.x, self.translation.y, 1.0, 0.0],
+        ]
+    }
+}
+
+impl Default for Transform {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Mul for Transform {
+    type Output = Self;
+
+    /// Composes two transformations, applying *rhs* first.
+    #[inline]
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(
+            self.transform_vector(rhs.x_axis),
+            self.transform_vector(rhs.y_axis),
+            self.transform_point(rhs.translation),
+        )
+    }
+}
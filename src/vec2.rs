@@ -1,3 +1,5 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
 /// A 2D value with direction and magnitude.
 #[derive(bytemuck::Zeroable, bytemuck::Pod, Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
 #[repr(C)]
@@ -9,7 +11,69 @@ pub struct Vec2 {
 impl Vec2 {
     /// Creates a new [Vec2] from the provided *x* and *y* values.
     #[inline]
-    pub fn new(x: f32, y: f32) -> Self {
+    pub const fn new(x: f32, y: f32) -> Self {
         Self { x, y }
     }
+
+    /// Returns the length of this [Vec2].
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.x.hypot(self.y)
+    }
+
+    /// Linearly interpolates between this [Vec2] and *other*.
+    #[inline]
+    pub fn lerp(&self, other: Vec2, t: f32) -> Self {
+        *self + (other - *self) * t
+    }
+}
+
+impl Add for Vec2 {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Vec2 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<f32> for Vec2 {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: f32) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Vec2 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl AddAssign for Vec2 {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Vec2 {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
 }
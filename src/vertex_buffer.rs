@@ -58,4 +58,10 @@ impl<'a, H: Handle> VertexBuffer<'a, H> {
     pub fn len(&self) -> wgpu::BufferAddress {
         self.len
     }
+
+    /// Returns `true` if this [VertexBuffer] contains no [Vertices](Vertex).
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
 }